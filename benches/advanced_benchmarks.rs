@@ -54,6 +54,7 @@ fn generate_test_commits(count: usize) -> Vec<CommitNode> {
                     test_pass_rate: 0.85,
                     quality_trend: vec![0.8, 0.82, 0.85],
                     is_converged: true,
+                    ..Default::default()
                 },
             }
         })
@@ -89,6 +89,7 @@ fn bench_convergence_analysis(c: &mut Criterion) {
                         test_pass_rate: recent_avg,
                         quality_trend: quality_trend.clone(),
                         is_converged,
+                        ..Default::default()
                     })
                 })
             }
@@ -134,6 +135,7 @@ fn bench_serialization(c: &mut Criterion) {
                 test_pass_rate: 1.0,
                 quality_trend: vec![0.8, 0.85, 0.9, 0.92, 0.92],
                 is_converged: true,
+                ..Default::default()
             },
         };
         
@@ -0,0 +1,265 @@
+//! Thread-confined workflow actor for multi-threaded runtimes
+//!
+//! [`GitWorkflowManager`] owns a `git2::Repository` and implements the
+//! `?Send` [`GitWorkflow`] trait, so it cannot be used from `tokio::spawn`ed
+//! tasks or shared between server threads. This module confines it instead:
+//! - A dedicated `gdk-workflow` thread owns the manager and runs its futures
+//!   on a single-threaded runtime
+//! - [`WorkflowHandle`] sends commands over an mpsc channel and awaits each
+//!   reply on a oneshot channel
+//! - Handles are `Clone + Send + Sync` and implement [`SendGitWorkflow`]
+//! - The thread exits once every handle has been dropped
+//!
+//! Commands run one at a time in the order they were sent.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::actor::WorkflowHandle;
+//! use gdk::SendGitWorkflow;
+//!
+//! #[tokio::main(flavor = "multi_thread")]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let workflow = WorkflowHandle::open("./repo")?;
+//!
+//!     let checkpoint = tokio::spawn({
+//!         let workflow = workflow.clone();
+//!         async move { workflow.create_revert_point("before refactor").await }
+//!     });
+//!     let point = checkpoint.await??;
+//!     workflow.revert_to_point(&point).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core::GitWorkflowManager;
+use crate::{CommitNode, ConvergenceMetrics, GdkError, GdkResult, GitWorkflow, RevertPoint, SendGitWorkflow};
+use std::fmt;
+use tokio::sync::{mpsc, oneshot};
+
+type Reply<T> = oneshot::Sender<GdkResult<T>>;
+type ManagerCall = Box<dyn FnOnce(&mut GitWorkflowManager) + Send>;
+
+/// Work the actor thread performs on the manager
+enum Command {
+    CreateCommitNode { message: String, reply: Reply<CommitNode> },
+    CreateRevertPoint { reason: String, reply: Reply<RevertPoint> },
+    BeginSpiral { reply: Reply<RevertPoint> },
+    RevertToPoint { point: Box<RevertPoint>, reply: Reply<()> },
+    AnalyzeConvergence { reply: Reply<ConvergenceMetrics> },
+    UpdateThreadColors { reply: Reply<()> },
+    ValidateCiCd { commit_hash: String, reply: Reply<bool> },
+    Call(ManagerCall),
+}
+
+/// Cloneable, `Send + Sync` handle to a workflow manager on its own thread
+#[derive(Clone)]
+pub struct WorkflowHandle {
+    sender: mpsc::UnboundedSender<Command>,
+    repo_path: String,
+}
+
+impl WorkflowHandle {
+    /// Open the repository at `repo_path` on a new actor thread
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository cannot be opened,
+    /// or an I/O error if the thread or its runtime cannot be started
+    pub fn open(repo_path: &str) -> GdkResult<Self> {
+        Self::spawn(GitWorkflowManager::new(repo_path)?)
+    }
+
+    /// Move an existing manager onto a new actor thread
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the thread or its runtime cannot be started
+    pub fn spawn(mut manager: GitWorkflowManager) -> GdkResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let repo_path = manager.repo_path.clone();
+
+        std::thread::Builder::new()
+            .name("gdk-workflow".to_string())
+            .spawn(move || {
+                runtime.block_on(async {
+                    while let Some(command) = receiver.recv().await {
+                        run_command(&mut manager, command).await;
+                    }
+                });
+                tracing::debug!("Workflow actor for {} stopped", manager.repo_path);
+            })?;
+
+        Ok(Self { sender, repo_path })
+    }
+
+    /// Repository the actor's manager was opened on
+    pub fn repo_path(&self) -> &str {
+        &self.repo_path
+    }
+
+    /// Run a closure against the manager on the actor thread
+    ///
+    /// Use this for manager state not covered by [`SendGitWorkflow`], such
+    /// as the commit history or latest thread colors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the actor thread has stopped
+    pub async fn call<R, F>(&self, f: F) -> GdkResult<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut GitWorkflowManager) -> R + Send + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        let call: ManagerCall = Box::new(move |manager| {
+            let _ = reply.send(Ok(f(manager)));
+        });
+        self.request("call", Command::Call(call), response).await
+    }
+
+    async fn request<T>(
+        &self,
+        operation: &str,
+        command: Command,
+        response: oneshot::Receiver<GdkResult<T>>,
+    ) -> GdkResult<T> {
+        self.sender
+            .send(command)
+            .map_err(|_| actor_stopped(operation))?;
+        response.await.map_err(|_| actor_stopped(operation))?
+    }
+}
+
+impl fmt::Debug for WorkflowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkflowHandle")
+            .field("repo_path", &self.repo_path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl SendGitWorkflow for WorkflowHandle {
+    async fn create_commit_node(&self, message: &str) -> GdkResult<CommitNode> {
+        let (reply, response) = oneshot::channel();
+        let message = message.to_string();
+        self.request("create_commit_node", Command::CreateCommitNode { message, reply }, response)
+            .await
+    }
+
+    async fn create_revert_point(&self, reason: &str) -> GdkResult<RevertPoint> {
+        let (reply, response) = oneshot::channel();
+        let reason = reason.to_string();
+        self.request("create_revert_point", Command::CreateRevertPoint { reason, reply }, response)
+            .await
+    }
+
+    async fn begin_spiral(&self) -> GdkResult<RevertPoint> {
+        let (reply, response) = oneshot::channel();
+        self.request("begin_spiral", Command::BeginSpiral { reply }, response)
+            .await
+    }
+
+    async fn revert_to_point(&self, point: &RevertPoint) -> GdkResult<()> {
+        let (reply, response) = oneshot::channel();
+        let point = Box::new(point.clone());
+        self.request("revert_to_point", Command::RevertToPoint { point, reply }, response)
+            .await
+    }
+
+    async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics> {
+        let (reply, response) = oneshot::channel();
+        self.request("analyze_convergence", Command::AnalyzeConvergence { reply }, response)
+            .await
+    }
+
+    async fn update_thread_colors(&self) -> GdkResult<()> {
+        let (reply, response) = oneshot::channel();
+        self.request("update_thread_colors", Command::UpdateThreadColors { reply }, response)
+            .await
+    }
+
+    async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool> {
+        let (reply, response) = oneshot::channel();
+        let commit_hash = commit_hash.to_string();
+        self.request("validate_ci_cd", Command::ValidateCiCd { commit_hash, reply }, response)
+            .await
+    }
+}
+
+/// Lets an [`AgentWorkflowController`](crate::agent::AgentWorkflowController)
+/// drive a manager that lives on an actor thread
+#[async_trait::async_trait(?Send)]
+impl GitWorkflow for WorkflowHandle {
+    async fn create_commit_node(&mut self, message: &str) -> GdkResult<CommitNode> {
+        SendGitWorkflow::create_commit_node(self, message).await
+    }
+
+    async fn create_revert_point(&mut self, reason: &str) -> GdkResult<RevertPoint> {
+        SendGitWorkflow::create_revert_point(self, reason).await
+    }
+
+    async fn begin_spiral(&mut self) -> GdkResult<RevertPoint> {
+        SendGitWorkflow::begin_spiral(self).await
+    }
+
+    async fn revert_to_point(&mut self, point: &RevertPoint) -> GdkResult<()> {
+        SendGitWorkflow::revert_to_point(self, point).await
+    }
+
+    async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics> {
+        SendGitWorkflow::analyze_convergence(self).await
+    }
+
+    async fn update_thread_colors(&mut self) -> GdkResult<()> {
+        SendGitWorkflow::update_thread_colors(self).await
+    }
+
+    async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool> {
+        SendGitWorkflow::validate_ci_cd(self, commit_hash).await
+    }
+}
+
+/// Execute one command on the actor thread
+///
+/// A caller that stopped waiting simply drops its receiver, so failed
+/// replies are ignored.
+async fn run_command(manager: &mut GitWorkflowManager, command: Command) {
+    match command {
+        Command::CreateCommitNode { message, reply } => {
+            let _ = reply.send(manager.create_commit_node(&message).await);
+        }
+        Command::CreateRevertPoint { reason, reply } => {
+            let _ = reply.send(manager.create_revert_point(&reason).await);
+        }
+        Command::BeginSpiral { reply } => {
+            let _ = reply.send(manager.begin_spiral().await);
+        }
+        Command::RevertToPoint { point, reply } => {
+            let _ = reply.send(manager.revert_to_point(&point).await);
+        }
+        Command::AnalyzeConvergence { reply } => {
+            let _ = reply.send(manager.analyze_convergence().await);
+        }
+        Command::UpdateThreadColors { reply } => {
+            let _ = reply.send(manager.update_thread_colors().await);
+        }
+        Command::ValidateCiCd { commit_hash, reply } => {
+            let _ = reply.send(manager.validate_ci_cd(&commit_hash).await);
+        }
+        Command::Call(call) => call(manager),
+    }
+}
+
+fn actor_stopped(operation: &str) -> GdkError {
+    GdkError::agent_error(
+        "gdk-system",
+        operation,
+        None,
+        "Workflow actor thread has stopped",
+    )
+}
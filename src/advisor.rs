@@ -0,0 +1,506 @@
+//! Structured next-action recommendations for agents
+//!
+//! A [`NextActionAdvisor`] runs a list of [`NextActionRule`]s over an
+//! agent's session and returns typed [`Recommendation`]s, each with a
+//! confidence score and a rationale. The built-in rules draw on:
+//! - The latest convergence analysis (convergence, stalls, pass rate,
+//!   blocking threads and sibling branches)
+//! - Thread colors of the files the session committed
+//! - Budget usage
+//! - Streaks of failed actions
+//!
+//! Thresholds are set through [`AdvisorConfig`], and custom rules can be
+//! added with [`NextActionAdvisor::with_rule`].
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::advisor::{NextAction, NextActionAdvisor, AdvisorConfig};
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let advisor = NextActionAdvisor::with_config(AdvisorConfig {
+//!         failure_streak: 2,
+//!         ..Default::default()
+//!     });
+//!     let mut controller =
+//!         AgentWorkflowController::new(GitWorkflowManager::new("./repo")?).with_advisor(advisor);
+//!     controller.start_agent_session("agent-1").await?;
+//!
+//!     let recommendation = controller.suggest_next_action("agent-1")?;
+//!     if let NextAction::RevertToCheckpoint { commit_hash } = &recommendation.action {
+//!         println!("Reverting to {commit_hash}: {}", recommendation.rationale);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::agent::{AgentAction, AgentSession};
+use crate::budget::BudgetResource;
+use crate::{ConvergenceMetrics, ThreadColor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// What an agent should do next
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum NextAction {
+    /// Run another infinite monkey iteration
+    Continue,
+    /// Create a revert point before continuing
+    Checkpoint,
+    /// Revert to the checkpoint at `commit_hash`
+    RevertToCheckpoint { commit_hash: String },
+    /// Improve these files before the next attempt
+    FixFiles { files: Vec<String> },
+    /// Merge the sibling branch ending at `tip_hash`, forked from `base_hash`
+    MergeBranch { tip_hash: String, base_hash: String },
+    /// Branch off from `from_commit` and try a different approach
+    SpiralBranch { from_commit: Option<String> },
+    /// The session has converged; complete it
+    Complete,
+    /// Stop work; the session's budget for `resource` is spent
+    Stop { resource: BudgetResource },
+}
+
+impl NextAction {
+    /// Upper-case label, as used by the old free-text suggestions
+    pub fn label(&self) -> &'static str {
+        match self {
+            NextAction::Continue => "CONTINUE",
+            NextAction::Checkpoint => "CHECKPOINT",
+            NextAction::RevertToCheckpoint { .. } => "REVERT",
+            NextAction::FixFiles { .. } => "FIX",
+            NextAction::MergeBranch { .. } => "MERGE",
+            NextAction::SpiralBranch { .. } => "SPIRAL",
+            NextAction::Complete => "CONVERGED",
+            NextAction::Stop { .. } => "STOP",
+        }
+    }
+}
+
+/// A suggested action with how sure the rule is and why
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Recommendation {
+    pub action: NextAction,
+    /// Confidence in the recommendation (0.0-1.0)
+    pub confidence: f64,
+    /// Human-readable reason for the recommendation
+    pub rationale: String,
+    /// Name of the rule that produced it
+    pub rule: String,
+}
+
+impl fmt::Display for Recommendation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.action.label(), self.rationale)
+    }
+}
+
+/// Session state the rules look at
+#[derive(Debug, Clone)]
+pub struct AdviceContext<'a> {
+    pub session: &'a AgentSession,
+    /// Completed actions of the session, oldest first
+    pub actions: Vec<&'a AgentAction>,
+    /// Seconds since the session started
+    pub wall_time_secs: u64,
+}
+
+impl AdviceContext<'_> {
+    /// Most recent convergence analysis of the session
+    pub fn latest_convergence(&self) -> Option<&ConvergenceMetrics> {
+        self.session.convergence_history.last()
+    }
+
+    /// Number of failed actions since the last successful one
+    pub fn failure_streak(&self) -> usize {
+        self.actions.iter().rev().take_while(|a| !a.success).count()
+    }
+}
+
+/// A rule that may recommend an action for a session
+pub trait NextActionRule: Send + Sync + fmt::Debug {
+    /// Name reported in [`Recommendation::rule`]
+    fn name(&self) -> &str;
+
+    /// Recommendation for `context`, or `None` if the rule does not apply
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation>;
+}
+
+/// Thresholds for the built-in rules
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdvisorConfig {
+    /// Test pass rate below which reverting is recommended
+    pub low_pass_rate: f64,
+    /// Consecutive failed actions after which reverting is recommended
+    pub failure_streak: usize,
+    /// Share of any budget after which a checkpoint is recommended
+    pub budget_warning_share: f64,
+    /// Share of the spiral attempts after which a checkpoint is recommended
+    pub checkpoint_attempt_share: f64,
+    /// Most files listed in a single [`NextAction::FixFiles`]
+    pub max_fix_files: usize,
+}
+
+impl Default for AdvisorConfig {
+    fn default() -> Self {
+        Self {
+            low_pass_rate: 0.5,
+            failure_streak: 3,
+            budget_warning_share: 0.9,
+            checkpoint_attempt_share: 0.5,
+            max_fix_files: 5,
+        }
+    }
+}
+
+/// Runs rules over a session and ranks their recommendations
+#[derive(Debug)]
+pub struct NextActionAdvisor {
+    rules: Vec<Box<dyn NextActionRule>>,
+}
+
+impl Default for NextActionAdvisor {
+    fn default() -> Self {
+        Self::with_config(AdvisorConfig::default())
+    }
+}
+
+impl NextActionAdvisor {
+    /// Advisor without any rules; it always recommends continuing
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Advisor running the built-in rules with `config`
+    pub fn with_config(config: AdvisorConfig) -> Self {
+        Self::empty()
+            .with_rule(BudgetRule {
+                warning_share: config.budget_warning_share,
+            })
+            .with_rule(ConvergedRule)
+            .with_rule(FailureStreakRule {
+                min_streak: config.failure_streak,
+            })
+            .with_rule(LowPassRateRule {
+                threshold: config.low_pass_rate,
+            })
+            .with_rule(StalledRule)
+            .with_rule(BlockingFilesRule {
+                max_files: config.max_fix_files,
+            })
+            .with_rule(SiblingMergeRule)
+            .with_rule(AttemptCheckpointRule {
+                attempt_share: config.checkpoint_attempt_share,
+            })
+    }
+
+    /// Add a rule; on equal confidence, earlier rules win
+    pub fn with_rule(mut self, rule: impl NextActionRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Names of the configured rules, in evaluation order
+    pub fn rule_names(&self) -> Vec<&str> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    /// Every applicable recommendation, most confident first
+    ///
+    /// Falls back to [`NextAction::Continue`] when no rule applies.
+    pub fn recommend(&self, context: &AdviceContext<'_>) -> Vec<Recommendation> {
+        let mut recommendations: Vec<_> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.evaluate(context))
+            .collect();
+        recommendations.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+        if recommendations.is_empty() {
+            recommendations.push(Recommendation {
+                action: NextAction::Continue,
+                confidence: 0.5,
+                rationale: "Execute next iteration of infinite monkey workflow".to_string(),
+                rule: "default".to_string(),
+            });
+        }
+        recommendations
+    }
+}
+
+fn recommend(rule: &impl NextActionRule, action: NextAction, confidence: f64, rationale: String) -> Recommendation {
+    Recommendation {
+        action,
+        confidence: confidence.clamp(0.0, 1.0),
+        rationale,
+        rule: rule.name().to_string(),
+    }
+}
+
+/// Stop once a budget is spent; checkpoint when one is nearly spent
+#[derive(Debug, Clone)]
+pub struct BudgetRule {
+    pub warning_share: f64,
+}
+
+impl NextActionRule for BudgetRule {
+    fn name(&self) -> &str {
+        "budget"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let session = context.session;
+        let (resource, share) = session
+            .budget
+            .used_shares(&session.budget_usage, context.wall_time_secs)
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        if share >= 1.0 {
+            Some(recommend(
+                self,
+                NextAction::Stop { resource },
+                1.0,
+                format!("The {resource} budget is spent"),
+            ))
+        } else if share >= self.warning_share {
+            Some(recommend(
+                self,
+                NextAction::Checkpoint,
+                share,
+                format!("{:.0}% of the {resource} budget is used; save progress", share * 100.0),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Complete the session once the latest analysis reports convergence
+#[derive(Debug, Clone)]
+pub struct ConvergedRule;
+
+impl NextActionRule for ConvergedRule {
+    fn name(&self) -> &str {
+        "converged"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let convergence = context.latest_convergence()?;
+        convergence.is_converged.then(|| {
+            recommend(
+                self,
+                NextAction::Complete,
+                convergence.confidence_score.max(0.9),
+                "Converged; complete the session or start a new spiral branch for further exploration"
+                    .to_string(),
+            )
+        })
+    }
+}
+
+/// Revert after several consecutive failed actions
+#[derive(Debug, Clone)]
+pub struct FailureStreakRule {
+    pub min_streak: usize,
+}
+
+impl NextActionRule for FailureStreakRule {
+    fn name(&self) -> &str {
+        "failure_streak"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let streak = context.failure_streak();
+        if streak < self.min_streak.max(1) {
+            return None;
+        }
+        let checkpoint = context.session.revert_stack.last()?;
+        Some(recommend(
+            self,
+            NextAction::RevertToCheckpoint {
+                commit_hash: checkpoint.commit_hash.clone(),
+            },
+            (0.5 + 0.1 * streak as f64).min(0.95),
+            format!(
+                "The last {streak} actions failed; return to checkpoint '{}'",
+                checkpoint.metadata.reason
+            ),
+        ))
+    }
+}
+
+/// Revert when the test pass rate is low
+#[derive(Debug, Clone)]
+pub struct LowPassRateRule {
+    pub threshold: f64,
+}
+
+impl NextActionRule for LowPassRateRule {
+    fn name(&self) -> &str {
+        "low_pass_rate"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let convergence = context.latest_convergence()?;
+        if convergence.test_pass_rate >= self.threshold {
+            return None;
+        }
+        let checkpoint = context.session.revert_stack.last()?;
+        Some(recommend(
+            self,
+            NextAction::RevertToCheckpoint {
+                commit_hash: checkpoint.commit_hash.clone(),
+            },
+            (1.0 - convergence.test_pass_rate).max(0.5),
+            format!(
+                "Test pass rate {:.2} is below {:.2}; revert to checkpoint '{}'",
+                convergence.test_pass_rate, self.threshold, checkpoint.metadata.reason
+            ),
+        ))
+    }
+}
+
+/// Branch off when quality has plateaued
+#[derive(Debug, Clone)]
+pub struct StalledRule;
+
+impl NextActionRule for StalledRule {
+    fn name(&self) -> &str {
+        "stalled"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let convergence = context.latest_convergence()?;
+        convergence.is_stalled.then(|| {
+            recommend(
+                self,
+                NextAction::SpiralBranch {
+                    from_commit: context.session.current_commit.clone(),
+                },
+                0.8,
+                "Quality has plateaued; branch off and try a different approach".to_string(),
+            )
+        })
+    }
+}
+
+/// Fix files whose threads are red or orange, or block convergence
+#[derive(Debug, Clone)]
+pub struct BlockingFilesRule {
+    pub max_files: usize,
+}
+
+impl NextActionRule for BlockingFilesRule {
+    fn name(&self) -> &str {
+        "blocking_files"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let colors = &context.session.thread_colors;
+        let mut files: BTreeSet<&str> = colors
+            .iter()
+            .filter(|(_, color)| matches!(color, ThreadColor::Red | ThreadColor::Orange))
+            .map(|(path, _)| path.as_str())
+            .collect();
+        if let Some(convergence) = context.latest_convergence() {
+            files.extend(
+                convergence
+                    .thread_convergence
+                    .blocking_files
+                    .iter()
+                    .map(|blocking| blocking.file_path.as_str()),
+            );
+        }
+        if files.is_empty() {
+            return None;
+        }
+
+        let tracked = colors
+            .keys()
+            .map(String::as_str)
+            .chain(files.iter().copied())
+            .collect::<BTreeSet<_>>()
+            .len();
+        let share = files.len() as f64 / tracked as f64;
+        let total = files.len();
+        let files: Vec<String> = files.into_iter().take(self.max_files.max(1)).map(String::from).collect();
+        Some(recommend(
+            self,
+            NextAction::FixFiles { files },
+            0.5 + 0.4 * share,
+            format!("{total} of {tracked} files have red or orange threads or block convergence"),
+        ))
+    }
+}
+
+/// Merge a sibling branch that improved on its base
+#[derive(Debug, Clone)]
+pub struct SiblingMergeRule;
+
+impl NextActionRule for SiblingMergeRule {
+    fn name(&self) -> &str {
+        "sibling_merge"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let convergence = context.latest_convergence()?;
+        let current = context.session.current_commit.as_deref();
+        convergence
+            .siblings
+            .iter()
+            .filter_map(|comparison| {
+                let tip = comparison.best_tip.as_deref()?;
+                let branch = comparison.branches.iter().find(|b| b.tip_hash == tip)?;
+                (Some(tip) != current && branch.delta_from_base > 0.0).then_some((comparison, branch))
+            })
+            .max_by(|a, b| a.1.delta_from_base.total_cmp(&b.1.delta_from_base))
+            .map(|(comparison, branch)| {
+                recommend(
+                    self,
+                    NextAction::MergeBranch {
+                        tip_hash: branch.tip_hash.clone(),
+                        base_hash: comparison.base_hash.clone(),
+                    },
+                    0.5 + branch.delta_from_base.min(0.4),
+                    format!(
+                        "Sibling branch {} improved on its base by {:.2}",
+                        branch.tip_hash, branch.delta_from_base
+                    ),
+                )
+            })
+    }
+}
+
+/// Checkpoint once a session has used a large share of its attempts
+#[derive(Debug, Clone)]
+pub struct AttemptCheckpointRule {
+    pub attempt_share: f64,
+}
+
+impl NextActionRule for AttemptCheckpointRule {
+    fn name(&self) -> &str {
+        "attempt_checkpoint"
+    }
+
+    fn evaluate(&self, context: &AdviceContext<'_>) -> Option<Recommendation> {
+        let session = context.session;
+        let limit = session.max_spiral_attempts as f64 * self.attempt_share;
+        (session.spiral_attempts as f64 > limit).then(|| {
+            recommend(
+                self,
+                NextAction::Checkpoint,
+                0.6,
+                format!(
+                    "{} of {} spiral attempts used; create a revert point before continuing",
+                    session.spiral_attempts, session.max_spiral_attempts
+                ),
+            )
+        })
+    }
+}
@@ -1,10 +1,17 @@
 //! Agent workflow management for GDK system
 //!
 //! This module provides intelligent agent coordination for git workflows:
-//! - Multi-agent session tracking with isolated state
+//! - Multi-agent session tracking with isolated state (see
+//!   [`crate::multi_agent`] for isolated working trees)
 //! - Infinite monkey theorem implementation with convergence detection
 //! - Spiral branching with automatic revert capabilities
-//! - Action logging and statistical analysis
+//! - Pluggable search strategies choosing where each attempt starts
+//! - Change proposers producing a new candidate before each attempt
+//! - Per-session budgets for wall time, validator CPU, commits and reverts
+//! - Typed next-action recommendations from configurable rules
+//! - Optional human approval before converged results are promoted
+//! - Role-based permissions for every action type
+//! - Timed action logging with per-type success rates and latency percentiles
 //! - Quality validation and CI/CD integration
 //!
 //! # Example Usage
@@ -29,10 +36,19 @@
 //! }
 //! ```
 
-use crate::{CommitNode, ConvergenceMetrics, GitWorkflow, RevertPoint, GdkError, GdkResult};
+use crate::advisor::{AdviceContext, NextActionAdvisor, Recommendation};
+use crate::approval::{ApprovalGate, ApprovalRequest, ApprovalStatus};
+use crate::budget::{BudgetRemaining, BudgetResource, BudgetUsage, SessionBudget, ValidatorClock};
+use crate::core::GitWorkflowManager;
+use crate::policy::AccessPolicy;
+use crate::proposer::{ChangeProposer, Proposal, ProposalContext};
+use crate::search::{SearchAttempt, SearchStrategyKind};
+use crate::session::{SessionRecords, SessionState, SessionSummary, DEFAULT_IDLE_TIMEOUT};
+use crate::{CommitNode, ConvergenceMetrics, GitWorkflow, RevertPoint, ThreadColor, GdkError, GdkResult};
+use futures::future::LocalBoxFuture;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 /// Represents an active agent session with workflow state
@@ -67,6 +83,30 @@ pub struct AgentSession {
     pub spiral_attempts: u32,
     /// Maximum allowed spiral attempts before giving up
     pub max_spiral_attempts: u32,
+    /// Strategy choosing where each infinite monkey attempt starts
+    #[serde(default)]
+    pub search_strategy: SearchStrategyKind,
+    /// Highest health score of any attempt in this session
+    #[serde(default)]
+    pub best_attempt_score: Option<f64>,
+    /// Lifecycle state; only active sessions accept workflow operations
+    #[serde(default)]
+    pub state: SessionState,
+    /// Unix timestamp of the session's most recent operation
+    #[serde(default)]
+    pub last_activity: u64,
+    /// Unix timestamp when the session was completed, abandoned or expired
+    #[serde(default)]
+    pub end_time: Option<u64>,
+    /// Resource limits for this session (unlimited by default)
+    #[serde(default)]
+    pub budget: SessionBudget,
+    /// Resources consumed against the budget so far
+    #[serde(default)]
+    pub budget_usage: BudgetUsage,
+    /// Latest thread color of each file committed in this session
+    #[serde(default)]
+    pub thread_colors: BTreeMap<String, ThreadColor>,
 }
 
 /// Represents a single action taken by an agent during workflow execution
@@ -76,6 +116,26 @@ pub struct AgentSession {
 /// - Statistical analysis of agent performance
 /// - Audit trails for compliance and review
 /// - Pattern recognition for optimization
+///
+/// `metadata` holds per-type details, all as strings:
+/// - **InfiniteMonkeyIteration**: `attempt`, `proposal`, `health_score`,
+///   `red_threads`, `validator_ms`, `validator_cpu_secs`, `test_pass_rate`,
+///   `confidence_score`, `converged`, `stalled`
+/// - **QualityValidation**: `health_score`, `red_threads`, `validator_ms`,
+///   `validator_cpu_secs`
+/// - **CiCdValidation**: `ci_passed`, `validator_ms`, `validator_cpu_secs`
+/// - **RevertToPoint**: `checkpoint_reason` when creating a checkpoint,
+///   `revert_reason` when reverting to one
+/// - **ConvergenceCheck**: `test_pass_rate`, `confidence_score`, `converged`,
+///   `stalled`
+/// - **SpiralBranch**: `base_commit`, `branch`
+/// - **ApprovalRequest**: `approval_id`, `target_branch`, `gates_passed`
+/// - **ApprovalDecision**: `approval_id`, `decision`, `approver`, `reason`
+///   for rejections
+///
+/// Actions refused by the controller's [`AccessPolicy`] are logged as
+/// failed, with `permission_denied`, `role` and `required_role`, plus the
+/// `caller` whose role was checked when the controller has one.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AgentAction {
     /// Unique identifier for this specific action
@@ -86,6 +146,15 @@ pub struct AgentAction {
     pub action_type: ActionType,
     /// Unix timestamp when action was initiated
     pub timestamp: u64,
+    /// Unix time in milliseconds when the action was initiated
+    #[serde(default)]
+    pub started_at_ms: u64,
+    /// Unix time in milliseconds when the action finished
+    #[serde(default)]
+    pub ended_at_ms: Option<u64>,
+    /// Time from start to finish in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
     /// Git commit hash before action execution
     pub commit_before: Option<String>,
     /// Git commit hash after action completion
@@ -94,6 +163,9 @@ pub struct AgentAction {
     pub success: bool,
     /// Additional metadata specific to the action type
     pub metadata: HashMap<String, String>,
+    /// Session the action belongs to
+    #[serde(default)]
+    pub session_id: Option<Uuid>,
 }
 
 /// Types of actions that agents can perform in the workflow
@@ -106,7 +178,9 @@ pub struct AgentAction {
 /// - **QualityValidation**: Run quality checks (lint, tests, etc.)
 /// - **CiCdValidation**: Validate through CI/CD pipeline
 /// - **InfiniteMonkeyIteration**: Complete iteration of convergence algorithm
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// - **ApprovalRequest**: Park a converged result for human approval
+/// - **ApprovalDecision**: Approve or reject a parked result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ActionType {
     /// Create a new commit with quality thread analysis
     CommitCreate,
@@ -122,16 +196,24 @@ pub enum ActionType {
     CiCdValidation,
     /// Complete iteration of infinite monkey theorem algorithm
     InfiniteMonkeyIteration,
+    /// Submit a converged result for human approval
+    ApprovalRequest,
+    /// Human approval or rejection of a converged result
+    ApprovalDecision,
 }
 
 /// Multi-agent workflow controller implementing the infinite monkey theorem
 ///
-/// Coordinates multiple AI agents working simultaneously on git workflows:
+/// Coordinates multiple AI agents sharing one git workflow:
 /// - Isolated session management per agent
 /// - Convergence algorithm execution with automatic revert
 /// - Quality validation and CI/CD integration
 /// - Statistical tracking and recommendation engine
 ///
+/// All agents share the workflow's working directory, so one agent's revert
+/// also resets the others' files. To run agents concurrently in isolated
+/// worktrees, use [`crate::multi_agent::MultiAgentController`].
+///
 /// # Type Parameters
 ///
 /// * `T` - Implementation of GitWorkflow trait (typically GitWorkflowManager)
@@ -142,6 +224,7 @@ pub enum ActionType {
 /// use gdk::agent::AgentWorkflowController;
 /// use gdk::core::GitWorkflowManager;
 ///
+/// # async fn run() -> gdk::GdkResult<()> {
 /// let workflow = GitWorkflowManager::new("./repo")?;
 /// let mut controller = AgentWorkflowController::new(workflow);
 ///
@@ -149,11 +232,11 @@ pub enum ActionType {
 /// controller.start_agent_session("agent-1").await?;
 /// controller.start_agent_session("agent-2").await?;
 ///
-/// // Execute convergence workflows in parallel
-/// tokio::join!(
-///     controller.execute_infinite_monkey_workflow("agent-1", 0.8),
-///     controller.execute_infinite_monkey_workflow("agent-2", 0.8)
-/// );
+/// // Agents take turns on the shared working tree
+/// controller.execute_infinite_monkey_workflow("agent-1", 0.8).await?;
+/// controller.execute_infinite_monkey_workflow("agent-2", 0.8).await?;
+/// # Ok(())
+/// # }
 /// ```
 #[derive(Debug)]
 pub struct AgentWorkflowController<T: GitWorkflow> {
@@ -163,6 +246,20 @@ pub struct AgentWorkflowController<T: GitWorkflow> {
     pub active_sessions: HashMap<String, AgentSession>,
     /// Complete history of all agent actions for analysis
     pub action_history: Vec<AgentAction>,
+    /// Change proposers indexed by agent_id
+    pub change_proposers: HashMap<String, Box<dyn ChangeProposer>>,
+    /// Idle time after which live sessions expire (`None` never expires)
+    pub idle_timeout: Option<Duration>,
+    /// Rules behind [`Self::suggest_next_action`]
+    pub advisor: NextActionAdvisor,
+    /// Holds converged results until a human approves them (`None`
+    /// promotes them immediately)
+    pub approval_gate: Option<ApprovalGate>,
+    /// Roles authorizing each action (allows everything by default)
+    pub policy: AccessPolicy,
+    /// Authenticated identity whose role authorizes each action (`None`
+    /// authorizes each agent as itself)
+    pub caller: Option<String>,
 }
 
 impl<T: GitWorkflow> AgentWorkflowController<T> {
@@ -180,9 +277,102 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
             workflow,
             active_sessions: HashMap::new(),
             action_history: Vec::new(),
+            change_proposers: HashMap::new(),
+            idle_timeout: Some(DEFAULT_IDLE_TIMEOUT),
+            advisor: NextActionAdvisor::default(),
+            approval_gate: None,
+            policy: AccessPolicy::default(),
+            caller: None,
         }
     }
 
+    /// Set the idle time after which live sessions expire
+    ///
+    /// Pass `None` to keep sessions alive until they are ended explicitly.
+    pub fn with_idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Replace the rules used to recommend next actions
+    pub fn with_advisor(mut self, advisor: NextActionAdvisor) -> Self {
+        self.advisor = advisor;
+        self
+    }
+
+    /// Require human approval before converged results reach their branch
+    pub fn with_approval_gate(mut self, gate: ApprovalGate) -> Self {
+        self.approval_gate = Some(gate);
+        self
+    }
+
+    /// Authorize actions by the agents' roles in `policy`
+    pub fn with_policy(mut self, policy: AccessPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Run `f` on this controller's sessions, history and settings driving
+    /// `workflow` instead, such as a
+    /// [`RecordingWorkflow`](crate::replay::RecordingWorkflow) around a
+    /// fresh manager
+    ///
+    /// All state moves to the temporary controller and back once `f`
+    /// finishes, so whatever `f` changes is kept. Returns `workflow` and the
+    /// result of `f`.
+    pub async fn with_workflow<U, R>(
+        &mut self,
+        workflow: U,
+        f: impl for<'a> FnOnce(&'a mut AgentWorkflowController<U>) -> LocalBoxFuture<'a, R>,
+    ) -> (U, R)
+    where
+        U: GitWorkflow,
+    {
+        let mut swapped = AgentWorkflowController {
+            workflow,
+            active_sessions: std::mem::take(&mut self.active_sessions),
+            action_history: std::mem::take(&mut self.action_history),
+            change_proposers: std::mem::take(&mut self.change_proposers),
+            idle_timeout: self.idle_timeout,
+            advisor: std::mem::take(&mut self.advisor),
+            approval_gate: self.approval_gate.take(),
+            policy: std::mem::take(&mut self.policy),
+            caller: self.caller.take(),
+        };
+        let result = f(&mut swapped).await;
+
+        let AgentWorkflowController {
+            workflow,
+            active_sessions,
+            action_history,
+            change_proposers,
+            idle_timeout,
+            advisor,
+            approval_gate,
+            policy,
+            caller,
+        } = swapped;
+        self.active_sessions = active_sessions;
+        self.action_history = action_history;
+        self.change_proposers = change_proposers;
+        self.idle_timeout = idle_timeout;
+        self.advisor = advisor;
+        self.approval_gate = approval_gate;
+        self.policy = policy;
+        self.caller = caller;
+        (workflow, result)
+    }
+
+    /// Authorize every action by `identity`'s role rather than the role of
+    /// the agent it names
+    ///
+    /// Front ends acting for someone else set this to the identity they
+    /// authenticated, so naming a more privileged agent grants nothing.
+    pub fn with_caller(mut self, identity: &str) -> Self {
+        self.caller = Some(identity.to_string());
+        self
+    }
+
     /// Start a new agent session with default configuration
     ///
     /// Creates an isolated session for the specified agent with:
@@ -199,36 +389,252 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
     ///
     /// Session UUID for tracking this agent's workflow
     ///
+    /// A completed, abandoned or expired session for the same agent is
+    /// replaced; its actions stay in the history.
+    ///
     /// # Errors
     ///
-    /// Returns error if system time cannot be determined
+    /// Returns error if the agent already has an active or paused session,
+    /// or if system time cannot be determined
     pub async fn start_agent_session(&mut self, agent_id: &str) -> GdkResult<Uuid> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.expire_if_idle(agent_id, now);
+        if let Some(existing) = self.active_sessions.get(agent_id) {
+            if existing.state.is_live() {
+                return Err(GdkError::agent_error(
+                    agent_id,
+                    "session_start",
+                    Some(existing.session_id),
+                    format!("Agent {agent_id} already has a {} session", existing.state),
+                ));
+            }
+        }
+
         let session_id = Uuid::new_v4();
         let session = AgentSession {
             session_id,
             agent_id: agent_id.to_string(),
             workflow: "gdk-workflow".to_string(),
-            start_time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            start_time: now,
             current_commit: None,
             revert_stack: Vec::new(),
             convergence_history: Vec::new(),
             spiral_attempts: 0,
             max_spiral_attempts: 100,
+            search_strategy: SearchStrategyKind::default(),
+            best_attempt_score: None,
+            state: SessionState::Active,
+            last_activity: now,
+            end_time: None,
+            budget: SessionBudget::default(),
+            budget_usage: BudgetUsage::default(),
+            thread_colors: BTreeMap::new(),
         };
 
         self.active_sessions.insert(agent_id.to_string(), session);
         Ok(session_id)
     }
 
+    /// Suspend an active session; operations fail until it is resumed
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session is not found or not active
+    pub fn pause_agent_session(&mut self, agent_id: &str) -> GdkResult<()> {
+        self.ensure_active(agent_id, "session_pause")?.state = SessionState::Paused;
+        Ok(())
+    }
+
+    /// Resume a paused session
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session is not found, not paused, or expired
+    /// while it was paused
+    pub fn resume_agent_session(&mut self, agent_id: &str) -> GdkResult<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.expire_if_idle(agent_id, now);
+        let session = self.get_session_mut(agent_id)?;
+        if session.state != SessionState::Paused {
+            return Err(GdkError::agent_error(
+                agent_id,
+                "session_resume",
+                Some(session.session_id),
+                format!("Agent {agent_id} has a {} session, not a paused one", session.state),
+            ));
+        }
+        session.state = SessionState::Active;
+        session.last_activity = now;
+        Ok(())
+    }
+
+    /// End a session after the agent finished its work
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session is not found or already ended
+    pub fn complete_agent_session(&mut self, agent_id: &str) -> GdkResult<SessionSummary> {
+        self.end_session(agent_id, SessionState::Completed)
+    }
+
+    /// End a session without finishing the agent's work
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session is not found or already ended
+    pub fn abandon_agent_session(&mut self, agent_id: &str) -> GdkResult<SessionSummary> {
+        self.end_session(agent_id, SessionState::Abandoned)
+    }
+
+    /// Expire every live session idle for longer than the idle timeout
+    ///
+    /// Sessions are also expired lazily when an operation touches them.
+    ///
+    /// # Returns
+    ///
+    /// Summaries of the sessions expired by this call
+    pub fn expire_idle_sessions(&mut self) -> GdkResult<Vec<SessionSummary>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut agent_ids: Vec<String> = self.active_sessions.keys().cloned().collect();
+        agent_ids.sort();
+
+        let mut expired = Vec::new();
+        for agent_id in agent_ids {
+            if self.expire_if_idle(&agent_id, now) {
+                expired.push(self.session_summary(&agent_id)?);
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Summarize a session in any state
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the session is not found
+    pub fn session_summary(&self, agent_id: &str) -> GdkResult<SessionSummary> {
+        let session = self.get_session(agent_id)?;
+        let actions: Vec<_> = self
+            .action_history
+            .iter()
+            .filter(|a| a.session_id == Some(session.session_id))
+            .collect();
+
+        Ok(SessionSummary {
+            session_id: session.session_id,
+            agent_id: agent_id.to_string(),
+            state: session.state,
+            start_time: session.start_time,
+            last_activity: session.last_activity,
+            end_time: session.end_time,
+            final_commit: session.current_commit.clone(),
+            converged: session
+                .convergence_history
+                .last()
+                .is_some_and(|c| c.is_converged),
+            spiral_attempts: session.spiral_attempts,
+            total_actions: actions.len(),
+            successful_actions: actions.iter().filter(|a| a.success).count(),
+            best_attempt_score: session.best_attempt_score,
+        })
+    }
+
+    /// Summaries of every known session, sorted by agent id
+    pub fn list_sessions(&self) -> Vec<SessionSummary> {
+        let mut agent_ids: Vec<&String> = self.active_sessions.keys().collect();
+        agent_ids.sort();
+        agent_ids
+            .into_iter()
+            .filter_map(|agent_id| self.session_summary(agent_id).ok())
+            .collect()
+    }
+
+    /// Whether the agent has an active or paused session
+    pub fn has_live_session(&self, agent_id: &str) -> bool {
+        self.active_sessions
+            .get(agent_id)
+            .is_some_and(|session| session.state.is_live())
+    }
+
+    /// Replace sessions and action history with previously saved records
+    pub fn restore_sessions(&mut self, records: SessionRecords) {
+        self.active_sessions = records.sessions;
+        self.action_history = records.action_history;
+    }
+
+    /// Sessions and action history for saving in a
+    /// [`SessionStore`](crate::session::SessionStore)
+    pub fn session_records(&self) -> SessionRecords {
+        SessionRecords {
+            sessions: self.active_sessions.clone(),
+            action_history: self.action_history.clone(),
+        }
+    }
+
+    /// Select the search strategy used by an agent's infinite monkey workflow
+    ///
+    /// Takes effect the next time the workflow is executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the agent session is not found
+    pub fn set_search_strategy(&mut self, agent_id: &str, strategy: SearchStrategyKind) -> GdkResult<()> {
+        self.get_session_mut(agent_id)?.search_strategy = strategy;
+        Ok(())
+    }
+
+    /// Limit the resources an agent's session may consume
+    ///
+    /// Consumption so far is kept, so lowering a limit below it stops the
+    /// session's next budgeted operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the agent session is not found
+    pub fn set_session_budget(&mut self, agent_id: &str, budget: SessionBudget) -> GdkResult<()> {
+        self.get_session_mut(agent_id)?.budget = budget;
+        Ok(())
+    }
+
+    /// Attach a change proposer to an agent's infinite monkey workflow
+    ///
+    /// The proposer is called before every attempt is committed. Without a
+    /// proposer each attempt commits the working tree as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the agent session is not found
+    pub fn set_change_proposer(
+        &mut self,
+        agent_id: &str,
+        proposer: impl ChangeProposer + 'static,
+    ) -> GdkResult<()> {
+        self.get_session(agent_id)?;
+        self.change_proposers
+            .insert(agent_id.to_string(), Box::new(proposer));
+        Ok(())
+    }
+
     /// Execute the infinite monkey theorem convergence algorithm
     ///
     /// Implements the core GDK workflow:
     /// 1. Create initial revert point for safe experimentation
-    /// 2. Iteratively attempt solutions with quality validation
-    /// 3. Revert unsuccessful attempts automatically
-    /// 4. Continue until convergence criteria are met
+    /// 2. Iteratively attempt solutions with quality validation, asking the
+    ///    agent's [`ChangeProposer`] (if any) for a new candidate each time
+    /// 3. Move unsuccessful attempts to the start point chosen by the
+    ///    session's [`SearchStrategyKind`] (the initial point by default)
+    /// 4. Continue until convergence criteria are met, or stop early once
+    ///    the convergence policy reports that quality has plateaued within
+    ///    this spiral or the session's [`SessionBudget`] runs out
     /// 5. Return final converged commit with quality metrics
     ///
+    /// Reverts that restore the initial point when the workflow gives up
+    /// are not charged to the session's revert budget.
+    ///
+    /// With an [`ApprovalGate`], the converged commit is submitted for
+    /// approval and the branch is restored to the initial point until
+    /// [`Self::approve_promotion`] promotes it.
+    ///
     /// # Arguments
     ///
     /// * `agent_id` - Agent to execute workflow for
@@ -242,24 +648,44 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
     ///
     /// Returns [`GdkError::ConvergenceError`] if:
     /// - Maximum spiral attempts reached without convergence
+    /// - Quality stalled within the policy's plateau patience window
+    /// - The change proposer has no candidates left
     /// - Agent session not found
     /// - Git operations fail during iteration
+    ///
+    /// Returns [`GdkError::BudgetExhausted`] if a session budget runs out.
+    ///
+    /// Returns [`GdkError::PermissionDenied`] unless the caller may both run
+    /// spirals and revert, since every spiral may reset the working tree.
     pub async fn execute_infinite_monkey_workflow(
         &mut self,
         agent_id: &str,
         target_convergence: f64,
     ) -> GdkResult<CommitNode> {
-        let initial_revert_point = self
-            .workflow
-            .create_revert_point("infinite_monkey_start")
+        self.ensure_active(agent_id, "infinite_monkey_workflow")?;
+        self.authorize(agent_id, &ActionType::InfiniteMonkeyIteration)
             .await?;
+        self.authorize(agent_id, &ActionType::RevertToPoint).await?;
+        let initial_revert_point = self.workflow.begin_spiral().await?;
 
-        {
+        let mut strategy = {
             let session = self.get_session_mut(agent_id)?;
             session.revert_stack.push(initial_revert_point.clone());
-        }
+            session.search_strategy.build()
+        };
+        let mut proposal_context = ProposalContext {
+            agent_id: agent_id.to_string(),
+            ..Default::default()
+        };
 
         loop {
+            // Stop before starting an attempt the budget cannot pay for
+            if let Err(e) = self.check_budget(agent_id, Some(BudgetResource::Commits)) {
+                tracing::info!("Agent {} stopping: {}", agent_id, e);
+                self.workflow.revert_to_point(&initial_revert_point).await?;
+                return Err(e);
+            }
+
             // Increment spiral attempts and check limits
             let (spiral_attempts, max_attempts) = {
                 let session = self.get_session_mut(agent_id)?;
@@ -277,32 +703,68 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
                 ));
             }
 
-            let action = self
+            let mut action = self
                 .log_action(agent_id, ActionType::InfiniteMonkeyIteration)
                 .await?;
+            action
+                .metadata
+                .insert("attempt".to_string(), spiral_attempts.to_string());
 
-            // Create commit with current state and analyze quality
-            let commit_node = self
-                .workflow
-                .create_commit_node(&format!("Infinite monkey attempt {spiral_attempts}"))
-                .await?;
+            let attempt = async {
+                // Produce a new candidate change before committing it
+                let mut message = format!("Infinite monkey attempt {spiral_attempts}");
+                if let Some(proposer) = self.change_proposers.get_mut(agent_id) {
+                    proposal_context.attempt = spiral_attempts;
+                    match proposer.propose(&proposal_context).await? {
+                        Proposal::Applied { description } => {
+                            message = format!("{message}: {description}");
+                            action.metadata.insert("proposal".to_string(), description);
+                        }
+                        Proposal::Exhausted => return Ok(None),
+                    }
+                }
 
-            // Update session with new commit
-            {
-                let session = self.get_session_mut(agent_id)?;
-                session.current_commit = Some(commit_node.hash.clone());
-            }
+                // Create commit with current state and analyze quality
+                let commit_node = self
+                    .commit_within_budget(agent_id, &message, &mut action)
+                    .await?;
+                proposal_context.record_attempt(&commit_node);
 
-            // Analyze convergence metrics for this iteration
-            let convergence = self.workflow.analyze_convergence().await?;
+                // Update session with new commit
+                {
+                    let session = self.get_session_mut(agent_id)?;
+                    session.current_commit = Some(commit_node.hash.clone());
+                }
 
-            // Store convergence data for trend analysis
-            {
-                let session = self.get_session_mut(agent_id)?;
-                session.convergence_history.push(convergence.clone());
+                // Analyze convergence metrics for this iteration
+                let convergence = self.workflow.analyze_convergence().await?;
+
+                // Store convergence data for trend analysis
+                {
+                    let session = self.get_session_mut(agent_id)?;
+                    session.convergence_history.push(convergence.clone());
+                }
+
+                record_convergence(&mut action, &convergence);
+                Ok(Some((commit_node, convergence)))
             }
+            .await;
 
-            self.complete_action(&action, true, Some(&commit_node.hash))
+            let (commit_node, convergence) = match attempt {
+                Ok(Some(attempt)) => attempt,
+                Ok(None) => {
+                    self.complete_action(action, false, None).await?;
+                    self.workflow.revert_to_point(&initial_revert_point).await?;
+                    return Err(GdkError::convergence_error(
+                        "Change proposer exhausted without convergence",
+                        spiral_attempts - 1,
+                        proposal_context.previous_score().unwrap_or(0.0),
+                        target_convergence,
+                    ));
+                }
+                Err(e) => return Err(self.fail_action(action, None, e).await),
+            };
+            self.complete_action(action, true, Some(&commit_node.hash))
                 .await?;
 
             // Check if convergence criteria are met
@@ -313,17 +775,73 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
                     spiral_attempts,
                     convergence.test_pass_rate
                 );
+                if self.approval_gate.is_some() {
+                    self.submit_for_approval(
+                        agent_id,
+                        &initial_revert_point,
+                        &commit_node,
+                        &convergence,
+                        target_convergence,
+                    )
+                    .await?;
+                }
                 return Ok(commit_node);
             }
 
-            // Revert to starting point for next iteration
+            // Stop early once quality has plateaued instead of burning attempts
+            if convergence.is_stalled {
+                tracing::info!(
+                    "Agent {} stalled after {} attempts (score: {:.3}), stopping early",
+                    agent_id,
+                    spiral_attempts,
+                    convergence.test_pass_rate
+                );
+                self.workflow.revert_to_point(&initial_revert_point).await?;
+                return Err(GdkError::convergence_error(
+                    "Quality plateaued without convergence",
+                    spiral_attempts,
+                    convergence.test_pass_rate,
+                    target_convergence,
+                ));
+            }
+
+            // Let the search strategy pick where the next attempt starts
+            let attempt_point = self
+                .workflow
+                .create_revert_point(&format!("infinite_monkey_attempt_{spiral_attempts}"))
+                .await?;
+            let next_start = strategy.next_start(
+                &initial_revert_point,
+                SearchAttempt {
+                    point: attempt_point,
+                    score: commit_node.health_score,
+                },
+            );
+            {
+                let session = self.get_session_mut(agent_id)?;
+                session.best_attempt_score = strategy.best().map(|best| best.score);
+            }
+
             tracing::debug!(
-                "Agent {} attempt {} failed (score: {:.3}), reverting",
+                "Agent {} attempt {} failed (score: {:.3}), {} continues from {}",
                 agent_id,
                 spiral_attempts,
-                convergence.test_pass_rate
+                convergence.test_pass_rate,
+                strategy.kind().name(),
+                next_start.commit_hash
             );
-            self.workflow.revert_to_point(&initial_revert_point).await?;
+            if next_start.commit_hash != commit_node.hash {
+                match self.revert_within_budget(agent_id, &next_start).await {
+                    Ok(()) => {}
+                    // Like the commit budget, leave the tree as the spiral found it
+                    Err(e @ GdkError::BudgetExhausted { .. }) => {
+                        tracing::info!("Agent {} stopping: {}", agent_id, e);
+                        self.workflow.revert_to_point(&initial_revert_point).await?;
+                        return Err(e);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
         }
     }
 
@@ -348,15 +866,21 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
         agent_id: &str,
         reason: &str,
     ) -> GdkResult<RevertPoint> {
-        let action = self.log_action(agent_id, ActionType::RevertToPoint).await?;
+        let mut action = self.log_action(agent_id, ActionType::RevertToPoint).await?;
+        action
+            .metadata
+            .insert("checkpoint_reason".to_string(), reason.to_string());
 
-        let revert_point = self.workflow.create_revert_point(reason).await?;
+        let revert_point = match self.workflow.create_revert_point(reason).await {
+            Ok(revert_point) => revert_point,
+            Err(e) => return Err(self.fail_action(action, None, e).await),
+        };
 
         if let Some(session) = self.active_sessions.get_mut(agent_id) {
             session.revert_stack.push(revert_point.clone());
         }
 
-        self.complete_action(&action, true, Some(&revert_point.commit_hash))
+        self.complete_action(action, true, Some(&revert_point.commit_hash))
             .await?;
 
         Ok(revert_point)
@@ -378,32 +902,43 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
     ///
     /// Returns error if no revert points are available
     pub async fn revert_to_last_checkpoint(&mut self, agent_id: &str) -> GdkResult<()> {
-        // Pop the most recent revert point from the stack
-        let revert_point = {
-            let session = self.get_session_mut(agent_id)?;
-            session
-                .revert_stack
-                .pop()
-                .ok_or_else(|| GdkError::validation_error(
-                    "No revert points available",
-                    "revert_stack",
-                    format!("Agent {agent_id} has no checkpoints to revert to"),
-                ))?
-        };
+        let mut action = self.log_action(agent_id, ActionType::RevertToPoint).await?;
+        let reverted = async {
+            self.check_budget(agent_id, Some(BudgetResource::Reverts))?;
 
-        let action = self.log_action(agent_id, ActionType::RevertToPoint).await?;
+            // Pop the most recent revert point from the stack
+            let revert_point = {
+                let session = self.get_session_mut(agent_id)?;
+                session
+                    .revert_stack
+                    .pop()
+                    .ok_or_else(|| GdkError::validation_error(
+                        "No revert points available",
+                        "revert_stack",
+                        format!("Agent {agent_id} has no checkpoints to revert to"),
+                    ))?
+            };
 
-        self.workflow.revert_to_point(&revert_point).await?;
+            action
+                .metadata
+                .insert("revert_reason".to_string(), revert_point.metadata.reason.clone());
+            self.revert_within_budget(agent_id, &revert_point).await?;
 
-        {
-            let session = self.get_session_mut(agent_id)?;
-            session.current_commit = Some(revert_point.commit_hash.clone());
+            {
+                let session = self.get_session_mut(agent_id)?;
+                session.current_commit = Some(revert_point.commit_hash.clone());
+            }
+            Ok(revert_point)
         }
+        .await;
 
-        self.complete_action(&action, true, Some(&revert_point.commit_hash))
-            .await?;
-
-        Ok(())
+        match reverted {
+            Ok(revert_point) => {
+                self.complete_action(action, true, Some(&revert_point.commit_hash))
+                    .await
+            }
+            Err(e) => Err(self.fail_action(action, None, e).await),
+        }
     }
 
     /// Validate current state and create commit if quality standards are met
@@ -431,23 +966,51 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
         agent_id: &str,
         message: &str,
     ) -> GdkResult<CommitNode> {
-        let action = self
+        let mut action = self
             .log_action(agent_id, ActionType::QualityValidation)
             .await?;
 
-        self.workflow.update_thread_colors().await?;
-
-        let commit_node = self.workflow.create_commit_node(message).await?;
+        let committed = async {
+            self.workflow.update_thread_colors().await?;
+            self.commit_within_budget(agent_id, message, &mut action)
+                .await
+        }
+        .await;
+        let commit_node = match committed {
+            Ok(commit_node) => commit_node,
+            Err(e) => return Err(self.fail_action(action, None, e).await),
+        };
+        let hash = Some(commit_node.hash.as_str());
 
-        let ci_validation_action = self
+        let mut ci_validation_action = match self
             .log_action(agent_id, ActionType::CiCdValidation)
-            .await?;
-        let ci_success = self.workflow.validate_ci_cd(&commit_node.hash).await?;
+            .await
+        {
+            Ok(ci_validation_action) => ci_validation_action,
+            Err(e) => return Err(self.fail_action(action, hash, e).await),
+        };
+        let clock = ValidatorClock::start();
+        let ci_success = self.workflow.validate_ci_cd(&commit_node.hash).await;
+        let cpu_secs = clock.elapsed_cpu_secs();
+        if let Some(session) = self.active_sessions.get_mut(agent_id) {
+            session.budget_usage.validator_cpu_secs += cpu_secs;
+        }
+        record_validator(&mut ci_validation_action, &clock, cpu_secs);
+        let ci_success = match ci_success {
+            Ok(ci_success) => ci_success,
+            Err(e) => {
+                let e = self.fail_action(ci_validation_action, hash, e).await;
+                return Err(self.fail_action(action, hash, e).await);
+            }
+        };
+        ci_validation_action
+            .metadata
+            .insert("ci_passed".to_string(), ci_success.to_string());
 
         if !ci_success {
-            self.complete_action(&ci_validation_action, false, Some(&commit_node.hash))
+            self.complete_action(ci_validation_action, false, Some(&commit_node.hash))
                 .await?;
-            self.complete_action(&action, false, Some(&commit_node.hash))
+            self.complete_action(action, false, Some(&commit_node.hash))
                 .await?;
             return Err(GdkError::validation_error(
                 "ci_cd_validation",
@@ -460,54 +1023,208 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
             session.current_commit = Some(commit_node.hash.clone());
         }
 
-        self.complete_action(&ci_validation_action, true, Some(&commit_node.hash))
+        self.complete_action(ci_validation_action, true, Some(&commit_node.hash))
             .await?;
-        self.complete_action(&action, true, Some(&commit_node.hash))
+        self.complete_action(action, true, Some(&commit_node.hash))
             .await?;
 
         Ok(commit_node)
     }
 
     pub async fn get_convergence_status(&mut self, agent_id: &str) -> GdkResult<ConvergenceMetrics> {
-        let action = self
+        let mut action = self
             .log_action(agent_id, ActionType::ConvergenceCheck)
             .await?;
 
-        let convergence = self.workflow.analyze_convergence().await?;
+        let convergence = match self.workflow.analyze_convergence().await {
+            Ok(convergence) => convergence,
+            Err(e) => return Err(self.fail_action(action, None, e).await),
+        };
 
         if let Some(session) = self.active_sessions.get_mut(agent_id) {
             session.convergence_history.push(convergence.clone());
         }
 
-        self.complete_action(&action, true, None).await?;
+        record_convergence(&mut action, &convergence);
+        self.complete_action(action, true, None).await?;
 
         Ok(convergence)
     }
 
-    pub async fn suggest_next_action(&self, agent_id: &str) -> GdkResult<String> {
+    /// Recommend what the agent should do next
+    ///
+    /// Returns the most confident recommendation of the controller's
+    /// [`NextActionAdvisor`]; see [`Self::recommend_next_actions`] for all
+    /// of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent has no session
+    pub fn suggest_next_action(&self, agent_id: &str) -> GdkResult<Recommendation> {
+        let mut recommendations = self.recommend_next_actions(agent_id)?;
+        Ok(recommendations.remove(0))
+    }
+
+    /// Every applicable recommendation for the agent, most confident first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent has no session
+    pub fn recommend_next_actions(&self, agent_id: &str) -> GdkResult<Vec<Recommendation>> {
         let session = self.get_session(agent_id)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let context = AdviceContext {
+            session,
+            actions: self
+                .action_history
+                .iter()
+                .filter(|a| a.session_id == Some(session.session_id))
+                .collect(),
+            wall_time_secs: now.saturating_sub(session.start_time),
+        };
+        Ok(self.advisor.recommend(&context))
+    }
 
-        if let Some(latest_convergence) = session.convergence_history.last() {
-            if latest_convergence.is_converged {
-                return Ok(
-                    "CONVERGED: Consider creating a new spiral branch for further exploration"
-                        .to_string(),
-                );
-            }
+    /// Converged results waiting for approval, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ConfigurationError`] without an approval gate
+    pub fn pending_approvals(&self) -> GdkResult<Vec<ApprovalRequest>> {
+        self.require_approval_gate()?.pending()
+    }
 
-            if latest_convergence.test_pass_rate < 0.5 {
-                return Ok(
-                    "REVERT: Test pass rate is low, consider reverting to last checkpoint"
-                        .to_string(),
-                );
-            }
+    /// Promote a pending result to its target branch
+    ///
+    /// The decision is logged as an [`ActionType::ApprovalDecision`] in the
+    /// history of the session that produced the result, even if that
+    /// session has since ended. `approver` must be one of the gate's
+    /// approvers and have a role allowing approval decisions in the
+    /// controller's [`AccessPolicy`]; it is trusted as given, so pass an
+    /// authenticated identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ConfigurationError`] without an approval gate,
+    /// [`GdkError::PermissionDenied`] if `approver` may not decide, and
+    /// [`GdkError::ValidationError`] if the request is not pending or the
+    /// branch cannot be fast-forwarded
+    pub fn approve_promotion(&mut self, approval_id: Uuid, approver: &str) -> GdkResult<ApprovalRequest> {
+        self.decide_promotion(approval_id, approver, |gate| gate.approve(approval_id, approver))
+    }
+
+    /// Reject a pending result, logging `reason` in the session's history
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ConfigurationError`] without an approval gate,
+    /// [`GdkError::PermissionDenied`] if `approver` may not decide, and
+    /// [`GdkError::ValidationError`] if the request is not pending or
+    /// `reason` is empty
+    pub fn reject_promotion(
+        &mut self,
+        approval_id: Uuid,
+        approver: &str,
+        reason: &str,
+    ) -> GdkResult<ApprovalRequest> {
+        self.decide_promotion(approval_id, approver, |gate| {
+            gate.reject(approval_id, approver, reason)
+        })
+    }
+
+    /// Apply an approval decision and log it, or log why it was refused
+    fn decide_promotion(
+        &mut self,
+        approval_id: Uuid,
+        approver: &str,
+        decide: impl FnOnce(&ApprovalGate) -> GdkResult<ApprovalRequest>,
+    ) -> GdkResult<ApprovalRequest> {
+        let gate = self.require_approval_gate()?;
+        let decided = self
+            .policy
+            .authorize(approver, &ActionType::ApprovalDecision)
+            .and_then(|_| decide(gate));
 
-            if session.spiral_attempts > session.max_spiral_attempts / 2 {
-                return Ok("CHECKPOINT: Create a revert point before continuing".to_string());
+        match decided {
+            Ok(request) => {
+                let mut action = decision_action(&request)?;
+                action.commit_after = (request.status == ApprovalStatus::Approved)
+                    .then(|| request.converged_commit.clone());
+                action.success = true;
+                self.action_history.push(action);
+                Ok(request)
             }
+            Err(error @ GdkError::PermissionDenied { .. }) => {
+                let request = gate.get(approval_id)?;
+                let mut action = decision_action(&request)?;
+                action
+                    .metadata
+                    .insert("approver".to_string(), approver.to_string());
+                action
+                    .metadata
+                    .insert("decision".to_string(), "denied".to_string());
+                self.record_denial(&mut action, approver, &error);
+                self.action_history.push(action);
+                Err(error)
+            }
+            Err(error) => Err(error),
         }
+    }
 
-        Ok("CONTINUE: Execute next iteration of infinite monkey workflow".to_string())
+    fn require_approval_gate(&self) -> GdkResult<&ApprovalGate> {
+        self.approval_gate.as_ref().ok_or_else(|| {
+            GdkError::configuration_error(
+                "approval_gate",
+                "No approval gate is configured",
+                Some("Attach one with AgentWorkflowController::with_approval_gate".to_string()),
+            )
+        })
+    }
+
+    /// Park a converged result and restore the spiral's starting point
+    async fn submit_for_approval(
+        &mut self,
+        agent_id: &str,
+        base: &RevertPoint,
+        converged: &CommitNode,
+        convergence: &ConvergenceMetrics,
+        target: f64,
+    ) -> GdkResult<ApprovalRequest> {
+        let mut action = self.log_action(agent_id, ActionType::ApprovalRequest).await?;
+        let session_id = action.session_id;
+        let submitted = async {
+            let request = self
+                .require_approval_gate()?
+                .submit(agent_id, session_id, base, converged, convergence, target)?;
+            self.workflow.revert_to_point(base).await?;
+            self.get_session_mut(agent_id)?.current_commit = Some(base.commit_hash.clone());
+            Ok(request)
+        }
+        .await;
+        let request = match submitted {
+            Ok(request) => request,
+            Err(e) => return Err(self.fail_action(action, Some(&converged.hash), e).await),
+        };
+
+        tracing::info!(
+            "Agent {} result {} awaits approval {} for {}",
+            agent_id,
+            converged.hash,
+            request.id,
+            request.target_branch
+        );
+        action
+            .metadata
+            .insert("approval_id".to_string(), request.id.to_string());
+        action
+            .metadata
+            .insert("target_branch".to_string(), request.target_branch.clone());
+        action.metadata.insert(
+            "gates_passed".to_string(),
+            request.summary.gates_passed().to_string(),
+        );
+        self.complete_action(action, true, Some(&converged.hash)).await?;
+        Ok(request)
     }
 
     fn get_session(&self, agent_id: &str) -> GdkResult<&AgentSession> {
@@ -532,44 +1249,213 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
             ))
     }
 
-    async fn log_action(&mut self, agent_id: &str, action_type: ActionType) -> GdkResult<AgentAction> {
+    /// Fail with [`GdkError::BudgetExhausted`] if the session cannot afford
+    /// another operation needing `needed`
+    fn check_budget(&self, agent_id: &str, needed: Option<BudgetResource>) -> GdkResult<()> {
         let session = self.get_session(agent_id)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let wall_time = now.saturating_sub(session.start_time);
+        match session.budget.overrun(&session.budget_usage, wall_time, needed) {
+            Some(overrun) => Err(GdkError::budget_exhausted(
+                agent_id,
+                overrun.resource.name(),
+                overrun.limit,
+                overrun.used,
+            )),
+            None => Ok(()),
+        }
+    }
 
-        let action = AgentAction {
+    /// Create a commit node, charging the commit and its quality checks
+    ///
+    /// The commit's health and validator timings are recorded on `action`.
+    async fn commit_within_budget(
+        &mut self,
+        agent_id: &str,
+        message: &str,
+        action: &mut AgentAction,
+    ) -> GdkResult<CommitNode> {
+        self.check_budget(agent_id, Some(BudgetResource::Commits))?;
+
+        let clock = ValidatorClock::start();
+        let commit_node = self.workflow.create_commit_node(message).await;
+        let cpu_secs = clock.elapsed_cpu_secs();
+        let usage = &mut self.get_session_mut(agent_id)?.budget_usage;
+        usage.validator_cpu_secs += cpu_secs;
+        record_validator(action, &clock, cpu_secs);
+        let commit_node = commit_node?;
+        usage.commits += 1;
+
+        let session = self.get_session_mut(agent_id)?;
+        for (path, thread) in &commit_node.file_threads {
+            session.thread_colors.insert(path.clone(), thread.color_status.clone());
+        }
+
+        let red_threads = commit_node
+            .file_threads
+            .values()
+            .filter(|thread| thread.color_status == ThreadColor::Red)
+            .count();
+        action
+            .metadata
+            .insert("health_score".to_string(), format!("{:.3}", commit_node.health_score));
+        action
+            .metadata
+            .insert("red_threads".to_string(), red_threads.to_string());
+        Ok(commit_node)
+    }
+
+    /// Revert to `point`, charging the session's revert budget
+    async fn revert_within_budget(&mut self, agent_id: &str, point: &RevertPoint) -> GdkResult<()> {
+        self.check_budget(agent_id, Some(BudgetResource::Reverts))?;
+        self.workflow.revert_to_point(point).await?;
+        self.get_session_mut(agent_id)?.budget_usage.reverts += 1;
+        Ok(())
+    }
+
+    /// Mark a live session expired if it has been idle too long
+    ///
+    /// Returns whether the session was expired by this call.
+    fn expire_if_idle(&mut self, agent_id: &str, now: u64) -> bool {
+        let Some(timeout) = self.idle_timeout else {
+            return false;
+        };
+        let Some(session) = self.active_sessions.get_mut(agent_id) else {
+            return false;
+        };
+        let last_activity = session.last_activity.max(session.start_time);
+        if !session.state.is_live() || now.saturating_sub(last_activity) <= timeout.as_secs() {
+            return false;
+        }
+
+        tracing::info!("Agent {} session expired after idling since {}", agent_id, last_activity);
+        session.state = SessionState::Expired;
+        session.end_time = Some(now);
+        true
+    }
+
+    /// Look up a session that accepts operations and record activity on it
+    fn ensure_active(&mut self, agent_id: &str, operation: &str) -> GdkResult<&mut AgentSession> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.expire_if_idle(agent_id, now);
+        let session = self.get_session_mut(agent_id)?;
+        if session.state != SessionState::Active {
+            return Err(GdkError::agent_error(
+                agent_id,
+                operation,
+                Some(session.session_id),
+                format!("Agent {agent_id} session is {}", session.state),
+            ));
+        }
+        session.last_activity = now;
+        Ok(session)
+    }
+
+    fn end_session(&mut self, agent_id: &str, state: SessionState) -> GdkResult<SessionSummary> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.expire_if_idle(agent_id, now);
+        let session = self.get_session_mut(agent_id)?;
+        if !session.state.is_live() {
+            return Err(GdkError::agent_error(
+                agent_id,
+                "session_end",
+                Some(session.session_id),
+                format!("Agent {agent_id} session already {}", session.state),
+            ));
+        }
+        session.state = state;
+        session.end_time = Some(now);
+        self.change_proposers.remove(agent_id);
+        self.session_summary(agent_id)
+    }
+
+    async fn log_action(&mut self, agent_id: &str, action_type: ActionType) -> GdkResult<AgentAction> {
+        self.authorize(agent_id, &action_type).await?;
+        self.new_action(agent_id, action_type)
+    }
+
+    /// Fail with [`GdkError::PermissionDenied`] if the caller's role (the
+    /// agent's, without a caller) does not allow `action_type`, logging the
+    /// refused action
+    async fn authorize(&mut self, agent_id: &str, action_type: &ActionType) -> GdkResult<()> {
+        let identity = self.caller.as_deref().unwrap_or(agent_id);
+        let Err(error) = self.policy.authorize(identity, action_type) else {
+            return Ok(());
+        };
+
+        tracing::warn!("{}", error);
+        let identity = identity.to_string();
+        let mut action = self.new_action(agent_id, action_type.clone())?;
+        if self.caller.is_some() {
+            action.metadata.insert("caller".to_string(), identity.clone());
+        }
+        self.record_denial(&mut action, &identity, &error);
+        self.complete_action(action, false, None).await?;
+        Err(error)
+    }
+
+    fn record_denial(&self, action: &mut AgentAction, identity: &str, error: &GdkError) {
+        let role = self
+            .policy
+            .role_of(identity)
+            .map_or("none", |role| role.name());
+        action
+            .metadata
+            .insert("permission_denied".to_string(), error.to_string());
+        action.metadata.insert("role".to_string(), role.to_string());
+        action.metadata.insert(
+            "required_role".to_string(),
+            self.policy.required_role(&action.action_type).to_string(),
+        );
+    }
+
+    fn new_action(&mut self, agent_id: &str, action_type: ActionType) -> GdkResult<AgentAction> {
+        let operation = format!("{action_type:?}");
+        let session = self.ensure_active(agent_id, &operation)?;
+        let started_at_ms = unix_millis()?;
+
+        Ok(AgentAction {
             action_id: Uuid::new_v4(),
             agent_id: agent_id.to_string(),
             action_type,
-            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            timestamp: started_at_ms / 1000,
+            started_at_ms,
+            ended_at_ms: None,
+            duration_ms: None,
             commit_before: session.current_commit.clone(),
             commit_after: None,
             success: false,
             metadata: HashMap::new(),
-        };
-
-        Ok(action)
+            session_id: Some(session.session_id),
+        })
     }
 
     async fn complete_action(
         &mut self,
-        action: &AgentAction,
+        mut action: AgentAction,
         success: bool,
         commit_after: Option<&str>,
     ) -> GdkResult<()> {
-        let completed_action = AgentAction {
-            action_id: action.action_id,
-            agent_id: action.agent_id.clone(),
-            action_type: action.action_type.clone(),
-            timestamp: action.timestamp,
-            commit_before: action.commit_before.clone(),
-            commit_after: commit_after.map(|s| s.to_string()),
-            success,
-            metadata: action.metadata.clone(),
-        };
+        let ended_at_ms = unix_millis()?;
+        action.ended_at_ms = Some(ended_at_ms);
+        action.duration_ms = Some(ended_at_ms.saturating_sub(action.started_at_ms));
+        action.commit_after = commit_after.map(|s| s.to_string());
+        action.success = success;
 
-        self.action_history.push(completed_action);
+        self.action_history.push(action);
         Ok(())
     }
 
+    /// Complete `action` as failed with `error` recorded, returning the error
+    /// for the caller to propagate
+    async fn fail_action(&mut self, mut action: AgentAction, commit_after: Option<&str>, error: GdkError) -> GdkError {
+        action.metadata.insert("error".to_string(), error.to_string());
+        if let Err(e) = self.complete_action(action, false, commit_after).await {
+            tracing::warn!("Failed to record failed action: {}", e);
+        }
+        error
+    }
+
     pub fn get_agent_statistics(&self, agent_id: &str) -> GdkResult<AgentStatistics> {
         let session = self.get_session(agent_id)?;
         let agent_actions: Vec<_> = self
@@ -586,6 +1472,26 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
             0.0
         };
 
+        let mut by_type: BTreeMap<&ActionType, Vec<&AgentAction>> = BTreeMap::new();
+        for action in &agent_actions {
+            by_type.entry(&action.action_type).or_default().push(action);
+        }
+        let action_types = by_type
+            .into_iter()
+            .map(|(action_type, actions)| {
+                let successful = actions.iter().filter(|a| a.success).count();
+                ActionTypeStatistics {
+                    action_type: action_type.clone(),
+                    count: actions.len(),
+                    successful,
+                    success_rate: successful as f64 / actions.len() as f64,
+                    latency: LatencyPercentiles::from_durations(
+                        actions.iter().filter_map(|a| a.duration_ms).collect(),
+                    ),
+                }
+            })
+            .collect();
+
         let latest_convergence = session
             .convergence_history
             .last()
@@ -596,6 +1502,7 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
                 test_pass_rate: 0.0,
                 quality_trend: Vec::new(),
                 is_converged: false,
+                ..Default::default()
             });
 
         Ok(AgentStatistics {
@@ -605,10 +1512,51 @@ impl<T: GitWorkflow> AgentWorkflowController<T> {
             spiral_attempts: session.spiral_attempts,
             convergence_state: latest_convergence,
             revert_points_used: session.revert_stack.len(),
+            search_strategy: session.search_strategy.clone(),
+            best_attempt_score: session.best_attempt_score,
+            action_types,
+            budget_usage: session.budget_usage.clone(),
+            budget_remaining: session.budget.remaining(
+                &session.budget_usage,
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)?
+                    .as_secs()
+                    .saturating_sub(session.start_time),
+            ),
         })
     }
 }
 
+impl AgentWorkflowController<GitWorkflowManager> {
+    /// Check out a new spiral branch at `base_commit` for risky experiments
+    ///
+    /// # Returns
+    ///
+    /// Name of the created branch
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::PermissionDenied`] unless the caller's role allows
+    /// [`ActionType::SpiralBranch`], or an error if the branch cannot be
+    /// created
+    pub async fn create_spiral_branch(&mut self, agent_id: &str, base_commit: &str) -> GdkResult<String> {
+        let mut action = self.log_action(agent_id, ActionType::SpiralBranch).await?;
+        action
+            .metadata
+            .insert("base_commit".to_string(), base_commit.to_string());
+
+        let branch = match self.workflow.create_spiral_branch(base_commit).await {
+            Ok(branch) => branch,
+            Err(e) => return Err(self.fail_action(action, None, e).await),
+        };
+
+        self.get_session_mut(agent_id)?.current_commit = Some(base_commit.to_string());
+        action.metadata.insert("branch".to_string(), branch.clone());
+        self.complete_action(action, true, Some(base_commit)).await?;
+        Ok(branch)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentStatistics {
     pub agent_id: String,
@@ -617,4 +1565,115 @@ pub struct AgentStatistics {
     pub spiral_attempts: u32,
     pub convergence_state: ConvergenceMetrics,
     pub revert_points_used: usize,
+    #[serde(default)]
+    pub search_strategy: SearchStrategyKind,
+    #[serde(default)]
+    pub best_attempt_score: Option<f64>,
+    /// Counts, success rates and latencies per action type
+    #[serde(default)]
+    pub action_types: Vec<ActionTypeStatistics>,
+    #[serde(default)]
+    pub budget_usage: BudgetUsage,
+    /// Remaining amount of each limited resource (`None` when unlimited)
+    #[serde(default)]
+    pub budget_remaining: BudgetRemaining,
+}
+
+/// Statistics for one type of action taken by an agent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActionTypeStatistics {
+    pub action_type: ActionType,
+    pub count: usize,
+    pub successful: usize,
+    pub success_rate: f64,
+    /// Duration percentiles; `None` if no action recorded a duration
+    pub latency: Option<LatencyPercentiles>,
+}
+
+/// Nearest-rank percentiles of action durations in milliseconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyPercentiles {
+    /// Percentiles of `durations`, or `None` if it is empty
+    pub fn from_durations(mut durations: Vec<u64>) -> Option<Self> {
+        durations.sort_unstable();
+        let max_ms = *durations.last()?;
+        let percentile = |p: usize| {
+            let rank = (p * durations.len()).div_ceil(100).max(1);
+            durations[rank - 1]
+        };
+        Some(Self {
+            p50_ms: percentile(50),
+            p90_ms: percentile(90),
+            p99_ms: percentile(99),
+            max_ms,
+        })
+    }
+}
+
+/// Unsuccessful [`ActionType::ApprovalDecision`] on `request`, attributed to
+/// the agent and session that produced it
+fn decision_action(request: &ApprovalRequest) -> GdkResult<AgentAction> {
+    let now_ms = unix_millis()?;
+    let mut metadata = HashMap::from([
+        ("approval_id".to_string(), request.id.to_string()),
+        ("decision".to_string(), request.status.to_string()),
+    ]);
+    if let Some(approver) = &request.decided_by {
+        metadata.insert("approver".to_string(), approver.clone());
+    }
+    if let Some(reason) = &request.reason {
+        metadata.insert("reason".to_string(), reason.clone());
+    }
+
+    Ok(AgentAction {
+        action_id: Uuid::new_v4(),
+        agent_id: request.agent_id.clone(),
+        action_type: ActionType::ApprovalDecision,
+        timestamp: now_ms / 1000,
+        started_at_ms: now_ms,
+        ended_at_ms: Some(now_ms),
+        duration_ms: Some(0),
+        commit_before: Some(request.base_commit.clone()),
+        commit_after: None,
+        success: false,
+        metadata,
+        session_id: request.session_id,
+    })
+}
+
+fn unix_millis() -> GdkResult<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64)
+}
+
+/// Record how long a validator ran on `action`
+fn record_validator(action: &mut AgentAction, clock: &ValidatorClock, cpu_secs: f64) {
+    action.metadata.insert(
+        "validator_ms".to_string(),
+        clock.elapsed().as_millis().to_string(),
+    );
+    action
+        .metadata
+        .insert("validator_cpu_secs".to_string(), format!("{cpu_secs:.3}"));
+}
+
+/// Record the outcome of a convergence analysis on `action`
+fn record_convergence(action: &mut AgentAction, convergence: &ConvergenceMetrics) {
+    let metadata = &mut action.metadata;
+    metadata.insert(
+        "test_pass_rate".to_string(),
+        format!("{:.3}", convergence.test_pass_rate),
+    );
+    metadata.insert(
+        "confidence_score".to_string(),
+        format!("{:.3}", convergence.confidence_score),
+    );
+    metadata.insert("converged".to_string(), convergence.is_converged.to_string());
+    metadata.insert("stalled".to_string(), convergence.is_stalled.to_string());
 }
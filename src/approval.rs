@@ -0,0 +1,560 @@
+//! Human approval gate for converged spiral results
+//!
+//! Without a gate, a converged spiral leaves its result checked out on the
+//! working branch. With an [`ApprovalGate`] attached to the controller, a
+//! converged result is instead parked as a pending [`ApprovalRequest`]:
+//! - The branch is reset to where the spiral started
+//! - The converged commit is kept alive under `refs/gdk/approvals/<id>`
+//! - An [`ApprovalSummary`] records diff stats, the quality delta and the
+//!   result of each gate check
+//!
+//! Approving fast-forwards the target branch to the converged commit;
+//! rejecting records the reason. Either decision must come from one of the
+//! identities listed in the repository's `gdk.approver` git config entries,
+//! other than the agent that produced the result. With no approvers
+//! configured, nobody can decide.
+//!
+//! The gate trusts the identity it is given, so callers must authenticate
+//! it: the HTTP API takes it from the request's bearer token and the CLI
+//! uses the OS user.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::approval::ApprovalGate;
+//! use gdk::core::GitWorkflowManager;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let workflow = GitWorkflowManager::new("./repo")?;
+//!     let mut controller =
+//!         AgentWorkflowController::new(workflow).with_approval_gate(ApprovalGate::for_repo("./repo")?);
+//!     controller.start_agent_session("agent-1").await?;
+//!     controller.execute_infinite_monkey_workflow("agent-1", 0.8).await?;
+//!
+//!     for request in controller.pending_approvals()? {
+//!         println!("{} awaiting approval: {:+.2}", request.id, request.summary.quality_delta.unwrap_or(0.0));
+//!         controller.approve_promotion(request.id, "reviewer@example.com")?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::storage::{write_atomic, LockFile, LockFileGuard};
+use crate::validation_cache::git_common_dir;
+use crate::{CommitNode, ConvergenceMetrics, GdkError, GdkResult, GdkResultExt, RevertPoint, ThreadColor};
+use git2::build::CheckoutBuilder;
+use git2::{Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// Where a request stands in the approval workflow
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl fmt::Display for ApprovalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        })
+    }
+}
+
+/// Size of the change between the spiral's base and its converged commit
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Paths touched by the change
+    pub files: Vec<String>,
+}
+
+/// Outcome of one check run before the request was submitted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GateResult {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// What a reviewer needs to decide on a converged result
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ApprovalSummary {
+    pub diff: DiffStats,
+    /// Health of the base commit, if it was analyzed
+    pub base_health: Option<f64>,
+    pub converged_health: f64,
+    /// `converged_health - base_health`
+    pub quality_delta: Option<f64>,
+    pub test_pass_rate: f64,
+    pub confidence_score: f64,
+    pub gates: Vec<GateResult>,
+}
+
+impl ApprovalSummary {
+    /// Whether every gate check passed
+    pub fn gates_passed(&self) -> bool {
+        self.gates.iter().all(|gate| gate.passed)
+    }
+}
+
+/// A converged result waiting for, or closed by, a human decision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApprovalRequest {
+    pub id: Uuid,
+    /// Agent whose spiral produced the result
+    pub agent_id: String,
+    pub session_id: Option<Uuid>,
+    /// Branch the result is promoted to when approved
+    pub target_branch: String,
+    /// Commit the spiral started from
+    pub base_commit: String,
+    pub converged_commit: String,
+    pub message: String,
+    /// Unix timestamp of submission
+    pub submitted_at: u64,
+    pub summary: ApprovalSummary,
+    pub status: ApprovalStatus,
+    /// Identity that approved or rejected the request
+    pub decided_by: Option<String>,
+    pub decided_at: Option<u64>,
+    /// Reason given for a rejection
+    pub reason: Option<String>,
+}
+
+impl ApprovalRequest {
+    /// Ref keeping the converged commit reachable while pending
+    pub fn pending_ref(&self) -> String {
+        format!("refs/gdk/approvals/{}", self.id)
+    }
+}
+
+/// Pending and decided approval requests of one repository
+///
+/// Requests are stored as JSON in the repository's git directory, so every
+/// worktree and every CLI or API caller sees the same queue. Changes to the
+/// queue hold `approvals.json.lock`.
+#[derive(Debug, Clone)]
+pub struct ApprovalGate {
+    repo_path: PathBuf,
+    store_path: PathBuf,
+    approvers: Vec<String>,
+}
+
+impl ApprovalGate {
+    /// Gate for the repository at `repo_path`
+    ///
+    /// Approvers are read from the repository's `gdk.approver` config
+    /// entries (`git config --add gdk.approver alice@example.com`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository or its config cannot
+    /// be read
+    pub fn for_repo(repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let repo = Repository::discover(repo_path.as_ref()).with_git_context("opening repository for approval gate")?;
+        let config = repo.config().with_git_context("reading approver config")?;
+        let mut approvers = Vec::new();
+        if let Ok(mut entries) = config.multivar("gdk.approver", None) {
+            while let Some(entry) = entries.next() {
+                let entry = entry.with_git_context("reading gdk.approver")?;
+                if let Some(value) = entry.value().map(str::trim).filter(|v| !v.is_empty()) {
+                    approvers.push(value.to_string());
+                }
+            }
+        }
+
+        Ok(Self {
+            repo_path: repo_path.as_ref().to_path_buf(),
+            store_path: git_common_dir(&repo).join("gdk").join("approvals.json"),
+            approvers,
+        })
+    }
+
+    /// Replace the configured approvers
+    pub fn with_approvers<I, S>(mut self, approvers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.approvers = approvers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Identities allowed to decide; empty means nobody
+    pub fn approvers(&self) -> &[String] {
+        &self.approvers
+    }
+
+    /// File the requests are stored in
+    pub fn path(&self) -> &Path {
+        &self.store_path
+    }
+
+    /// Every stored request, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or parsed
+    pub fn list(&self) -> GdkResult<Vec<ApprovalRequest>> {
+        let json = match fs::read_to_string(&self.store_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(GdkError::file_system_error(
+                    self.store_path.to_string_lossy(),
+                    "reading approval store",
+                    e,
+                ))
+            }
+        };
+        serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "approval store".to_string(),
+            source: e,
+        })
+    }
+
+    /// Requests still waiting for a decision
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read
+    pub fn pending(&self) -> GdkResult<Vec<ApprovalRequest>> {
+        let mut requests = self.list()?;
+        requests.retain(|request| request.status == ApprovalStatus::Pending);
+        Ok(requests)
+    }
+
+    /// Look up one request
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ValidationError`] if no request has this id
+    pub fn get(&self, id: Uuid) -> GdkResult<ApprovalRequest> {
+        self.list()?
+            .into_iter()
+            .find(|request| request.id == id)
+            .ok_or_else(|| unknown_request(id))
+    }
+
+    /// Park a converged result as a pending request
+    ///
+    /// `base` is the revert point the spiral started from and `target` the
+    /// score it had to reach. The caller is responsible for moving the
+    /// branch back to `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the diff cannot be computed or the request
+    /// cannot be stored
+    pub fn submit(
+        &self,
+        agent_id: &str,
+        session_id: Option<Uuid>,
+        base: &RevertPoint,
+        converged: &CommitNode,
+        convergence: &ConvergenceMetrics,
+        target: f64,
+    ) -> GdkResult<ApprovalRequest> {
+        let repo = self.open()?;
+        let base_health = base.metadata.convergence_state.quality_trend.last().copied();
+        let request = ApprovalRequest {
+            id: Uuid::new_v4(),
+            agent_id: agent_id.to_string(),
+            session_id,
+            target_branch: base.branch_name.clone(),
+            base_commit: base.commit_hash.clone(),
+            converged_commit: converged.hash.clone(),
+            message: converged.message.clone(),
+            submitted_at: unix_now()?,
+            summary: ApprovalSummary {
+                diff: diff_stats(&repo, &base.commit_hash, &converged.hash)?,
+                base_health,
+                converged_health: converged.health_score,
+                quality_delta: base_health.map(|base| converged.health_score - base),
+                test_pass_rate: convergence.test_pass_rate,
+                confidence_score: convergence.confidence_score,
+                gates: gate_results(converged, convergence, target),
+            },
+            status: ApprovalStatus::Pending,
+            decided_by: None,
+            decided_at: None,
+            reason: None,
+        };
+
+        repo.reference(
+            &request.pending_ref(),
+            Oid::from_str(&request.converged_commit)?,
+            true,
+            &format!("gdk: pending approval for {}", request.agent_id),
+        )
+        .with_git_context("creating pending approval ref")?;
+
+        let stored = self.lock().and_then(|_lock| {
+            let mut requests = self.list()?;
+            requests.push(request.clone());
+            self.save(&requests)
+        });
+        if let Err(e) = stored {
+            if let Ok(mut reference) = repo.find_reference(&request.pending_ref()) {
+                let _ = reference.delete();
+            }
+            return Err(e);
+        }
+        Ok(request)
+    }
+
+    /// Approve a pending request and fast-forward its target branch
+    ///
+    /// If the target branch is checked out, the working tree is updated
+    /// too; local modifications that would be overwritten abort the
+    /// promotion.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::PermissionDenied`] if the approver is not
+    /// authorized, and [`GdkError::ValidationError`] if the request is
+    /// unknown or already decided, or the target branch has moved so it
+    /// can no longer be fast-forwarded
+    pub fn approve(&self, id: Uuid, approver: &str) -> GdkResult<ApprovalRequest> {
+        self.decide(id, approver, |request| request.status = ApprovalStatus::Approved)
+    }
+
+    /// Reject a pending request, recording why
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::PermissionDenied`] if the approver is not
+    /// authorized, and [`GdkError::ValidationError`] if the request is
+    /// unknown or already decided, or `reason` is empty
+    pub fn reject(&self, id: Uuid, approver: &str, reason: &str) -> GdkResult<ApprovalRequest> {
+        if reason.trim().is_empty() {
+            return Err(GdkError::validation_error(
+                "approval_reason",
+                format!("Rejecting approval {id}"),
+                "A rejection needs a reason",
+            ));
+        }
+        self.decide(id, approver, |request| {
+            request.status = ApprovalStatus::Rejected;
+            request.reason = Some(reason.trim().to_string());
+        })
+    }
+
+    /// Whether `approver` may decide on `request`
+    ///
+    /// Only configured approvers may, and never on their own work.
+    pub fn is_authorized(&self, approver: &str, request: &ApprovalRequest) -> bool {
+        let approver = approver.trim();
+        !approver.is_empty() && approver != request.agent_id && self.approvers.iter().any(|a| a == approver)
+    }
+
+    /// Record a decision, then promote approved requests
+    ///
+    /// The store stays locked throughout. The decision is saved before the
+    /// branch moves, and put back to pending if the promotion fails, so a
+    /// failed save never leaves a promoted branch behind a pending request.
+    fn decide(
+        &self,
+        id: Uuid,
+        approver: &str,
+        apply: impl FnOnce(&mut ApprovalRequest),
+    ) -> GdkResult<ApprovalRequest> {
+        let _lock = self.lock()?;
+        let undecided = self.list()?;
+        let mut requests = undecided.clone();
+        let request = requests
+            .iter_mut()
+            .find(|request| request.id == id)
+            .ok_or_else(|| unknown_request(id))?;
+        if request.status != ApprovalStatus::Pending {
+            return Err(GdkError::validation_error(
+                "approval_status",
+                format!("Deciding approval {id}"),
+                format!("Approval {id} is already {}", request.status),
+            ));
+        }
+        if !self.is_authorized(approver, request) {
+            return Err(GdkError::permission_denied(
+                approver,
+                format!("deciding approval {id}"),
+                format!("not an approver for work by {}", request.agent_id),
+            ));
+        }
+
+        apply(request);
+        request.decided_by = Some(approver.trim().to_string());
+        request.decided_at = Some(unix_now()?);
+        let decided = request.clone();
+        self.save(&requests)?;
+
+        let repo = match self.open() {
+            Ok(repo) => repo,
+            Err(e) => return self.undo(&undecided, e),
+        };
+        if decided.status == ApprovalStatus::Approved {
+            if let Err(e) = promote(&repo, &decided) {
+                return self.undo(&undecided, e);
+            }
+        }
+
+        // The decision is final now; a leftover ref only keeps the commit alive
+        if let Ok(mut reference) = repo.find_reference(&decided.pending_ref()) {
+            if let Err(e) = reference.delete() {
+                tracing::warn!("Failed to delete {}: {}", decided.pending_ref(), e);
+            }
+        }
+        Ok(decided)
+    }
+
+    /// Restore the requests as they were before a failed decision
+    fn undo(&self, undecided: &[ApprovalRequest], error: GdkError) -> GdkResult<ApprovalRequest> {
+        self.save(undecided)?;
+        Err(error)
+    }
+
+    /// Exclusive lock on the store for the length of a read-modify-write
+    fn lock(&self) -> GdkResult<LockFileGuard> {
+        LockFile::beside(&self.store_path).acquire()
+    }
+
+    fn open(&self) -> GdkResult<Repository> {
+        Repository::discover(&self.repo_path).with_git_context("opening repository for approval gate")
+    }
+
+    fn save(&self, requests: &[ApprovalRequest]) -> GdkResult<()> {
+        let json = serde_json::to_vec_pretty(requests).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "approval store".to_string(),
+            source: e,
+        })?;
+
+        write_atomic(&self.store_path, &json, "approval store")
+    }
+}
+
+/// Fast-forward the request's target branch to its converged commit
+fn promote(repo: &Repository, request: &ApprovalRequest) -> GdkResult<()> {
+    let refname = format!("refs/heads/{}", request.target_branch);
+    let converged = Oid::from_str(&request.converged_commit)?;
+    let current = repo
+        .refname_to_id(&refname)
+        .with_git_context("resolving approval target branch")?;
+    if current != converged && !repo.graph_descendant_of(converged, current)? {
+        return Err(GdkError::validation_error(
+            "approval_fast_forward",
+            format!("Promoting approval {}", request.id),
+            format!(
+                "Branch {} moved to {current}; {} no longer fast-forwards it",
+                request.target_branch, request.converged_commit
+            ),
+        ));
+    }
+
+    let head_is_target = repo.head().ok().and_then(|head| head.name().map(str::to_string)) == Some(refname.clone());
+    if head_is_target {
+        let commit = repo.find_commit(converged)?;
+        repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))
+            .with_git_context("checking out approved commit")?;
+    }
+    repo.reference(
+        &refname,
+        converged,
+        true,
+        &format!("gdk: promote approval {} by {}", request.id, request.agent_id),
+    )
+    .with_git_context("promoting approved commit")?;
+    Ok(())
+}
+
+fn diff_stats(repo: &Repository, base: &str, converged: &str) -> GdkResult<DiffStats> {
+    let base_tree = repo.find_commit(Oid::from_str(base)?)?.tree()?;
+    let converged_tree = repo.find_commit(Oid::from_str(converged)?)?.tree()?;
+    let diff = repo
+        .diff_tree_to_tree(Some(&base_tree), Some(&converged_tree), None)
+        .with_git_context("diffing converged result")?;
+    let stats = diff.stats().with_git_context("computing diff stats")?;
+    let files = diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+
+    Ok(DiffStats {
+        files_changed: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+        files,
+    })
+}
+
+fn gate_results(converged: &CommitNode, convergence: &ConvergenceMetrics, target: f64) -> Vec<GateResult> {
+    let mut red: Vec<&str> = converged
+        .file_threads
+        .values()
+        .filter(|thread| thread.color_status == ThreadColor::Red)
+        .map(|thread| thread.file_path.as_str())
+        .collect();
+    red.sort_unstable();
+    let blocking: Vec<&str> = convergence
+        .thread_convergence
+        .blocking_files
+        .iter()
+        .map(|thread| thread.file_path.as_str())
+        .collect();
+
+    vec![
+        GateResult {
+            name: "convergence".to_string(),
+            passed: convergence.is_converged && convergence.test_pass_rate >= target,
+            detail: format!(
+                "pass rate {:.3} against target {target:.3}, confidence {:.3}",
+                convergence.test_pass_rate, convergence.confidence_score
+            ),
+        },
+        GateResult {
+            name: "red_threads".to_string(),
+            passed: red.is_empty(),
+            detail: if red.is_empty() {
+                "no red threads".to_string()
+            } else {
+                format!("red: {}", red.join(", "))
+            },
+        },
+        GateResult {
+            name: "blocking_threads".to_string(),
+            passed: blocking.is_empty(),
+            detail: if blocking.is_empty() {
+                "no blocking threads".to_string()
+            } else {
+                format!("blocking: {}", blocking.join(", "))
+            },
+        },
+    ]
+}
+
+fn unknown_request(id: Uuid) -> GdkError {
+    GdkError::validation_error(
+        "approval_lookup",
+        format!("Looking up approval {id}"),
+        format!("No approval request {id}"),
+    )
+}
+
+fn unix_now() -> GdkResult<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
@@ -1,8 +1,21 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use gdk::approval::{ApprovalGate, ApprovalRequest};
+use gdk::budget::SessionBudget;
+use gdk::mcp::McpServer;
+use gdk::policy::{AccessPolicy, CommitPolicy};
+use gdk::proposer::{CommandProposer, PatchProposer};
+use gdk::rpc::RpcServer;
+use gdk::search::SearchStrategyKind;
+use gdk::server::{ApiTokens, HttpServer};
+use gdk::replay::{self, Recording, RecordingWorkflow};
+use gdk::session::{SessionStore, SessionSummary};
+use gdk::validation::{ValidationEvent, ValidationSuite};
+use gdk::validation_cache::{git_common_dir, ValidationCacheConfig};
 use gdk::{agent::AgentWorkflowController, core::GitWorkflowManager, visualization::*};
 use std::fs::File;
 use std::io::Write;
+use std::path::PathBuf;
 use tracing::{info, Level};
 
 #[derive(Parser)]
@@ -24,6 +37,18 @@ enum Commands {
     Init {
         #[arg(short, long)]
         agent_id: String,
+        /// Stop the session after this many seconds
+        #[arg(long)]
+        max_wall_time: Option<u64>,
+        /// Stop after validators have used this many CPU seconds
+        #[arg(long)]
+        max_validator_cpu: Option<f64>,
+        /// Stop after this many commits
+        #[arg(long)]
+        max_commits: Option<u32>,
+        /// Stop after this many reverts
+        #[arg(long)]
+        max_reverts: Option<u32>,
     },
     Commit {
         #[arg(short, long)]
@@ -38,6 +63,29 @@ enum Commands {
         max_attempts: u32,
         #[arg(short, long, default_value = "0.8")]
         target_convergence: f64,
+        /// random-restart, hill-climbing, beam-search or simulated-annealing
+        #[arg(long, default_value = "random-restart")]
+        strategy: String,
+        #[arg(long, default_value = "3")]
+        beam_width: usize,
+        #[arg(long, default_value = "0.1")]
+        temperature: f64,
+        #[arg(long, default_value = "0.9")]
+        cooling_rate: f64,
+        #[arg(long, default_value = "1")]
+        seed: u64,
+        /// Shell command that edits the working tree before each attempt
+        #[arg(long, conflicts_with_all = ["patch_dir", "mbox"])]
+        propose_command: Option<String>,
+        /// Directory of *.patch/*.diff files applied one per attempt
+        #[arg(long, conflicts_with = "mbox")]
+        patch_dir: Option<String>,
+        /// Mbox file whose patches are applied one per attempt
+        #[arg(long)]
+        mbox: Option<String>,
+        /// Record every workflow call to this file for later replay
+        #[arg(long)]
+        record: Option<String>,
     },
     Revert {
         #[arg(short, long)]
@@ -60,6 +108,61 @@ enum Commands {
     Suggest {
         #[arg(short, long)]
         agent_id: String,
+        /// List every applicable recommendation, not just the best
+        #[arg(long)]
+        all: bool,
+    },
+    /// Replay a recorded spiral in a scratch clone and report divergences
+    Replay {
+        /// Recording written by `spiral --record`
+        recording: String,
+        /// Directory for the scratch clone (default: .git/gdk/replays/<id>)
+        #[arg(long)]
+        scratch: Option<String>,
+    },
+    /// List, inspect and close agent sessions
+    Sessions {
+        #[command(subcommand)]
+        command: SessionCommands,
+    },
+    /// Review converged results awaiting approval
+    Approvals {
+        #[command(subcommand)]
+        command: ApprovalCommands,
+    },
+    Validate {
+        /// Preset to run: rust, python, node, go or polyglot
+        #[arg(short, long, default_value = "rust")]
+        preset: String,
+        /// Reuse cached validator results for identical trees
+        #[arg(long)]
+        cache: bool,
+        /// Stream validator output lines as they are produced
+        #[arg(long)]
+        show_output: bool,
+    },
+    /// Serve the JSON-RPC 2.0 agent protocol
+    Serve {
+        /// Read requests from stdin and write responses to stdout
+        #[arg(long)]
+        stdio: bool,
+    },
+    /// Serve GDK as Model Context Protocol tools and resources on stdio
+    Mcp,
+    /// Serve the HTTP REST API
+    Server {
+        /// Address to listen on (default: 127.0.0.1:8080, or 0.0.0.0:8080 with --enterprise)
+        #[arg(long)]
+        bind: Option<String>,
+        /// Repository to host as NAME=PATH (repeatable; default: --repo-path as "default")
+        #[arg(long = "repo")]
+        repos: Vec<String>,
+        /// Listen on all interfaces for the enterprise deployment
+        #[arg(long)]
+        enterprise: bool,
+        /// JSON file mapping identities to SHA-256 digests of their bearer tokens
+        #[arg(long)]
+        tokens: Option<String>,
     },
     Visualize {
         #[arg(short, long, default_value = "ascii")]
@@ -77,6 +180,45 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum SessionCommands {
+    /// List every session with its state
+    List,
+    /// Show a session's summary
+    Show {
+        #[arg(short, long)]
+        agent_id: String,
+    },
+    /// End a session and print its summary
+    Close {
+        #[arg(short, long)]
+        agent_id: String,
+        /// Record the session as abandoned rather than completed
+        #[arg(long)]
+        abandon: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApprovalCommands {
+    /// List results awaiting approval
+    List {
+        /// Include approved and rejected results
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show a result's diff stats, quality delta and gate results
+    Show { id: uuid::Uuid },
+    /// Promote a result to its target branch, as the OS user
+    Approve { id: uuid::Uuid },
+    /// Reject a result, recording why, as the OS user
+    Reject {
+        id: uuid::Uuid,
+        #[arg(short, long)]
+        reason: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -86,183 +228,657 @@ async fn main() -> Result<()> {
     } else {
         Level::INFO
     };
-    tracing_subscriber::fmt().with_max_level(level).init();
-
-    let workflow = GitWorkflowManager::new(&cli.repo_path)?;
-    let mut controller = AgentWorkflowController::new(workflow);
-
-    match cli.command {
-        Commands::Init { agent_id } => {
-            let session_id = controller.start_agent_session(&agent_id).await?;
-            info!(
-                "Initialized agent session {} with ID: {}",
-                agent_id, session_id
-            );
-            println!("Agent session initialized: {session_id}");
+    if matches!(cli.command, Commands::Serve { .. } | Commands::Mcp) {
+        // Keep stdout free for protocol messages
+        tracing_subscriber::fmt()
+            .with_max_level(level)
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_max_level(level).init();
+    }
+
+    // .git/gdk/policy.json (if present) limits what commits may change and
+    // what each identity may do; converged spirals wait for a human before
+    // reaching their branch. Roles are those of the OS user, whichever agent
+    // a command names (an unknown user only gets the default role).
+    let commit_policy = CommitPolicy::for_repo(&cli.repo_path)?;
+    let workflow = GitWorkflowManager::new(&cli.repo_path)?.with_commit_policy(commit_policy.clone());
+    let mut controller = AgentWorkflowController::new(workflow)
+        .with_approval_gate(ApprovalGate::for_repo(&cli.repo_path)?)
+        .with_policy(AccessPolicy::for_repo(&cli.repo_path)?);
+    match os_user() {
+        Ok(user) => controller.caller = Some(user),
+        // Nothing to authorize against without a policy
+        Err(_) if controller.policy == AccessPolicy::default() => {}
+        Err(e) => return Err(e.context("An access policy is configured, so the OS user must be known")),
+    }
+
+    // Sessions outlive a single invocation in the repository's git directory
+    let store = SessionStore::for_repo(&cli.repo_path)?;
+    let loaded = store.load()?;
+    controller.restore_sessions(loaded.clone());
+
+    // The servers own the controller and save its sessions when they stop
+    let command = match cli.command {
+        Commands::Serve { stdio } => {
+            if !stdio {
+                anyhow::bail!("Only --stdio transport is supported");
+            }
+            info!("Serving JSON-RPC on stdio for {}", cli.repo_path);
+            let mut server = RpcServer::new(controller);
+            let result = server
+                .serve(tokio::io::BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+                .await;
+            store.save(&server.controller.session_records().changes_since(&loaded))?;
+            return Ok(result?);
         }
 
-        Commands::Commit { agent_id, message } => {
-            let commit_node = controller.validate_and_commit(&agent_id, &message).await?;
-            info!("Created commit: {}", commit_node.hash);
-            println!("Commit created: {}", commit_node.hash);
-            println!("Health score: {:.2}", commit_node.health_score);
-            println!(
-                "Convergence: {}",
-                commit_node.convergence_metrics.is_converged
-            );
+        Commands::Mcp => {
+            info!("Serving MCP on stdio for {}", cli.repo_path);
+            let mut server = McpServer::new(controller);
+            let result = server
+                .serve(tokio::io::BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+                .await;
+            store.save(&server.controller.session_records().changes_since(&loaded))?;
+            return Ok(result?);
         }
 
-        Commands::Spiral {
-            agent_id,
-            max_attempts,
-            target_convergence,
+        Commands::Server {
+            bind,
+            repos,
+            enterprise,
+            tokens,
         } => {
-            info!(
-                "Starting infinite monkey spiral for agent {} (max: {}, target: {})",
-                agent_id, max_attempts, target_convergence
-            );
-
-            // Set max attempts in session
-            if let Some(session) = controller.active_sessions.get_mut(&agent_id) {
-                session.max_spiral_attempts = max_attempts;
+            let mut server = HttpServer::new();
+            if let Some(tokens) = &tokens {
+                server = server.with_tokens(ApiTokens::load(tokens)?);
+            }
+            if repos.is_empty() {
+                server.add_repository("default", controller);
+            }
+            for repo in &repos {
+                let Some((name, path)) = repo.split_once('=') else {
+                    anyhow::bail!("Invalid --repo '{repo}', expected NAME=PATH");
+                };
+                server = server.with_repository(name, path)?;
             }
 
-            match controller
-                .execute_infinite_monkey_workflow(&agent_id, target_convergence)
-                .await
-            {
-                Ok(commit_node) => {
-                    println!("🎉 CONVERGENCE ACHIEVED!");
-                    println!("Final commit: {}", commit_node.hash);
-                    println!("Health score: {:.2}", commit_node.health_score);
-                    println!(
-                        "Attempts: {}",
-                        controller
-                            .active_sessions
-                            .get(&agent_id)
-                            .unwrap()
-                            .spiral_attempts
-                    );
+            let bind = bind.unwrap_or_else(|| {
+                let host = if enterprise { "0.0.0.0" } else { "127.0.0.1" };
+                format!("{host}:8080")
+            });
+            let listener = tokio::net::TcpListener::bind(&bind).await?;
+            info!("Serving HTTP API on {}", listener.local_addr()?);
+            let result = server
+                .serve(listener, async {
+                    tokio::signal::ctrl_c().await.ok();
+                })
+                .await;
+            // Only the --repo-path controller shares the CLI's session store
+            if repos.is_empty() {
+                store.save(&server.repositories["default"].session_records().changes_since(&loaded))?;
+            }
+            return Ok(result?);
+        }
+
+        command => command,
+    };
+
+    // Failed and denied actions, budget usage and revert stacks are saved
+    // even when the command itself fails
+    let result = async {
+        match command {
+            Commands::Init {
+                agent_id,
+                max_wall_time,
+                max_validator_cpu,
+                max_commits,
+                max_reverts,
+            } => {
+                let session_id = controller.start_agent_session(&agent_id).await?;
+                controller.set_session_budget(
+                    &agent_id,
+                    SessionBudget {
+                        max_wall_time_secs: max_wall_time,
+                        max_validator_cpu_secs: max_validator_cpu,
+                        max_commits,
+                        max_reverts,
+                    },
+                )?;
+                info!(
+                    "Initialized agent session {} with ID: {}",
+                    agent_id, session_id
+                );
+                println!("Agent session initialized: {session_id}");
+            }
+
+            Commands::Commit { agent_id, message } => {
+                let commit_node = controller.validate_and_commit(&agent_id, &message).await?;
+                info!("Created commit: {}", commit_node.hash);
+                println!("Commit created: {}", commit_node.hash);
+                println!("Health score: {:.2}", commit_node.health_score);
+                println!(
+                    "Convergence: {}",
+                    commit_node.convergence_metrics.is_converged
+                );
+            }
+
+            Commands::Spiral {
+                agent_id,
+                max_attempts,
+                target_convergence,
+                strategy,
+                beam_width,
+                temperature,
+                cooling_rate,
+                seed,
+                propose_command,
+                patch_dir,
+                mbox,
+                record,
+            } => {
+                let strategy = match strategy.as_str() {
+                    "random-restart" => SearchStrategyKind::RandomRestart,
+                    "hill-climbing" => SearchStrategyKind::HillClimbing,
+                    "beam-search" => SearchStrategyKind::BeamSearch { width: beam_width },
+                    "simulated-annealing" => SearchStrategyKind::SimulatedAnnealing {
+                        initial_temperature: temperature,
+                        cooling_rate,
+                        seed,
+                    },
+                    other => anyhow::bail!("Unknown search strategy: {other}"),
+                };
+
+                info!(
+                    "Starting infinite monkey spiral for agent {} (max: {}, target: {})",
+                    agent_id, max_attempts, target_convergence
+                );
+
+                // Set max attempts and search strategy in session
+                if let Some(session) = controller.active_sessions.get_mut(&agent_id) {
+                    session.max_spiral_attempts = max_attempts;
+                    session.search_strategy = strategy;
+                }
+
+                if let Some(command) = propose_command {
+                    let proposer = CommandProposer::new("sh", &cli.repo_path).arg("-c").arg(command);
+                    controller.set_change_proposer(&agent_id, proposer)?;
+                } else if let Some(patch_dir) = patch_dir {
+                    let proposer = PatchProposer::from_dir(&cli.repo_path, patch_dir)?;
+                    controller.set_change_proposer(&agent_id, proposer)?;
+                } else if let Some(mbox) = mbox {
+                    let proposer = PatchProposer::from_mbox(&cli.repo_path, mbox)?;
+                    controller.set_change_proposer(&agent_id, proposer)?;
                 }
-                Err(e) => {
-                    println!("❌ Failed to converge: {e}");
-                    if let Some(session) = controller.active_sessions.get(&agent_id) {
-                        println!("Attempts made: {}", session.spiral_attempts);
+
+                let outcome = match record {
+                    Some(path) => {
+                        // Run this spiral through a recording workflow, then hand
+                        // the session state back to the main controller
+                        let manager = GitWorkflowManager::new(&cli.repo_path)?.with_commit_policy(commit_policy.clone());
+                        let workflow = RecordingWorkflow::new(manager, &cli.repo_path)?;
+                        let spiral_agent = agent_id.clone();
+                        let (workflow, outcome) = controller
+                            .with_workflow(workflow, move |recorder| {
+                                Box::pin(async move {
+                                    recorder
+                                        .execute_infinite_monkey_workflow(&spiral_agent, target_convergence)
+                                        .await
+                                })
+                            })
+                            .await;
+                        let recording = workflow.into_recording();
+                        recording.save(&path)?;
+                        println!("📼 Recorded {} workflow calls to {path}", recording.steps.len());
+                        outcome
+                    }
+                    None => {
+                        controller
+                            .execute_infinite_monkey_workflow(&agent_id, target_convergence)
+                            .await
+                    }
+                };
+
+                match outcome {
+                    Ok(commit_node) => {
+                        println!("🎉 CONVERGENCE ACHIEVED!");
+                        println!("Final commit: {}", commit_node.hash);
+                        println!("Health score: {:.2}", commit_node.health_score);
+                        println!(
+                            "Attempts: {}",
+                            controller
+                                .active_sessions
+                                .get(&agent_id)
+                                .unwrap()
+                                .spiral_attempts
+                        );
+                        let pending = controller.pending_approvals()?;
+                        if let Some(request) = pending.iter().find(|r| r.converged_commit == commit_node.hash) {
+                            print_approval(request);
+                            println!("⏸️  Awaiting approval: gdk approvals approve {}", request.id);
+                        }
+                    }
+                    Err(e) => {
+                        println!("❌ Failed to converge: {e}");
+                        if let Some(session) = controller.active_sessions.get(&agent_id) {
+                            println!("Attempts made: {}", session.spiral_attempts);
+                        }
                     }
                 }
             }
-        }
 
-        Commands::Revert { agent_id } => {
-            controller.revert_to_last_checkpoint(&agent_id).await?;
-            info!("Reverted agent {} to last checkpoint", agent_id);
-            println!("Reverted to last checkpoint");
-        }
+            Commands::Revert { agent_id } => {
+                controller.revert_to_last_checkpoint(&agent_id).await?;
+                info!("Reverted agent {} to last checkpoint", agent_id);
+                println!("Reverted to last checkpoint");
+            }
 
-        Commands::Checkpoint { agent_id, reason } => {
-            let revert_point = controller
-                .create_spiral_checkpoint(&agent_id, &reason)
-                .await?;
-            info!(
-                "Created checkpoint for agent {}: {}",
-                agent_id, revert_point.commit_hash
-            );
-            println!("Checkpoint created at commit: {}", revert_point.commit_hash);
-        }
+            Commands::Checkpoint { agent_id, reason } => {
+                let revert_point = controller
+                    .create_spiral_checkpoint(&agent_id, &reason)
+                    .await?;
+                info!(
+                    "Created checkpoint for agent {}: {}",
+                    agent_id, revert_point.commit_hash
+                );
+                println!("Checkpoint created at commit: {}", revert_point.commit_hash);
+            }
+
+            Commands::Status { agent_id } => {
+                let convergence = controller.get_convergence_status(&agent_id).await?;
+                println!("=== Agent Status: {agent_id} ===");
+                println!("Converged: {}", convergence.is_converged);
+                println!("Attempts: {}", convergence.attempts);
+                println!("Test pass rate: {:.2}%", convergence.test_pass_rate * 100.0);
+                println!("Successful builds: {}", convergence.successful_builds);
 
-        Commands::Status { agent_id } => {
-            let convergence = controller.get_convergence_status(&agent_id).await?;
-            println!("=== Agent Status: {agent_id} ===");
-            println!("Converged: {}", convergence.is_converged);
-            println!("Attempts: {}", convergence.attempts);
-            println!("Test pass rate: {:.2}%", convergence.test_pass_rate * 100.0);
-            println!("Successful builds: {}", convergence.successful_builds);
-
-            if !convergence.quality_trend.is_empty() {
-                let latest_quality = convergence.quality_trend.last().unwrap();
-                println!("Latest quality: {latest_quality:.2}");
+                if !convergence.quality_trend.is_empty() {
+                    let latest_quality = convergence.quality_trend.last().unwrap();
+                    println!("Latest quality: {latest_quality:.2}");
+                }
             }
-        }
 
-        Commands::Stats { agent_id } => {
-            let stats = controller.get_agent_statistics(&agent_id)?;
-            println!("=== Agent Statistics: {} ===", stats.agent_id);
-            println!("Total actions: {}", stats.total_actions);
-            println!("Success rate: {:.2}%", stats.success_rate * 100.0);
-            println!("Spiral attempts: {}", stats.spiral_attempts);
-            println!("Revert points used: {}", stats.revert_points_used);
-            println!(
-                "Current convergence: {}",
-                stats.convergence_state.is_converged
-            );
-        }
+            Commands::Stats { agent_id } => {
+                let stats = controller.get_agent_statistics(&agent_id)?;
+                println!("=== Agent Statistics: {} ===", stats.agent_id);
+                println!("Total actions: {}", stats.total_actions);
+                println!("Success rate: {:.2}%", stats.success_rate * 100.0);
+                println!("Spiral attempts: {}", stats.spiral_attempts);
+                println!("Revert points used: {}", stats.revert_points_used);
+                println!("Search strategy: {}", stats.search_strategy.name());
+                if let Some(best) = stats.best_attempt_score {
+                    println!("Best attempt score: {best:.2}");
+                }
+                let remaining = &stats.budget_remaining;
+                if let Some(secs) = remaining.wall_time_secs {
+                    println!("Wall time remaining: {secs}s");
+                }
+                if let Some(cpu) = remaining.validator_cpu_secs {
+                    println!("Validator CPU remaining: {cpu:.1}s");
+                }
+                if let Some(commits) = remaining.commits {
+                    println!("Commits remaining: {commits}");
+                }
+                if let Some(reverts) = remaining.reverts {
+                    println!("Reverts remaining: {reverts}");
+                }
+                println!(
+                    "Current convergence: {}",
+                    stats.convergence_state.is_converged
+                );
+                if !stats.action_types.is_empty() {
+                    println!("Actions by type:");
+                    for entry in &stats.action_types {
+                        let latency = entry
+                            .latency
+                            .map(|l| format!("p50 {}ms, p90 {}ms, p99 {}ms", l.p50_ms, l.p90_ms, l.p99_ms))
+                            .unwrap_or_else(|| "no timings".to_string());
+                        println!(
+                            "  {:<24} {:>4} actions, {:>6.2}% success, {}",
+                            format!("{:?}", entry.action_type),
+                            entry.count,
+                            entry.success_rate * 100.0,
+                            latency
+                        );
+                    }
+                }
+            }
 
-        Commands::Suggest { agent_id } => {
-            let suggestion = controller.suggest_next_action(&agent_id).await?;
-            println!("💡 Suggested action: {suggestion}");
-        }
+            Commands::Suggest { agent_id, all } => {
+                let recommendations = controller.recommend_next_actions(&agent_id)?;
+                let best = &recommendations[0];
+                println!("💡 Suggested action: {best} (confidence {:.2})", best.confidence);
+                if all {
+                    for other in &recommendations[1..] {
+                        println!("   {other} (confidence {:.2}, rule {})", other.confidence, other.rule);
+                    }
+                }
+            }
 
-        Commands::Visualize {
-            format,
-            output,
-            show_health,
-            show_threads,
-            show_timestamps,
-            style,
-        } => {
-            let commits = &controller.workflow.commit_history;
+            Commands::Replay { recording, scratch } => {
+                let recording = Recording::load(&recording)?;
+                let scratch = match scratch {
+                    Some(dir) => PathBuf::from(dir),
+                    None => {
+                        let repo = git2::Repository::discover(&cli.repo_path)?;
+                        git_common_dir(&repo)
+                            .join("gdk")
+                            .join("replays")
+                            .join(uuid::Uuid::new_v4().to_string())
+                    }
+                };
+
+                let mut workflow = replay::scratch_clone(&recording, &cli.repo_path, &scratch)?;
+                let report = replay::replay(&recording, &mut workflow).await?;
+                println!("Replayed {} steps in {}", report.steps_replayed, scratch.display());
+                for divergence in &report.divergences {
+                    println!("  ❌ {divergence}");
+                }
+                if let Some(step) = report.first_divergent_step() {
+                    anyhow::bail!("Replay diverged at step {step}");
+                }
+                println!("✅ Replay matched the recording");
+            }
+
+            Commands::Sessions { command } => {
+                let expired = controller.expire_idle_sessions()?;
+                for summary in &expired {
+                    info!("Session for agent {} expired", summary.agent_id);
+                }
 
-            if commits.is_empty() {
-                println!("⚠️  No commits found. Create some commits first using 'gdk commit' or 'gdk spiral'.");
-                return Ok(());
+                match command {
+                    SessionCommands::List => {
+                        let sessions = controller.list_sessions();
+                        if sessions.is_empty() {
+                            println!("No sessions found. Start one with 'gdk init --agent-id <id>'.");
+                        }
+                        for summary in sessions {
+                            println!(
+                                "{:<20} {:<10} {}  attempts: {}  actions: {}",
+                                summary.agent_id,
+                                summary.state,
+                                summary.session_id,
+                                summary.spiral_attempts,
+                                summary.total_actions
+                            );
+                        }
+                    }
+                    SessionCommands::Show { agent_id } => {
+                        print_session_summary(&controller.session_summary(&agent_id)?);
+                    }
+                    SessionCommands::Close { agent_id, abandon } => {
+                        let summary = if abandon {
+                            controller.abandon_agent_session(&agent_id)?
+                        } else {
+                            controller.complete_agent_session(&agent_id)?
+                        };
+                        print_session_summary(&summary);
+                    }
+                }
             }
 
-            let ascii_style = match style.as_str() {
-                "simple" => AsciiStyle::Simple,
-                "unicode" => AsciiStyle::Unicode,
-                "organic" => AsciiStyle::Organic,
-                _ => AsciiStyle::Unicode,
-            };
+            Commands::Approvals { command } => match command {
+                ApprovalCommands::List { all } => {
+                    let gate = controller.approval_gate.as_ref().expect("approval gate configured");
+                    let requests = if all { gate.list()? } else { gate.pending()? };
+                    if requests.is_empty() {
+                        println!("No results awaiting approval.");
+                    }
+                    for request in &requests {
+                        println!(
+                            "{}  {:<9} {:<20} {} → {}  {} files",
+                            request.id,
+                            request.status,
+                            request.agent_id,
+                            &request.converged_commit[..request.converged_commit.len().min(8)],
+                            request.target_branch,
+                            request.summary.diff.files_changed
+                        );
+                    }
+                }
+                ApprovalCommands::Show { id } => {
+                    let gate = controller.approval_gate.as_ref().expect("approval gate configured");
+                    print_approval(&gate.get(id)?);
+                }
+                ApprovalCommands::Approve { id } => {
+                    let approver = os_user()?;
+                    let request = controller.approve_promotion(id, &approver)?;
+                    println!(
+                        "✅ Promoted {} to {} (approved by {approver})",
+                        request.converged_commit, request.target_branch
+                    );
+                }
+                ApprovalCommands::Reject { id, reason } => {
+                    let approver = os_user()?;
+                    let request = controller.reject_promotion(id, &approver, &reason)?;
+                    println!("🚫 Rejected {} (by {approver}): {reason}", request.converged_commit);
+                }
+            },
+
+            Commands::Validate {
+                preset,
+                cache,
+                show_output,
+            } => {
+                let mut suite = ValidationSuite::from_preset(&preset, &cli.repo_path)?;
+                if cache {
+                    suite.set_cache(ValidationCacheConfig::default());
+                }
+
+                let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+                let renderer = tokio::spawn(async move {
+                    while let Some(event) = rx.recv().await {
+                        render_validation_event(&event, show_output);
+                    }
+                });
+
+                let result = suite.validate_with_progress(&cli.repo_path, tx).await?;
+                renderer.await?;
 
-            let config = VisualizationConfig {
-                show_health_scores: show_health,
-                show_thread_colors: show_threads,
+                println!("=== Validation Result ===");
+                println!("Overall score: {:.3}", result.overall_score);
+                println!("Passed: {}", result.passed);
+                println!("Execution time: {}ms", result.execution_time_ms);
+                for recommendation in &result.recommendations {
+                    println!("💡 {recommendation}");
+                }
+            }
+
+            Commands::Visualize {
+                format,
+                output,
+                show_health,
+                show_threads,
                 show_timestamps,
-                ascii_style,
-                ..Default::default()
-            };
-
-            let tree_output = match format.as_str() {
-                "ascii" | "txt" => export_tree_ascii(commits, Some(config))?,
-                "svg" => export_tree_svg(commits, Some(config))?,
-                "html" => export_tree_html(commits, Some(config))?,
-                _ => {
-                    println!("❌ Unsupported format: {format}. Use 'ascii', 'svg', or 'html'");
+                style,
+            } => {
+                let commits = &controller.workflow.commit_history;
+
+                if commits.is_empty() {
+                    println!("⚠️  No commits found. Create some commits first using 'gdk commit' or 'gdk spiral'.");
                     return Ok(());
                 }
-            };
 
-            match output {
-                Some(filename) => {
-                    let mut file = File::create(&filename)?;
-                    file.write_all(tree_output.as_bytes())?;
-                    println!("🌳 Tree visualization saved to: {filename}");
+                let ascii_style = match style.as_str() {
+                    "simple" => AsciiStyle::Simple,
+                    "unicode" => AsciiStyle::Unicode,
+                    "organic" => AsciiStyle::Organic,
+                    _ => AsciiStyle::Unicode,
+                };
+
+                let config = VisualizationConfig {
+                    show_health_scores: show_health,
+                    show_thread_colors: show_threads,
+                    show_timestamps,
+                    ascii_style,
+                    ..Default::default()
+                };
 
-                    if format == "html" {
-                        println!("🌐 Open {filename} in your browser to view the interactive tree");
+                let tree_output = match format.as_str() {
+                    "ascii" | "txt" => export_tree_ascii(commits, Some(config))?,
+                    "svg" => export_tree_svg(commits, Some(config))?,
+                    "html" => export_tree_html(commits, Some(config))?,
+                    _ => {
+                        println!("❌ Unsupported format: {format}. Use 'ascii', 'svg', or 'html'");
+                        return Ok(());
                     }
-                }
-                None => {
-                    if format == "ascii" || format == "txt" {
-                        println!("{tree_output}");
-                    } else {
-                        println!("📄 {} output:", format.to_uppercase());
-                        println!("{tree_output}");
+                };
+
+                match output {
+                    Some(filename) => {
+                        let mut file = File::create(&filename)?;
+                        file.write_all(tree_output.as_bytes())?;
+                        println!("🌳 Tree visualization saved to: {filename}");
+
+                        if format == "html" {
+                            println!("🌐 Open {filename} in your browser to view the interactive tree");
+                        }
+                    }
+                    None => {
+                        if format == "ascii" || format == "txt" {
+                            println!("{tree_output}");
+                        } else {
+                            println!("📄 {} output:", format.to_uppercase());
+                            println!("{tree_output}");
+                        }
                     }
                 }
             }
+
+            Commands::Serve { .. } | Commands::Mcp | Commands::Server { .. } => {
+                unreachable!("servers are handled above")
+            }
+        }
+        Ok::<(), anyhow::Error>(())
+    }
+    .await;
+
+    store.save(&controller.session_records().changes_since(&loaded))?;
+    result
+}
+
+fn print_session_summary(summary: &SessionSummary) {
+    println!("=== Session: {} ===", summary.agent_id);
+    println!("Session ID: {}", summary.session_id);
+    println!("State: {}", summary.state);
+    println!("Converged: {}", summary.converged);
+    println!("Final commit: {}", summary.final_commit.as_deref().unwrap_or("none"));
+    println!("Spiral attempts: {}", summary.spiral_attempts);
+    println!(
+        "Actions: {} ({} successful)",
+        summary.total_actions, summary.successful_actions
+    );
+    if let Some(best) = summary.best_attempt_score {
+        println!("Best attempt score: {best:.2}");
+    }
+}
+
+fn print_approval(request: &ApprovalRequest) {
+    let summary = &request.summary;
+    println!("=== Approval: {} ===", request.id);
+    println!("Status: {}", request.status);
+    println!("Agent: {}", request.agent_id);
+    println!("Target branch: {}", request.target_branch);
+    println!("Base commit: {}", request.base_commit);
+    println!("Converged commit: {}", request.converged_commit);
+    println!(
+        "Diff: {} files changed, {} insertions(+), {} deletions(-)",
+        summary.diff.files_changed, summary.diff.insertions, summary.diff.deletions
+    );
+    match summary.quality_delta {
+        Some(delta) => println!("Quality: {:.2} ({delta:+.2})", summary.converged_health),
+        None => println!("Quality: {:.2}", summary.converged_health),
+    }
+    for gate in &summary.gates {
+        let status = if gate.passed { "✅" } else { "❌" };
+        println!("{status} {}: {}", gate.name, gate.detail);
+    }
+    if let Some(decided_by) = &request.decided_by {
+        println!("Decided by: {decided_by}");
+    }
+    if let Some(reason) = &request.reason {
+        println!("Reason: {reason}");
+    }
+}
+
+/// Login name of the user running the CLI
+///
+/// Taken from the passwd entry of the real uid, not from `USER` or
+/// `LOGNAME`, which anyone running the CLI can set to an approver's name.
+#[cfg(unix)]
+fn os_user() -> Result<String> {
+    use std::ffi::{c_char, CStr};
+
+    /// Leading field of `struct passwd`, the same on every Unix
+    #[repr(C)]
+    struct Passwd {
+        pw_name: *const c_char,
+    }
+
+    extern "C" {
+        fn getuid() -> u32;
+        fn getpwuid(uid: u32) -> *const Passwd;
+    }
+
+    // SAFETY: getuid cannot fail. getpwuid returns null or a pointer to a
+    // passwd entry that stays valid until the next getpwuid call, and the
+    // name is copied out before returning.
+    unsafe {
+        let uid = getuid();
+        let entry = getpwuid(uid);
+        if entry.is_null() || (*entry).pw_name.is_null() {
+            anyhow::bail!("Cannot determine the OS user: uid {uid} has no passwd entry");
         }
+        Ok(CStr::from_ptr((*entry).pw_name).to_string_lossy().into_owned())
     }
+}
+
+/// Login name of the user running the CLI
+#[cfg(not(unix))]
+fn os_user() -> Result<String> {
+    anyhow::bail!("Cannot determine the OS user on this platform")
+}
 
-    Ok(())
+fn render_validation_event(event: &ValidationEvent, show_output: bool) {
+    match event {
+        ValidationEvent::Started { validator } => {
+            println!("▶️  {validator} started");
+        }
+        ValidationEvent::OutputLine {
+            validator, line, ..
+        } => {
+            if show_output {
+                println!("   │ {validator}: {line}");
+            }
+        }
+        ValidationEvent::Finished {
+            validator,
+            passed,
+            score,
+            execution_time_ms,
+            cache_hit,
+        } => {
+            let status = if *passed { "✅" } else { "❌" };
+            let cached = if *cache_hit { " [cached]" } else { "" };
+            println!("{status} {validator}: score {score:.2} ({execution_time_ms}ms){cached}");
+        }
+        ValidationEvent::Skipped { validator, reason } => {
+            println!("⏭️  {validator} skipped: {reason}");
+        }
+        ValidationEvent::TimedOut {
+            validator,
+            timeout_seconds,
+        } => {
+            println!("⏱️  {validator} timed out after {timeout_seconds}s");
+        }
+        ValidationEvent::Retrying {
+            validator,
+            attempt,
+            reason,
+            backoff_ms,
+        } => {
+            println!("🔁 {validator}: {reason}, retrying (attempt {attempt}) in {backoff_ms}ms");
+        }
+    }
 }
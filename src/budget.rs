@@ -0,0 +1,246 @@
+//! Per-session resource budgets
+//!
+//! `max_spiral_attempts` bounds how many attempts a session makes, but not
+//! what they cost. A [`SessionBudget`] caps a session's:
+//! - Wall-clock time since the session started
+//! - Cumulative CPU seconds spent by validators (lint, type check, tests, CI)
+//! - Commits created
+//! - Reverts performed
+//!
+//! The controller records consumption in a [`BudgetUsage`] and stops with
+//! [`GdkError::BudgetExhausted`](crate::GdkError::BudgetExhausted) once a
+//! limit is reached. Unset limits are unlimited.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::budget::SessionBudget;
+//! use gdk::core::GitWorkflowManager;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let mut controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     controller.start_agent_session("agent-1").await?;
+//!     controller.set_session_budget(
+//!         "agent-1",
+//!         SessionBudget {
+//!             max_wall_time_secs: Some(2 * 60 * 60),
+//!             max_validator_cpu_secs: Some(1800.0),
+//!             max_commits: Some(50),
+//!             max_reverts: Some(20),
+//!         },
+//!     )?;
+//!
+//!     if let Err(e) = controller.execute_infinite_monkey_workflow("agent-1", 0.8).await {
+//!         println!("Stopped: {e}");
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Limits for one session; `None` means unlimited
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionBudget {
+    /// Seconds since the session started
+    pub max_wall_time_secs: Option<u64>,
+    /// Cumulative CPU seconds of validator processes
+    pub max_validator_cpu_secs: Option<f64>,
+    /// Commits created by the session
+    pub max_commits: Option<u32>,
+    /// Reverts performed by the session
+    pub max_reverts: Option<u32>,
+}
+
+/// Resources consumed by a session so far
+///
+/// Wall time is derived from the session start time rather than stored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BudgetUsage {
+    pub validator_cpu_secs: f64,
+    pub commits: u32,
+    pub reverts: u32,
+}
+
+/// What is left of each limited resource
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BudgetRemaining {
+    pub wall_time_secs: Option<u64>,
+    pub validator_cpu_secs: Option<f64>,
+    pub commits: Option<u32>,
+    pub reverts: Option<u32>,
+}
+
+/// A budgeted resource
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetResource {
+    WallTime,
+    ValidatorCpu,
+    Commits,
+    Reverts,
+}
+
+impl BudgetResource {
+    /// Name used in errors and output
+    pub fn name(&self) -> &'static str {
+        match self {
+            BudgetResource::WallTime => "wall_time_secs",
+            BudgetResource::ValidatorCpu => "validator_cpu_secs",
+            BudgetResource::Commits => "commits",
+            BudgetResource::Reverts => "reverts",
+        }
+    }
+}
+
+impl fmt::Display for BudgetResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// A limit that has been reached
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetOverrun {
+    pub resource: BudgetResource,
+    pub limit: f64,
+    pub used: f64,
+}
+
+impl SessionBudget {
+    /// Whether every resource is unlimited
+    pub fn is_unlimited(&self) -> bool {
+        *self == SessionBudget::default()
+    }
+
+    /// First limit that leaves no room for another unit of `needed`
+    ///
+    /// Wall time and validator CPU are always checked; commits and reverts
+    /// only when the caller is about to spend one.
+    pub fn overrun(
+        &self,
+        usage: &BudgetUsage,
+        wall_time_secs: u64,
+        needed: Option<BudgetResource>,
+    ) -> Option<BudgetOverrun> {
+        if let Some(limit) = self.max_wall_time_secs {
+            if wall_time_secs >= limit {
+                return Some(BudgetOverrun {
+                    resource: BudgetResource::WallTime,
+                    limit: limit as f64,
+                    used: wall_time_secs as f64,
+                });
+            }
+        }
+        if let Some(limit) = self.max_validator_cpu_secs {
+            if usage.validator_cpu_secs >= limit {
+                return Some(BudgetOverrun {
+                    resource: BudgetResource::ValidatorCpu,
+                    limit,
+                    used: usage.validator_cpu_secs,
+                });
+            }
+        }
+        let (limit, used) = match needed {
+            Some(BudgetResource::Commits) => (self.max_commits?, usage.commits),
+            Some(BudgetResource::Reverts) => (self.max_reverts?, usage.reverts),
+            _ => return None,
+        };
+        (used >= limit).then_some(BudgetOverrun {
+            resource: needed?,
+            limit: limit as f64,
+            used: used as f64,
+        })
+    }
+
+    /// Share of each limited resource used so far (1.0 means exhausted)
+    pub fn used_shares(&self, usage: &BudgetUsage, wall_time_secs: u64) -> Vec<(BudgetResource, f64)> {
+        let share = |used: f64, limit: f64| if limit > 0.0 { used / limit } else { 1.0 };
+        let mut shares = Vec::new();
+        if let Some(limit) = self.max_wall_time_secs {
+            shares.push((BudgetResource::WallTime, share(wall_time_secs as f64, limit as f64)));
+        }
+        if let Some(limit) = self.max_validator_cpu_secs {
+            shares.push((BudgetResource::ValidatorCpu, share(usage.validator_cpu_secs, limit)));
+        }
+        if let Some(limit) = self.max_commits {
+            shares.push((BudgetResource::Commits, share(usage.commits as f64, limit as f64)));
+        }
+        if let Some(limit) = self.max_reverts {
+            shares.push((BudgetResource::Reverts, share(usage.reverts as f64, limit as f64)));
+        }
+        shares
+    }
+
+    /// Remaining amount of each limited resource
+    pub fn remaining(&self, usage: &BudgetUsage, wall_time_secs: u64) -> BudgetRemaining {
+        BudgetRemaining {
+            wall_time_secs: self
+                .max_wall_time_secs
+                .map(|limit| limit.saturating_sub(wall_time_secs)),
+            validator_cpu_secs: self
+                .max_validator_cpu_secs
+                .map(|limit| (limit - usage.validator_cpu_secs).max(0.0)),
+            commits: self.max_commits.map(|limit| limit.saturating_sub(usage.commits)),
+            reverts: self.max_reverts.map(|limit| limit.saturating_sub(usage.reverts)),
+        }
+    }
+}
+
+/// Measures CPU time of validator processes spawned while it runs
+///
+/// On Linux this reads the CPU time of reaped child processes from
+/// `/proc/self/stat`, so concurrent validators from other sessions in the
+/// same process are included. Elsewhere it falls back to elapsed wall time,
+/// which overestimates CPU use for validators that wait on I/O.
+#[derive(Debug)]
+pub struct ValidatorClock {
+    started: Instant,
+    children_cpu_secs: Option<f64>,
+}
+
+impl ValidatorClock {
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            children_cpu_secs: children_cpu_secs(),
+        }
+    }
+
+    /// Wall time since [`ValidatorClock::start`]
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Validator CPU seconds consumed since [`ValidatorClock::start`]
+    pub fn elapsed_cpu_secs(&self) -> f64 {
+        match (self.children_cpu_secs, children_cpu_secs()) {
+            (Some(before), Some(after)) => (after - before).max(0.0),
+            _ => self.started.elapsed().as_secs_f64(),
+        }
+    }
+}
+
+/// User plus system CPU seconds of this process's reaped children
+#[cfg(target_os = "linux")]
+fn children_cpu_secs() -> Option<f64> {
+    // Linux reports these in USER_HZ ticks, which is 100 on every platform
+    const TICKS_PER_SEC: f64 = 100.0;
+
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // The command name may contain spaces, so count fields after its ')'
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    // cutime and cstime are fields 16 and 17 of the full line
+    let cutime: f64 = fields.get(13)?.parse().ok()?;
+    let cstime: f64 = fields.get(14)?.parse().ok()?;
+    Some((cutime + cstime) / TICKS_PER_SEC)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn children_cpu_secs() -> Option<f64> {
+    None
+}
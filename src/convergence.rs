@@ -6,7 +6,11 @@
 //! - Test pass consistency analysis for reliability metrics
 //! - Build success rate tracking for compilation health
 //! - Trend improvement analysis using linear regression
-//! - Convergence prediction algorithms
+//! - Change-point detection (two-sided CUSUM) and plateau detection
+//! - Optional multi-objective (Pareto) convergence over quality dimensions
+//! - Per-file thread convergence with per-path thresholds
+//! - Branch-aware analysis over the commit DAG (see [`crate::lineage`])
+//! - Convergence forecasting with confidence bounds (see [`crate::forecast`])
 //!
 //! # Mathematical Foundation
 //!
@@ -17,6 +21,34 @@
 //! - **Build Success Rate (15%)**: Compilation and build health
 //! - **Trend Improvement (10%)**: Linear regression slope of quality
 //!
+//! Stability and trend are only measured over the current quality regime,
+//! i.e. the commits since the last detected change point, so a recent
+//! regression cannot hide inside an older upward trend.
+//!
+//! # Branch Lineages
+//!
+//! Histories interleave spiral branches, so trend, stability, change points
+//! and thread convergence are computed over the first-parent lineage of the
+//! analyzed tip rather than the insertion order. Plateau and Pareto analysis
+//! deliberately span every attempt, since attempts on sibling branches are
+//! exactly what they compare. Sibling branches are additionally compared
+//! against their common base in [`ConvergenceResult::siblings`].
+//!
+//! # Pareto Mode
+//!
+//! With [`ConvergenceAnalyzer::pareto`] set, convergence is decided on the
+//! vector of [`QualityDimensions`] instead of the single health score: the
+//! latest attempt must clear every per-dimension floor and the Pareto front
+//! of attempts must have stopped moving. This prevents trading one dimension
+//! (e.g. security) for another (e.g. coverage) unnoticed.
+//!
+//! # Convergence Policies
+//!
+//! [`GitWorkflowManager`](crate::core::GitWorkflowManager) decides convergence
+//! through a [`ConvergencePolicy`]. [`ConvergenceAnalyzer`] is the default
+//! policy; custom policies can be installed with
+//! [`GitWorkflowManager::with_convergence_policy`](crate::core::GitWorkflowManager::with_convergence_policy).
+//!
 //! # Example Usage
 //!
 //! ```rust,no_run
@@ -34,8 +66,13 @@
 //! }
 //! ```
 
+use crate::forecast::{ConvergenceForecast, ForecastConfig, ForecastUnavailable, Forecaster};
+use crate::quality_metrics::QualityDimensions;
+use crate::lineage::{CommitGraph, SiblingComparison};
+use crate::threads::{ThreadConvergenceRules, ThreadThreshold};
 use crate::{CommitNode, ThreadColor, GdkResult, GdkError};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Mathematical analyzer for detecting workflow convergence
 ///
@@ -50,7 +87,10 @@ use serde::{Deserialize, Serialize};
 /// - **quality_trend_window**: Number of commits for trend analysis
 /// - **min_green_threads_ratio**: Required percentage of healthy threads
 /// - **variance_threshold**: Maximum allowed variance in quality scores
+/// - **cusum_drift** / **cusum_threshold**: CUSUM slack and alarm level for change points
+/// - **plateau_patience** / **plateau_min_improvement**: Stall detection window
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct ConvergenceAnalyzer {
     /// Minimum weighted confidence score required for convergence (default: 0.8)
     pub convergence_threshold: f64,
@@ -62,6 +102,43 @@ pub struct ConvergenceAnalyzer {
     pub min_green_threads_ratio: f64,
     /// Maximum allowed variance in quality scores for stability (default: 0.02)
     pub variance_threshold: f64,
+    /// Per-commit slack subtracted in the CUSUM statistic (default: 0.02)
+    pub cusum_drift: f64,
+    /// Cumulative deviation that signals a change point (default: 0.2)
+    pub cusum_threshold: f64,
+    /// Commits without improvement before the spiral counts as stalled (default: 5)
+    pub plateau_patience: usize,
+    /// Smallest gain over the best score that counts as improvement (default: 0.01)
+    pub plateau_min_improvement: f64,
+    /// Trend fitting configuration for convergence forecasts
+    pub forecast: ForecastConfig,
+    /// Forecasts further out than this many iterations are reported as unavailable (default: 100)
+    pub max_forecast_horizon: u32,
+    /// Decide convergence on quality dimensions instead of the health score (default: off)
+    pub pareto: Option<ParetoConfig>,
+    /// Per-path criteria for individual file threads
+    pub thread_rules: ThreadConvergenceRules,
+    /// Minimum share of converged file threads required for convergence (default: 0.7)
+    pub min_converged_thread_share: f64,
+}
+
+/// Configuration for multi-objective (Pareto) convergence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ParetoConfig {
+    /// Minimum score per dimension the latest attempt must reach (default: 0.7 everywhere)
+    pub floors: QualityDimensions,
+    /// Attempts without a change to the front before it counts as settled (default: 3)
+    pub stability_window: usize,
+}
+
+impl Default for ParetoConfig {
+    fn default() -> Self {
+        Self {
+            floors: QualityDimensions::uniform(0.7),
+            stability_window: 3,
+        }
+    }
 }
 
 impl Default for ConvergenceAnalyzer {
@@ -72,6 +149,15 @@ impl Default for ConvergenceAnalyzer {
             quality_trend_window: 10,
             min_green_threads_ratio: 0.7,
             variance_threshold: 0.02,
+            cusum_drift: 0.02,
+            cusum_threshold: 0.2,
+            plateau_patience: 5,
+            plateau_min_improvement: 0.01,
+            forecast: ForecastConfig::default(),
+            max_forecast_horizon: 100,
+            pareto: None,
+            thread_rules: ThreadConvergenceRules::default(),
+            min_converged_thread_share: 0.7,
         }
     }
 }
@@ -93,6 +179,215 @@ pub struct ConvergenceResult {
     pub convergence_factors: ConvergenceFactors,
     /// Human-readable recommendations for improving convergence
     pub recommendations: Vec<String>,
+    /// Most recent shift in the quality level, if any
+    #[serde(default)]
+    pub change_point: Option<ChangePoint>,
+    /// Plateau (stall) status of the quality trend
+    #[serde(default)]
+    pub plateau: PlateauStatus,
+    /// Multi-objective analysis (only in Pareto mode)
+    #[serde(default)]
+    pub pareto: Option<ParetoAnalysis>,
+    /// Per-file thread convergence rolled up to the commit level
+    #[serde(default)]
+    pub thread_convergence: ThreadConvergenceSummary,
+    /// Sibling branches compared against their common base
+    #[serde(default)]
+    pub siblings: Vec<SiblingComparison>,
+}
+
+/// Convergence of one branch lineage
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BranchConvergence {
+    /// Hash of the branch tip
+    pub tip_hash: String,
+    /// Number of commits in the tip's lineage
+    pub lineage_length: usize,
+    /// Analysis of the lineage
+    pub result: ConvergenceResult,
+}
+
+/// File thread that has not converged under its path's threshold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlockingThread {
+    /// Path of the file
+    pub file_path: String,
+    /// Most recent quality score of the file
+    pub latest_score: f64,
+    /// Threshold that applied to the file
+    pub threshold: ThreadThreshold,
+}
+
+/// Commit-level roll-up of per-file thread convergence
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ThreadConvergenceSummary {
+    /// Share of tracked files whose thread has converged (0.0-1.0)
+    pub converged_share: f64,
+    /// Files whose thread has converged, sorted by path
+    pub converged_files: Vec<String>,
+    /// Files blocking convergence, sorted by path
+    pub blocking_files: Vec<BlockingThread>,
+}
+
+/// Attempt on the Pareto front of quality dimensions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParetoPoint {
+    /// Index of the attempt in the analyzed history
+    pub index: usize,
+    /// Commit hash of the attempt
+    pub commit_hash: String,
+    /// Dimension scores of the attempt
+    pub dimensions: QualityDimensions,
+}
+
+/// Attempt that is worse or equal in every dimension than a front member
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DominatedAttempt {
+    /// Index of the attempt in the analyzed history
+    pub index: usize,
+    /// Commit hash of the attempt
+    pub commit_hash: String,
+    /// Commit hash of a front member that dominates it
+    pub dominated_by: String,
+}
+
+/// Dimension of the latest attempt scoring below its floor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FloorViolation {
+    /// Dimension name
+    pub dimension: String,
+    /// Score of the latest attempt
+    pub value: f64,
+    /// Configured floor
+    pub floor: f64,
+}
+
+/// Result of multi-objective convergence analysis
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParetoAnalysis {
+    /// Non-dominated attempts, in chronological order
+    pub front: Vec<ParetoPoint>,
+    /// Attempts dominated by a front member
+    pub dominated: Vec<DominatedAttempt>,
+    /// Dimensions of the latest attempt below their floor
+    pub floor_violations: Vec<FloorViolation>,
+    /// Attempts since the front last gained or lost a member
+    pub attempts_since_front_changed: usize,
+    /// Whether the front has stopped moving
+    pub front_stable: bool,
+}
+
+/// Direction of a quality level shift
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ShiftDirection {
+    /// Quality moved to a higher level
+    Improvement,
+    /// Quality moved to a lower level
+    Regression,
+}
+
+/// A detected shift in the mean health score
+///
+/// Located with a two-sided CUSUM: the change point is the first commit of
+/// the new regime, i.e. where the alarming cumulative sum last left zero.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangePoint {
+    /// Index of the first commit of the new regime in the analyzed lineage
+    pub index: usize,
+    /// Hash of the first commit of the new regime
+    pub commit_hash: String,
+    /// Whether quality shifted up or down
+    pub direction: ShiftDirection,
+    /// Mean health score of the previous regime
+    pub mean_before: f64,
+    /// Mean health score since the change point
+    pub mean_after: f64,
+}
+
+/// Plateau detection over the health score history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PlateauStatus {
+    /// Whether no meaningful improvement happened within the patience window
+    pub is_stalled: bool,
+    /// Commits since the best score last improved
+    pub commits_since_improvement: usize,
+    /// Best health score seen so far
+    pub best_score: f64,
+}
+
+/// Strategy deciding whether a commit history has converged
+///
+/// Implementations receive the full chronological commit history and return
+/// the same detailed [`ConvergenceResult`] as [`ConvergenceAnalyzer`], which
+/// is the default policy used by the workflow manager.
+///
+/// # Example
+///
+/// ```rust
+/// use gdk::convergence::{ConvergenceAnalyzer, ConvergencePolicy, ConvergenceResult};
+/// use gdk::{CommitNode, GdkResult};
+///
+/// /// Converge as soon as the latest commit is healthy enough
+/// #[derive(Debug)]
+/// struct LatestScore(f64);
+///
+/// impl ConvergencePolicy for LatestScore {
+///     fn evaluate(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult> {
+///         let mut result = ConvergenceAnalyzer::new().evaluate(commit_history)?;
+///         result.is_converged = commit_history
+///             .last()
+///             .is_some_and(|commit| commit.health_score >= self.0);
+///         Ok(result)
+///     }
+/// }
+/// ```
+pub trait ConvergencePolicy: Send + Sync + std::fmt::Debug {
+    /// Evaluate convergence of a chronological commit history
+    fn evaluate(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult>;
+
+    /// Evaluate convergence of the branch ending at `tip_hash`
+    ///
+    /// The default evaluates only the tip's first-parent lineage.
+    fn evaluate_at(&self, commit_history: &[CommitNode], tip_hash: &str) -> GdkResult<ConvergenceResult> {
+        let lineage: Vec<CommitNode> = CommitGraph::new(commit_history)
+            .lineage(tip_hash)
+            .into_iter()
+            .cloned()
+            .collect();
+        self.evaluate(&lineage)
+    }
+
+    /// Evaluate the branch ending at `tip_hash` during a spiral whose first
+    /// attempt is `commit_history[spiral_start]`
+    ///
+    /// The default ignores `spiral_start` and calls [`Self::evaluate_at`].
+    fn evaluate_spiral(
+        &self,
+        commit_history: &[CommitNode],
+        tip_hash: &str,
+        _spiral_start: usize,
+    ) -> GdkResult<ConvergenceResult> {
+        self.evaluate_at(commit_history, tip_hash)
+    }
+}
+
+impl ConvergencePolicy for ConvergenceAnalyzer {
+    fn evaluate(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult> {
+        self.analyze_convergence(commit_history)
+    }
+
+    fn evaluate_at(&self, commit_history: &[CommitNode], tip_hash: &str) -> GdkResult<ConvergenceResult> {
+        self.analyze_branch(commit_history, tip_hash)
+    }
+
+    fn evaluate_spiral(
+        &self,
+        commit_history: &[CommitNode],
+        tip_hash: &str,
+        spiral_start: usize,
+    ) -> GdkResult<ConvergenceResult> {
+        self.analyze_spiral(commit_history, tip_hash, spiral_start)
+    }
 }
 
 /// Individual factors contributing to overall convergence assessment
@@ -166,6 +461,7 @@ impl ConvergenceAnalyzer {
             quality_trend_window,
             min_green_threads_ratio,
             variance_threshold,
+            ..Self::default()
         }
     }
 
@@ -183,7 +479,8 @@ impl ConvergenceAnalyzer {
     ///
     /// # Returns
     ///
-    /// Detailed convergence analysis with recommendations
+    /// Detailed convergence analysis of the most recently recorded commit's
+    /// lineage, with recommendations and sibling branch comparisons
     ///
     /// # Mathematical Details
     ///
@@ -196,7 +493,72 @@ impl ConvergenceAnalyzer {
     ///              0.10 * trend_improvement
     /// ```
     pub fn analyze_convergence(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult> {
-        if commit_history.is_empty() {
+        let graph = CommitGraph::new(commit_history);
+        let lineage: Vec<CommitNode> = graph.latest_lineage().into_iter().cloned().collect();
+        self.analyze_with_lineage(&graph, commit_history, &lineage, 0)
+    }
+
+    /// Analyze the branch ending at `tip_hash`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ValidationError`] if the tip is not in the history.
+    pub fn analyze_branch(&self, commit_history: &[CommitNode], tip_hash: &str) -> GdkResult<ConvergenceResult> {
+        self.analyze_spiral(commit_history, tip_hash, 0)
+    }
+
+    /// Analyze the branch ending at `tip_hash`, detecting plateaus only among
+    /// the attempts from `commit_history[spiral_start]` on
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ValidationError`] if the tip is not in the history.
+    pub fn analyze_spiral(
+        &self,
+        commit_history: &[CommitNode],
+        tip_hash: &str,
+        spiral_start: usize,
+    ) -> GdkResult<ConvergenceResult> {
+        let graph = CommitGraph::new(commit_history);
+        let lineage: Vec<CommitNode> = graph.lineage(tip_hash).into_iter().cloned().collect();
+        if lineage.is_empty() && !commit_history.is_empty() {
+            return Err(GdkError::validation_error(
+                "unknown_commit",
+                format!("Commit {tip_hash} is not part of the analyzed history"),
+                "Branch tip not found".to_string(),
+            ));
+        }
+        self.analyze_with_lineage(&graph, commit_history, &lineage, spiral_start)
+    }
+
+    /// Analyze every branch tip in the history, in chronological order
+    pub fn analyze_branches(&self, commit_history: &[CommitNode]) -> GdkResult<Vec<BranchConvergence>> {
+        let graph = CommitGraph::new(commit_history);
+        graph
+            .tips()
+            .into_iter()
+            .map(|tip| {
+                let lineage: Vec<CommitNode> = graph.lineage(&tip.hash).into_iter().cloned().collect();
+                Ok(BranchConvergence {
+                    tip_hash: tip.hash.clone(),
+                    lineage_length: lineage.len(),
+                    result: self.analyze_with_lineage(&graph, commit_history, &lineage, 0)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Core analysis: trend-based factors on `lineage`, attempt-based
+    /// Pareto analysis on the full history and plateau detection on the
+    /// attempts since `spiral_start`
+    fn analyze_with_lineage(
+        &self,
+        graph: &CommitGraph<'_>,
+        commit_history: &[CommitNode],
+        lineage: &[CommitNode],
+        spiral_start: usize,
+    ) -> GdkResult<ConvergenceResult> {
+        if lineage.is_empty() {
             return Ok(ConvergenceResult {
                 is_converged: false,
                 confidence_score: 0.0,
@@ -208,21 +570,298 @@ impl ConvergenceAnalyzer {
                     trend_improvement: 0.0,
                 },
                 recommendations: vec!["No commit history available".to_string()],
+                change_point: None,
+                plateau: PlateauStatus::default(),
+                pareto: None,
+                thread_convergence: ThreadConvergenceSummary::default(),
+                siblings: Vec::new(),
             });
         }
 
-        let factors = self.calculate_convergence_factors(commit_history)?;
-        let (is_converged, confidence_score) = self.determine_convergence(&factors);
-        let recommendations = self.generate_recommendations(&factors, commit_history);
+        let change_point = self.detect_change_point(lineage);
+        let plateau = self.detect_plateau(&commit_history[spiral_start.min(commit_history.len())..]);
+
+        // Stability and trend only look at the current quality regime
+        let regime = &lineage[change_point.as_ref().map_or(0, |cp| cp.index)..];
+        let mut factors = self.calculate_convergence_factors(regime)?;
+        if matches!(&change_point, Some(cp) if cp.direction == ShiftDirection::Regression) {
+            factors.trend_improvement = 0.0;
+        }
+
+        let (mut is_converged, confidence_score) = self.determine_convergence(&factors);
+        let thread_convergence = self.analyze_thread_convergence(lineage);
+        let mut recommendations = self.generate_recommendations(&factors, lineage);
+
+        let pareto = self
+            .pareto
+            .as_ref()
+            .map(|config| Self::analyze_pareto(config, commit_history));
+        if let Some(analysis) = &pareto {
+            is_converged = analysis.floor_violations.is_empty() && analysis.front_stable;
+            for violation in &analysis.floor_violations {
+                recommendations.insert(0, format!(
+                    "{} is {:.3}, below its floor of {:.3}. Do not trade it for other dimensions.",
+                    violation.dimension, violation.value, violation.floor
+                ));
+            }
+        }
+
+        is_converged &= thread_convergence.converged_share >= self.min_converged_thread_share;
+        if !thread_convergence.blocking_files.is_empty() {
+            recommendations.push(format!(
+                "{} files block convergence: {}",
+                thread_convergence.blocking_files.len(),
+                thread_convergence
+                    .blocking_files
+                    .iter()
+                    .take(3)
+                    .map(|blocking| blocking.file_path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if let Some(cp) = change_point.as_ref().filter(|cp| cp.direction == ShiftDirection::Regression) {
+            recommendations.insert(0, format!(
+                "Quality regressed at commit {} (mean {:.3} -> {:.3}). Consider reverting to a checkpoint before it.",
+                &cp.commit_hash[..cp.commit_hash.len().min(8)],
+                cp.mean_before,
+                cp.mean_after
+            ));
+        }
+        if plateau.is_stalled && !is_converged {
+            recommendations.insert(0, format!(
+                "Quality has plateaued at {:.3} for {} commits. The spiral has stalled; change strategy or stop.",
+                plateau.best_score, plateau.commits_since_improvement
+            ));
+        }
 
         Ok(ConvergenceResult {
             is_converged,
             confidence_score,
             convergence_factors: factors,
             recommendations,
+            change_point,
+            plateau,
+            pareto,
+            thread_convergence,
+            siblings: graph.compare_siblings(),
         })
     }
 
+    /// Evaluate every file thread seen in the history against its path threshold
+    ///
+    /// A file's quality series consists of its thread score in each commit
+    /// that touched it, oldest first. Files touched by fewer commits than
+    /// their threshold's window block convergence.
+    pub fn analyze_thread_convergence(&self, commit_history: &[CommitNode]) -> ThreadConvergenceSummary {
+        let mut series: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        for commit in commit_history {
+            for (path, thread) in &commit.file_threads {
+                let score = thread
+                    .history
+                    .last()
+                    .map(|state| state.metrics.quality_score)
+                    .unwrap_or_else(|| {
+                        (thread.lint_score
+                            + thread.type_check_score
+                            + thread.test_coverage
+                            + thread.functionality_score)
+                            / 4.0
+                    });
+                series.entry(path.as_str()).or_default().push(score);
+            }
+        }
+
+        let mut summary = ThreadConvergenceSummary::default();
+        for (path, scores) in &series {
+            let threshold = self.thread_rules.threshold_for(path);
+            if threshold.is_converged(scores) {
+                summary.converged_files.push(path.to_string());
+            } else {
+                summary.blocking_files.push(BlockingThread {
+                    file_path: path.to_string(),
+                    latest_score: scores.last().copied().unwrap_or(0.0),
+                    threshold: threshold.clone(),
+                });
+            }
+        }
+
+        if !series.is_empty() {
+            summary.converged_share = summary.converged_files.len() as f64 / series.len() as f64;
+        }
+        summary
+    }
+
+    /// Track the Pareto front of attempts over their quality dimensions
+    ///
+    /// Attempts are added in order; the front "moves" whenever an attempt
+    /// joins it or knocks out an existing member. Repeating a front member's
+    /// scores exactly does not move the front.
+    pub fn analyze_pareto(config: &ParetoConfig, commit_history: &[CommitNode]) -> ParetoAnalysis {
+        let dimensions: Vec<QualityDimensions> = commit_history
+            .iter()
+            .map(QualityDimensions::from_commit)
+            .collect();
+
+        // Weak dominance: no worse in any dimension
+        let covers = |a: &QualityDimensions, b: &QualityDimensions| a == b || a.dominates(b);
+
+        let mut front: Vec<usize> = Vec::new();
+        let mut last_change = 0;
+        for (index, candidate) in dimensions.iter().enumerate() {
+            if front.iter().any(|&member| covers(&dimensions[member], candidate)) {
+                continue;
+            }
+            front.retain(|&member| !candidate.dominates(&dimensions[member]));
+            front.push(index);
+            last_change = index;
+        }
+
+        let dominated = (0..commit_history.len())
+            .filter(|index| !front.contains(index))
+            .filter_map(|index| {
+                let dominator = front
+                    .iter()
+                    .find(|&&member| covers(&dimensions[member], &dimensions[index]))?;
+                Some(DominatedAttempt {
+                    index,
+                    commit_hash: commit_history[index].hash.clone(),
+                    dominated_by: commit_history[*dominator].hash.clone(),
+                })
+            })
+            .collect();
+
+        let floor_violations = dimensions
+            .last()
+            .map(|latest| {
+                latest
+                    .values()
+                    .into_iter()
+                    .zip(config.floors.values())
+                    .filter(|((_, value), (_, floor))| value < floor)
+                    .map(|((dimension, value), (_, floor))| FloorViolation {
+                        dimension: dimension.to_string(),
+                        value,
+                        floor,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let attempts_since_front_changed = commit_history.len().saturating_sub(last_change + 1);
+
+        ParetoAnalysis {
+            front: front
+                .into_iter()
+                .map(|index| ParetoPoint {
+                    index,
+                    commit_hash: commit_history[index].hash.clone(),
+                    dimensions: dimensions[index].clone(),
+                })
+                .collect(),
+            dominated,
+            floor_violations,
+            attempts_since_front_changed,
+            front_stable: !commit_history.is_empty()
+                && attempts_since_front_changed >= config.stability_window,
+        }
+    }
+
+    /// Locate the most recent shift in the mean health score
+    ///
+    /// Runs a two-sided CUSUM against the running mean of the current regime.
+    /// When either sum exceeds `cusum_threshold`, a change point is recorded
+    /// where that sum last left zero and the statistic restarts from there.
+    pub fn detect_change_point(&self, commit_history: &[CommitNode]) -> Option<ChangePoint> {
+        let scores: Vec<f64> = commit_history.iter().map(|c| c.health_score).collect();
+        let mut regime_start = 0;
+        let mut last_change: Option<(usize, usize, ShiftDirection)> = None;
+
+        let mut i = 1;
+        while i < scores.len() {
+            let mut high = 0.0_f64;
+            let mut low = 0.0_f64;
+            let mut high_start = i;
+            let mut low_start = i;
+            let mut alarm = None;
+
+            for j in i..scores.len() {
+                let segment = &scores[regime_start..j];
+                let mean = segment.iter().sum::<f64>() / segment.len() as f64;
+                let deviation = scores[j] - mean;
+
+                if high == 0.0 {
+                    high_start = j;
+                }
+                if low == 0.0 {
+                    low_start = j;
+                }
+                high = (high + deviation - self.cusum_drift).max(0.0);
+                low = (low - deviation - self.cusum_drift).max(0.0);
+
+                if high > self.cusum_threshold {
+                    alarm = Some((high_start, ShiftDirection::Improvement));
+                    break;
+                }
+                if low > self.cusum_threshold {
+                    alarm = Some((low_start, ShiftDirection::Regression));
+                    break;
+                }
+            }
+
+            match alarm {
+                Some((index, direction)) => {
+                    last_change = Some((regime_start, index, direction));
+                    regime_start = index;
+                    i = index + 1;
+                }
+                None => break,
+            }
+        }
+
+        let (previous_start, index, direction) = last_change?;
+        let mean = |s: &[f64]| s.iter().sum::<f64>() / s.len().max(1) as f64;
+
+        Some(ChangePoint {
+            index,
+            commit_hash: commit_history[index].hash.clone(),
+            direction,
+            mean_before: mean(&scores[previous_start..index]),
+            mean_after: mean(&scores[index..]),
+        })
+    }
+
+    /// Detect whether the best health score stopped improving
+    ///
+    /// The spiral counts as stalled once `plateau_patience` commits pass
+    /// without beating the best score by at least `plateau_min_improvement`.
+    pub fn detect_plateau(&self, commit_history: &[CommitNode]) -> PlateauStatus {
+        let mut scores = commit_history.iter().map(|c| c.health_score);
+        let Some(first) = scores.next() else {
+            return PlateauStatus::default();
+        };
+
+        let mut best_score = first;
+        let mut commits_since_improvement = 0;
+        for score in scores {
+            if score >= best_score + self.plateau_min_improvement {
+                best_score = score;
+                commits_since_improvement = 0;
+            } else {
+                best_score = best_score.max(score);
+                commits_since_improvement += 1;
+            }
+        }
+
+        PlateauStatus {
+            is_stalled: self.plateau_patience > 0
+                && commits_since_improvement >= self.plateau_patience,
+            commits_since_improvement,
+            best_score,
+        }
+    }
+
     fn calculate_convergence_factors(
         &self,
         commit_history: &[CommitNode],
@@ -487,43 +1126,35 @@ impl ConvergenceAnalyzer {
         recommendations
     }
 
-    pub fn predict_convergence_time(&self, commit_history: &[CommitNode]) -> GdkResult<Option<u32>> {
-        if commit_history.len() < 3 {
-            return Ok(None);
-        }
-
-        let recent_scores: Vec<f64> = commit_history
+    /// Forecast the iterations needed to reach `convergence_threshold`
+    ///
+    /// Fits a linear trend to the health scores of the current quality regime
+    /// (since the last change point) of the latest commit's lineage and
+    /// derives expected iterations with bounds from the slope's confidence
+    /// interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ForecastUnavailable`] reason when the history is too
+    /// short, quality is not improving, or the target lies beyond
+    /// `max_forecast_horizon`.
+    pub fn predict_convergence_time(
+        &self,
+        commit_history: &[CommitNode],
+    ) -> Result<ConvergenceForecast, ForecastUnavailable> {
+        let lineage: Vec<CommitNode> = CommitGraph::new(commit_history)
+            .latest_lineage()
+            .into_iter()
+            .cloned()
+            .collect();
+        let regime_start = self.detect_change_point(&lineage).map_or(0, |cp| cp.index);
+        let scores: Vec<f64> = lineage[regime_start..]
             .iter()
-            .rev()
-            .take(10)
             .map(|commit| commit.health_score)
             .collect();
 
-        if recent_scores.len() < 3 {
-            return Ok(None);
-        }
-
-        // Calculate improvement rate
-        let current_score = recent_scores[0];
-        let oldest_score = recent_scores[recent_scores.len() - 1];
-        let improvement_rate = (current_score - oldest_score) / recent_scores.len() as f64;
-
-        if improvement_rate <= 0.0 {
-            return Ok(None); // No improvement trend
-        }
-
-        let remaining_improvement = self.convergence_threshold - current_score;
-        if remaining_improvement <= 0.0 {
-            return Ok(Some(0)); // Already converged
-        }
-
-        let predicted_iterations = (remaining_improvement / improvement_rate).ceil() as u32;
-
-        // Cap prediction at reasonable upper bound
-        if predicted_iterations > 100 {
-            Ok(None)
-        } else {
-            Ok(Some(predicted_iterations))
-        }
+        Forecaster::with_config(self.forecast.clone())
+            .fit(&scores)?
+            .iterations_to(self.convergence_threshold, self.max_forecast_horizon)
     }
 }
@@ -7,17 +7,27 @@
 //! - Commit node creation with comprehensive thread analysis
 //! - Revert point management for intelligent state restoration
 
+use crate::convergence::{ConvergenceAnalyzer, ConvergencePolicy};
+use crate::lineage::CommitGraph;
+use crate::policy::{CommitPolicy, StagedFile};
 use crate::{
     CommitNode, ConvergenceMetrics, FileThread, GitWorkflow, RevertPoint, ThreadColor,
     ThreadMetrics, ThreadState, GdkError, GdkResult, GdkResultExt,
 };
 use anyhow::anyhow;
 use git2::{Repository, Signature};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::process::Command;
 use uuid::Uuid;
 
+/// Reason recorded on the revert point created by
+/// [`GitWorkflow::begin_spiral`]
+pub const SPIRAL_START_REASON: &str = "infinite_monkey_start";
+
+/// Most first-parent ancestors of HEAD searched for a recorded commit
+pub const MAX_ANCESTOR_DEPTH: usize = 1_000;
+
 /// Primary workflow manager implementing the GDK git workflow system
 ///
 /// Manages the complete lifecycle of AI agent interactions with git:
@@ -29,7 +39,10 @@ use uuid::Uuid;
 /// # Thread Safety
 ///
 /// This struct is designed for single-threaded use within async contexts.
-/// For multi-agent scenarios, create separate instances per agent.
+/// For multi-agent scenarios, use [`crate::multi_agent::MultiAgentController`],
+/// which gives each agent its own instance on its own worktree. To use a
+/// manager from a multi-threaded runtime, move it onto an actor thread with
+/// [`crate::actor::WorkflowHandle::spawn`].
 ///
 /// # Example
 ///
@@ -61,6 +74,12 @@ pub struct GitWorkflowManager {
     pub revert_points: Vec<RevertPoint>,
     /// Current active branch name
     pub current_branch: String,
+    /// Policy deciding when the commit history has converged
+    pub convergence_policy: Box<dyn ConvergencePolicy>,
+    /// Limits checked before every commit (empty allows everything)
+    pub commit_policy: CommitPolicy,
+    /// Index in `commit_history` of the current spiral's first attempt
+    pub spiral_start: usize,
 }
 
 impl GitWorkflowManager {
@@ -98,15 +117,141 @@ impl GitWorkflowManager {
             commit_history: Vec::new(),
             revert_points: Vec::new(),
             current_branch,
+            convergence_policy: Box::new(ConvergenceAnalyzer::new()),
+            commit_policy: CommitPolicy::default(),
+            spiral_start: 0,
         })
     }
 
+    /// Find the closest first-parent ancestor of HEAD (inclusive) that has a
+    /// recorded [`CommitNode`]
+    ///
+    /// Returns `None` when HEAD is unborn or none of its ancestors were
+    /// recorded, e.g. right after reverting to the starting point. The walk
+    /// stops at commits older than the first recorded one, and after
+    /// [`MAX_ANCESTOR_DEPTH`] commits.
+    fn nearest_recorded_ancestor(&self) -> GdkResult<Option<String>> {
+        let Some(first) = self.commit_history.first() else {
+            return Ok(None);
+        };
+        let Ok(head) = self.repo.head() else {
+            return Ok(None);
+        };
+        let Some(head_oid) = head.target() else {
+            return Ok(None);
+        };
+
+        let recorded: HashSet<&str> = self.commit_history.iter().map(|commit| commit.hash.as_str()).collect();
+        let cutoff = git2::Oid::from_str(&first.hash)
+            .and_then(|oid| self.repo.find_commit(oid))
+            .map(|commit| commit.time().seconds())
+            .ok();
+
+        let mut revwalk = self.repo.revwalk().with_git_context("walking HEAD lineage")?;
+        revwalk.push(head_oid).with_git_context("walking HEAD lineage")?;
+        revwalk.simplify_first_parent().with_git_context("walking HEAD lineage")?;
+
+        for oid in revwalk.take(MAX_ANCESTOR_DEPTH) {
+            let oid = oid.with_git_context("walking HEAD lineage")?;
+            let hash = oid.to_string();
+            if recorded.contains(hash.as_str()) {
+                return Ok(Some(hash));
+            }
+
+            let commit = self.repo.find_commit(oid).with_git_context("walking HEAD lineage")?;
+            if cutoff.is_some_and(|cutoff| commit.time().seconds() < cutoff) {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Replace the convergence policy (defaults to [`ConvergenceAnalyzer`])
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use gdk::convergence::ConvergenceAnalyzer;
+    /// use gdk::core::GitWorkflowManager;
+    ///
+    /// # fn main() -> gdk::GdkResult<()> {
+    /// let strict = ConvergenceAnalyzer::with_config(0.9, 3, 5, 0.8, 0.01);
+    /// let manager = GitWorkflowManager::new("./my-project")?.with_convergence_policy(strict);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_convergence_policy(mut self, policy: impl ConvergencePolicy + 'static) -> Self {
+        self.convergence_policy = Box::new(policy);
+        self
+    }
+
+    /// Check commits against `policy` before they are written
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use gdk::core::GitWorkflowManager;
+    /// use gdk::policy::CommitPolicy;
+    ///
+    /// # fn main() -> gdk::GdkResult<()> {
+    /// let manager = GitWorkflowManager::new("./my-project")?
+    ///     .with_commit_policy(CommitPolicy::for_repo("./my-project")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_commit_policy(mut self, policy: CommitPolicy) -> Self {
+        self.commit_policy = policy;
+        self
+    }
+
+    /// Files that differ between HEAD and `index`, with their line counts
+    fn staged_files(&self, index: &git2::Index) -> GdkResult<Vec<StagedFile>> {
+        let head_tree = self.repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        let diff = self
+            .repo
+            .diff_tree_to_index(head_tree.as_ref(), Some(index), None)
+            .with_git_context("diffing staged changes")?;
+
+        let mut files = Vec::new();
+        for (idx, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let (insertions, deletions) = match git2::Patch::from_diff(&diff, idx)
+                .with_git_context("diffing staged changes")?
+            {
+                Some(patch) => {
+                    let (_, insertions, deletions) = patch.line_stats().with_git_context("counting staged lines")?;
+                    (insertions, deletions)
+                }
+                None => (0, 0),
+            };
+            files.push(StagedFile {
+                path: path.to_string_lossy().into_owned(),
+                insertions,
+                deletions,
+            });
+        }
+        Ok(files)
+    }
+
+    /// Latest thread state per file across the recorded commit history
+    pub fn latest_threads(&self) -> BTreeMap<String, FileThread> {
+        let mut threads = BTreeMap::new();
+        for commit in &self.commit_history {
+            for (path, thread) in &commit.file_threads {
+                threads.insert(path.clone(), thread.clone());
+            }
+        }
+        threads
+    }
+
     /// Execute infinite monkey theorem convergence algorithm
     ///
     /// Attempts to reach convergence through iterative improvement:
     /// 1. Create commit with current state
     /// 2. Analyze quality metrics across all threads
-    /// 3. Check convergence criteria (score > 0.8, stable trend)
+    /// 3. Check convergence with the configured [`ConvergencePolicy`]
     /// 4. If not converged, revert and try again
     /// 5. Repeat until convergence or max attempts reached
     ///
@@ -122,7 +267,7 @@ impl GitWorkflowManager {
     ///
     /// Returns [`GdkError::ConvergenceError`] if convergence not achieved
     pub async fn infinite_monkey_iteration(&mut self, max_attempts: u32) -> GdkResult<CommitNode> {
-        let initial_revert_point = self.create_revert_point("infinite_monkey_start").await?;
+        let initial_revert_point = self.begin_spiral().await?;
 
         for attempt in 1..=max_attempts {
             tracing::info!("Infinite monkey attempt {}/{}", attempt, max_attempts);
@@ -274,6 +419,21 @@ impl GitWorkflow for GitWorkflowManager {
         let (commit_hash, parent_hashes) = {
             let mut index = self.repo.index()?;
             index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+
+            if !self.commit_policy.is_empty() {
+                let branch = self
+                    .repo
+                    .head()
+                    .ok()
+                    .and_then(|head| head.shorthand().map(str::to_string))
+                    .unwrap_or_else(|| self.current_branch.clone());
+                let staged = self.staged_files(&index)?;
+                if let Err(violation) = self.commit_policy.check(&branch, &staged) {
+                    // Leave the rejected changes in the working tree only
+                    index.read(true).with_git_context("unstaging rejected changes")?;
+                    return Err(violation);
+                }
+            }
             index.write()?;
 
             let tree_id = index.write_tree()?;
@@ -386,6 +546,14 @@ impl GitWorkflow for GitWorkflowManager {
         })
     }
 
+    /// Restarts plateau detection, so a spiral is not judged stalled
+    /// against scores from earlier spirals
+    async fn begin_spiral(&mut self) -> GdkResult<RevertPoint> {
+        let point = self.create_revert_point(SPIRAL_START_REASON).await?;
+        self.spiral_start = self.commit_history.len();
+        Ok(point)
+    }
+
     async fn revert_to_point(&mut self, point: &RevertPoint) -> GdkResult<()> {
         let commit_oid = git2::Oid::from_str(&point.commit_hash)?;
         let commit = self.repo.find_commit(commit_oid)?;
@@ -403,15 +571,26 @@ impl GitWorkflow for GitWorkflowManager {
     }
 
     async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics> {
-        let recent_commits = self.commit_history.iter().rev().take(10);
-
-        let quality_trend: Vec<f64> = recent_commits.map(|c| c.health_score).collect();
+        // Analyze the lineage checked out at HEAD, not every recorded attempt
+        let head_tip = self.nearest_recorded_ancestor()?;
+        let graph = CommitGraph::new(&self.commit_history);
+        let lineage = head_tip
+            .as_deref()
+            .map(|tip| graph.lineage(tip))
+            .unwrap_or_default();
+
+        let quality_trend: Vec<f64> = lineage
+            .iter()
+            .rev()
+            .take(10)
+            .map(|c| c.health_score)
+            .collect();
 
-        let is_converged = if quality_trend.len() >= 3 {
-            let recent_avg = quality_trend.iter().take(3).sum::<f64>() / 3.0;
-            recent_avg > 0.8 && quality_trend.windows(2).all(|w| w[0] <= w[1])
-        } else {
-            false
+        let result = match &head_tip {
+            Some(tip) => self
+                .convergence_policy
+                .evaluate_spiral(&self.commit_history, tip, self.spiral_start)?,
+            None => self.convergence_policy.evaluate(&[])?,
         };
 
         Ok(ConvergenceMetrics {
@@ -419,7 +598,15 @@ impl GitWorkflow for GitWorkflowManager {
             successful_builds: quality_trend.iter().filter(|&&q| q > 0.7).count() as u32,
             test_pass_rate: quality_trend.iter().sum::<f64>() / quality_trend.len().max(1) as f64,
             quality_trend,
-            is_converged,
+            is_converged: result.is_converged,
+            confidence_score: result.confidence_score,
+            convergence_factors: Some(result.convergence_factors),
+            recommendations: result.recommendations,
+            last_change_point: result.change_point,
+            is_stalled: result.plateau.is_stalled,
+            pareto: result.pareto,
+            thread_convergence: result.thread_convergence,
+            siblings: result.siblings,
         })
     }
 
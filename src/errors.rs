@@ -6,6 +6,8 @@
 //! - Convergence analysis errors with mathematical context
 //! - Thread management errors with file-specific details
 //! - Agent workflow errors with session context
+//! - Budget exhaustion with the resource that ran out
+//! - Permission denials with the identity and operation refused
 
 use thiserror::Error;
 
@@ -83,6 +85,23 @@ pub enum GdkError {
         suggested_fix: Option<String>,
     },
 
+    /// A session used up one of its budgets
+    #[error("Budget exhausted for agent '{agent_id}': {resource} used {used} of {limit}")]
+    BudgetExhausted {
+        agent_id: String,
+        resource: String,
+        limit: f64,
+        used: f64,
+    },
+
+    /// An identity's role does not allow the operation
+    #[error("Permission denied for '{identity}': {operation} ({reason})")]
+    PermissionDenied {
+        identity: String,
+        operation: String,
+        reason: String,
+    },
+
     /// Visualization generation error
     #[error("Visualization error for format '{format}': {operation}")]
     VisualizationError {
@@ -189,6 +208,34 @@ impl GdkError {
         }
     }
 
+    /// Create a budget error for the resource that ran out
+    pub fn budget_exhausted(
+        agent_id: impl Into<String>,
+        resource: impl Into<String>,
+        limit: f64,
+        used: f64,
+    ) -> Self {
+        Self::BudgetExhausted {
+            agent_id: agent_id.into(),
+            resource: resource.into(),
+            limit,
+            used,
+        }
+    }
+
+    /// Create a permission error for a refused operation
+    pub fn permission_denied(
+        identity: impl Into<String>,
+        operation: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self::PermissionDenied {
+            identity: identity.into(),
+            operation: operation.into(),
+            reason: reason.into(),
+        }
+    }
+
     /// Get the error category for metrics and logging
     pub fn category(&self) -> &'static str {
         match self {
@@ -200,6 +247,8 @@ impl GdkError {
             Self::AgentError { .. } => "agent",
             Self::SerializationError { .. } => "serialization",
             Self::ConfigurationError { .. } => "configuration",
+            Self::BudgetExhausted { .. } => "budget",
+            Self::PermissionDenied { .. } => "permission",
             Self::VisualizationError { .. } => "visualization",
         }
     }
@@ -221,6 +270,8 @@ impl GdkError {
             Self::AgentError { .. } => true,       // Agent ops might succeed
             Self::SerializationError { .. } => false, // Data format issues
             Self::ConfigurationError { .. } => false, // Config needs fixing
+            Self::BudgetExhausted { .. } => false,    // Needs a new session or budget
+            Self::PermissionDenied { .. } => false,   // Needs a different role
             Self::VisualizationError { .. } => true,  // Visualization might succeed
         }
     }
@@ -0,0 +1,322 @@
+//! Statistical forecasting of quality and convergence
+//!
+//! This module fits a least-squares trend to observed quality scores and
+//! projects it forward with residual-based uncertainty:
+//! - Expected quality after a number of further commits, with a prediction interval
+//! - Expected iterations until a target score, with optimistic/pessimistic bounds
+//! - Explicit reasons when no forecast can be made
+//!
+//! # Mathematical Foundation
+//!
+//! For scores `y` observed at steps `x = 0..n`, the fitted line is
+//! `ŷ = a + b·x`. With residual standard error `s = sqrt(SSE / (n - 2))`
+//! and `Sxx = Σ(x - x̄)²`:
+//!
+//! ```text
+//! prediction interval at x₀:  ŷ(x₀) ± t · s · sqrt(1 + 1/n + (x₀ - x̄)² / Sxx)
+//! slope interval:             b ± t · s / sqrt(Sxx)
+//! ```
+//!
+//! where `t` is the Student-t quantile for the configured confidence level
+//! with `n - 2` degrees of freedom.
+//!
+//! # Example Usage
+//!
+//! ```rust
+//! use gdk::forecast::Forecaster;
+//!
+//! let scores = [0.40, 0.46, 0.51, 0.55, 0.62, 0.66];
+//! let model = Forecaster::new().fit(&scores).unwrap();
+//!
+//! let next = model.predict(3);
+//! println!("In 3 commits: {:.2} ({:.2}..{:.2})", next.expected, next.interval.lower, next.interval.upper);
+//!
+//! match model.iterations_to(0.8, 500) {
+//!     Ok(forecast) => println!("~{} iterations to converge", forecast.expected_iterations),
+//!     Err(reason) => println!("No forecast: {reason}"),
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Configuration for trend forecasting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForecastConfig {
+    /// Confidence level of reported intervals (default: 0.9)
+    pub confidence_level: f64,
+    /// Minimum number of observations required to fit (default: 4, at least 3)
+    pub min_points: usize,
+    /// Number of most recent observations used for fitting (default: 20)
+    pub window: usize,
+}
+
+impl Default for ForecastConfig {
+    fn default() -> Self {
+        Self {
+            confidence_level: 0.9,
+            min_points: 4,
+            window: 20,
+        }
+    }
+}
+
+/// Two-sided interval around a forecast value
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ConfidenceInterval {
+    /// Lower bound
+    pub lower: f64,
+    /// Upper bound
+    pub upper: f64,
+}
+
+/// Forecast of the quality score a number of commits ahead
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QualityForecast {
+    /// Number of commits after the latest observation
+    pub steps_ahead: u32,
+    /// Expected quality score (clamped to 0.0-1.0)
+    pub expected: f64,
+    /// Prediction interval at the configured confidence level (clamped to 0.0-1.0)
+    pub interval: ConfidenceInterval,
+}
+
+/// Forecast of the iterations needed to reach a target score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConvergenceForecast {
+    /// Score the forecast is aiming for
+    pub target_score: f64,
+    /// Expected number of further iterations
+    pub expected_iterations: u32,
+    /// Iterations under the optimistic end of the slope interval
+    pub iterations_lower: u32,
+    /// Iterations under the pessimistic end of the slope interval
+    /// (`None` if that slope never reaches the target)
+    pub iterations_upper: Option<u32>,
+    /// Expected quality once the expected iterations have run
+    pub expected_quality: QualityForecast,
+    /// Confidence level of the bounds
+    pub confidence_level: f64,
+}
+
+/// Reason a forecast could not be produced
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ForecastUnavailable {
+    /// Not enough observations to fit a trend with residuals
+    InsufficientHistory { required: usize, available: usize },
+    /// Scores are flat or declining, so the target is never reached
+    NoImprovement { slope: f64 },
+    /// The target lies further out than the allowed horizon
+    BeyondHorizon { expected_iterations: u64, max_horizon: u32 },
+}
+
+impl fmt::Display for ForecastUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForecastUnavailable::InsufficientHistory { required, available } => write!(
+                f,
+                "insufficient history: {available} observations, at least {required} required"
+            ),
+            ForecastUnavailable::NoImprovement { slope } => {
+                write!(f, "quality is not improving (slope {slope:+.4} per commit)")
+            }
+            ForecastUnavailable::BeyondHorizon {
+                expected_iterations,
+                max_horizon,
+            } => write!(
+                f,
+                "target is ~{expected_iterations} iterations away, beyond the {max_horizon} iteration horizon"
+            ),
+        }
+    }
+}
+
+/// Fits trend models to quality score series
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Forecaster {
+    /// Forecasting configuration
+    pub config: ForecastConfig,
+}
+
+impl Forecaster {
+    /// Create a forecaster with default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a forecaster with custom configuration
+    pub fn with_config(config: ForecastConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fit a linear trend to the most recent `window` scores (oldest first)
+    pub fn fit(&self, scores: &[f64]) -> Result<TrendModel, ForecastUnavailable> {
+        let required = self.config.min_points.max(3);
+        let recent = &scores[scores.len().saturating_sub(self.config.window.max(required))..];
+        if recent.len() < required {
+            return Err(ForecastUnavailable::InsufficientHistory {
+                required,
+                available: recent.len(),
+            });
+        }
+
+        let n = recent.len() as f64;
+        let x_mean = (n - 1.0) / 2.0;
+        let y_mean = recent.iter().sum::<f64>() / n;
+        let sxx: f64 = (0..recent.len()).map(|i| (i as f64 - x_mean).powi(2)).sum();
+        let sxy: f64 = recent
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| (i as f64 - x_mean) * (y - y_mean))
+            .sum();
+
+        let slope = sxy / sxx;
+        let intercept = y_mean - slope * x_mean;
+
+        let sse: f64 = recent
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| (y - (intercept + slope * i as f64)).powi(2))
+            .sum();
+        let sst: f64 = recent.iter().map(|&y| (y - y_mean).powi(2)).sum();
+
+        let degrees_of_freedom = recent.len() - 2;
+        Ok(TrendModel {
+            intercept,
+            slope,
+            residual_std: (sse / degrees_of_freedom as f64).sqrt(),
+            r_squared: if sst > 0.0 { 1.0 - sse / sst } else { 1.0 },
+            observations: recent.len(),
+            x_mean,
+            sxx,
+            t_quantile: student_t_quantile(self.config.confidence_level, degrees_of_freedom),
+            confidence_level: self.config.confidence_level,
+        })
+    }
+}
+
+/// Least-squares trend fitted to a score series
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrendModel {
+    /// Fitted score at the oldest observation
+    pub intercept: f64,
+    /// Fitted change in score per commit
+    pub slope: f64,
+    /// Residual standard error of the fit
+    pub residual_std: f64,
+    /// Coefficient of determination (0.0-1.0)
+    pub r_squared: f64,
+    /// Number of observations used
+    pub observations: usize,
+    x_mean: f64,
+    sxx: f64,
+    t_quantile: f64,
+    confidence_level: f64,
+}
+
+impl TrendModel {
+    /// Forecast the score `steps_ahead` commits after the latest observation
+    pub fn predict(&self, steps_ahead: u32) -> QualityForecast {
+        let x = (self.observations - 1) as f64 + steps_ahead as f64;
+        let expected = self.intercept + self.slope * x;
+        let margin = self.t_quantile
+            * self.residual_std
+            * (1.0 + 1.0 / self.observations as f64 + (x - self.x_mean).powi(2) / self.sxx).sqrt();
+
+        QualityForecast {
+            steps_ahead,
+            expected: expected.clamp(0.0, 1.0),
+            interval: ConfidenceInterval {
+                lower: (expected - margin).clamp(0.0, 1.0),
+                upper: (expected + margin).clamp(0.0, 1.0),
+            },
+        }
+    }
+
+    /// Interval for the per-commit slope at the model's confidence level
+    pub fn slope_interval(&self) -> ConfidenceInterval {
+        let margin = self.t_quantile * self.residual_std / self.sxx.sqrt();
+        ConfidenceInterval {
+            lower: self.slope - margin,
+            upper: self.slope + margin,
+        }
+    }
+
+    /// Forecast iterations until the trend reaches `target_score`
+    ///
+    /// Bounds come from the slope interval. Forecasts further out than
+    /// `max_horizon` iterations are reported as unavailable.
+    pub fn iterations_to(
+        &self,
+        target_score: f64,
+        max_horizon: u32,
+    ) -> Result<ConvergenceForecast, ForecastUnavailable> {
+        let current = self.intercept + self.slope * (self.observations - 1) as f64;
+        let gap = target_score - current;
+        let slopes = self.slope_interval();
+
+        let iterations = |slope: f64| -> Option<u64> {
+            if gap <= 0.0 {
+                Some(0)
+            } else if slope > 0.0 {
+                // Tolerate rounding so an exact fit does not need an extra iteration
+                Some((gap / slope - 1e-9).ceil() as u64)
+            } else {
+                None
+            }
+        };
+
+        let expected = iterations(self.slope)
+            .ok_or(ForecastUnavailable::NoImprovement { slope: self.slope })?;
+        if expected > max_horizon as u64 {
+            return Err(ForecastUnavailable::BeyondHorizon {
+                expected_iterations: expected,
+                max_horizon,
+            });
+        }
+
+        let expected = expected as u32;
+        Ok(ConvergenceForecast {
+            target_score,
+            expected_iterations: expected,
+            iterations_lower: iterations(slopes.upper).unwrap_or(0).min(expected as u64) as u32,
+            iterations_upper: iterations(slopes.lower)
+                .filter(|&i| i <= max_horizon as u64)
+                .map(|i| i as u32),
+            expected_quality: self.predict(expected),
+            confidence_level: self.confidence_level,
+        })
+    }
+}
+
+/// Two-sided Student-t quantile for `confidence` with `df` degrees of freedom
+///
+/// Exact for `df <= 2`, where the distribution has closed-form quantiles.
+/// Larger `df` use a four-term Cornish-Fisher expansion of the normal
+/// quantile, accurate to about 1% at `df = 3` and better above.
+fn student_t_quantile(confidence: f64, df: usize) -> f64 {
+    let confidence = confidence.clamp(0.0, 0.999_999);
+    match df {
+        0 | 1 => (std::f64::consts::FRAC_PI_2 * confidence).tan(),
+        2 => confidence * (2.0 / (1.0 - confidence * confidence)).sqrt(),
+        _ => {
+            let z = normal_quantile(0.5 + confidence / 2.0);
+            let v = df as f64;
+            z + (z.powi(3) + z) / (4.0 * v)
+                + (5.0 * z.powi(5) + 16.0 * z.powi(3) + 3.0 * z) / (96.0 * v.powi(2))
+                + (3.0 * z.powi(7) + 19.0 * z.powi(5) + 17.0 * z.powi(3) - 15.0 * z) / (384.0 * v.powi(3))
+                + (79.0 * z.powi(9) + 776.0 * z.powi(7) + 1482.0 * z.powi(5) - 1920.0 * z.powi(3) - 945.0 * z)
+                    / (92160.0 * v.powi(4))
+        }
+    }
+}
+
+/// Standard normal quantile (Abramowitz & Stegun 26.2.23, |error| < 4.5e-4)
+fn normal_quantile(p: f64) -> f64 {
+    if p < 0.5 {
+        return -normal_quantile(1.0 - p);
+    }
+    let t = (-2.0 * (1.0 - p).ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t.powi(3))
+}
@@ -15,15 +15,32 @@
 //! - [`ConvergenceMetrics`]: Mathematical convergence analysis
 //! - [`RevertPoint`]: Intelligent checkpoint for state restoration
 
+pub mod actor;
+pub mod advisor;
+pub mod approval;
 pub mod agent;
+pub mod budget;
 pub mod convergence;
 pub mod core;
 pub mod errors;
+pub mod forecast;
 pub mod git;
+pub mod lineage;
+pub mod mcp;
+pub mod multi_agent;
 pub mod performance;
+pub mod policy;
+pub mod proposer;
 pub mod quality_metrics;
+pub mod replay;
+pub mod rpc;
+pub mod search;
+pub mod server;
+pub mod session;
+pub mod storage;
 pub mod threads;
 pub mod validation;
+pub mod validation_cache;
 pub mod visualization;
 
 // Re-export commonly used types
@@ -126,13 +143,37 @@ pub struct ThreadMetrics {
     pub quality_score: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ConvergenceMetrics {
     pub attempts: u32,
     pub successful_builds: u32,
     pub test_pass_rate: f64,
     pub quality_trend: Vec<f64>,
     pub is_converged: bool,
+    /// Weighted confidence reported by the convergence policy (0.0-1.0)
+    #[serde(default)]
+    pub confidence_score: f64,
+    /// Factor breakdown reported by the convergence policy
+    #[serde(default)]
+    pub convergence_factors: Option<convergence::ConvergenceFactors>,
+    /// Recommendations for reaching convergence
+    #[serde(default)]
+    pub recommendations: Vec<String>,
+    /// Most recent shift in the quality level
+    #[serde(default)]
+    pub last_change_point: Option<convergence::ChangePoint>,
+    /// Whether quality stopped improving within the policy's patience window
+    #[serde(default)]
+    pub is_stalled: bool,
+    /// Pareto front and dominated attempts (only in Pareto mode)
+    #[serde(default)]
+    pub pareto: Option<convergence::ParetoAnalysis>,
+    /// Per-file thread convergence rolled up to the commit level
+    #[serde(default)]
+    pub thread_convergence: convergence::ThreadConvergenceSummary,
+    /// Sibling branches compared against their common base
+    #[serde(default)]
+    pub siblings: Vec<lineage::SiblingComparison>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -163,12 +204,32 @@ pub struct RevertMetadata {
 pub trait GitWorkflow {
     async fn create_commit_node(&mut self, message: &str) -> GdkResult<CommitNode>;
     async fn create_revert_point(&mut self, reason: &str) -> GdkResult<RevertPoint>;
+    /// Create the revert point a spiral starts from; convergence analysis
+    /// then only judges the attempts made after it
+    async fn begin_spiral(&mut self) -> GdkResult<RevertPoint>;
     async fn revert_to_point(&mut self, point: &RevertPoint) -> GdkResult<()>;
     async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics>;
     async fn update_thread_colors(&mut self) -> GdkResult<()>;
     async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool>;
 }
 
+/// [`GitWorkflow`] whose futures are `Send`, for multi-threaded runtimes
+///
+/// Methods take `&self`, so implementations can be shared behind an `Arc`
+/// and called from `tokio::spawn`ed tasks. [`actor::WorkflowHandle`]
+/// implements it by confining a [`core::GitWorkflowManager`] to its own
+/// thread.
+#[async_trait::async_trait]
+pub trait SendGitWorkflow: Send + Sync {
+    async fn create_commit_node(&self, message: &str) -> GdkResult<CommitNode>;
+    async fn create_revert_point(&self, reason: &str) -> GdkResult<RevertPoint>;
+    async fn begin_spiral(&self) -> GdkResult<RevertPoint>;
+    async fn revert_to_point(&self, point: &RevertPoint) -> GdkResult<()>;
+    async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics>;
+    async fn update_thread_colors(&self) -> GdkResult<()>;
+    async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool>;
+}
+
 impl fmt::Display for ThreadColor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let (emoji, name) = match self {
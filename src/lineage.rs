@@ -0,0 +1,214 @@
+//! Commit DAG and branch lineages for convergence analysis
+//!
+//! Commit histories recorded by agents interleave spiral branches: after a
+//! revert, new attempts are children of an older commit rather than of the
+//! previous attempt. This module rebuilds the parent graph from
+//! [`CommitNode::parent_hashes`] so analysis can follow real ancestry:
+//! - First-parent lineage from any tip back to the oldest known ancestor
+//! - Branch tips (commits without known children)
+//! - Fork points where sibling spiral branches diverge, compared against
+//!   their common base
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::lineage::CommitGraph;
+//! # let commit_history: Vec<gdk::CommitNode> = Vec::new();
+//!
+//! let graph = CommitGraph::new(&commit_history);
+//! for tip in graph.tips() {
+//!     let lineage = graph.lineage(&tip.hash);
+//!     println!("{}: {} commits", tip.hash, lineage.len());
+//! }
+//! for comparison in graph.compare_siblings() {
+//!     println!("fork at {}: best tip {:?}", comparison.base_hash, comparison.best_tip);
+//! }
+//! ```
+
+use crate::CommitNode;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Summary of one branch measured from its fork point
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BranchLineage {
+    /// Hash of the branch tip
+    pub tip_hash: String,
+    /// Commits on the branch after the fork point
+    pub commits_since_base: usize,
+    /// Health score of the tip
+    pub latest_score: f64,
+    /// Mean health score of the commits after the fork point
+    pub mean_score: f64,
+    /// Tip score minus base score
+    pub delta_from_base: f64,
+}
+
+/// Sibling branches that diverge from the same base commit
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SiblingComparison {
+    /// Hash of the common base (fork point)
+    pub base_hash: String,
+    /// Health score of the base
+    pub base_score: f64,
+    /// Every branch tip descending from the base, in chronological order
+    pub branches: Vec<BranchLineage>,
+    /// Tip with the highest score, if any branch improved on the base
+    pub best_tip: Option<String>,
+}
+
+/// Parent graph over a set of commit nodes
+///
+/// Edges point from parent to child. Parents outside the given nodes are
+/// ignored, so a lineage ends at the oldest ancestor that is present.
+pub struct CommitGraph<'a> {
+    commits: &'a [CommitNode],
+    graph: DiGraph<usize, ()>,
+    node_indices: HashMap<&'a str, NodeIndex>,
+}
+
+impl<'a> CommitGraph<'a> {
+    /// Build the graph for a commit history
+    pub fn new(commits: &'a [CommitNode]) -> Self {
+        let mut graph = DiGraph::new();
+        let mut node_indices = HashMap::new();
+
+        for (position, commit) in commits.iter().enumerate() {
+            node_indices.insert(commit.hash.as_str(), graph.add_node(position));
+        }
+        for commit in commits {
+            let child = node_indices[commit.hash.as_str()];
+            for parent in &commit.parent_hashes {
+                if let Some(&parent) = node_indices.get(parent.as_str()) {
+                    graph.add_edge(parent, child, ());
+                }
+            }
+        }
+
+        Self {
+            commits,
+            graph,
+            node_indices,
+        }
+    }
+
+    /// Whether any commit has a known parent
+    ///
+    /// Histories without links (e.g. hand-built fixtures) carry no ancestry
+    /// and are treated as linear by callers.
+    pub fn has_links(&self) -> bool {
+        self.graph.edge_count() > 0
+    }
+
+    /// Look up a commit by hash
+    pub fn get(&self, hash: &str) -> Option<&'a CommitNode> {
+        self.node_indices
+            .get(hash)
+            .map(|&index| &self.commits[self.graph[index]])
+    }
+
+    /// First-parent lineage ending at `tip_hash`, oldest first
+    ///
+    /// Returns an empty lineage if the tip is unknown.
+    pub fn lineage(&self, tip_hash: &str) -> Vec<&'a CommitNode> {
+        let mut lineage = Vec::new();
+        let mut current = self.get(tip_hash);
+
+        while let Some(commit) = current {
+            lineage.push(commit);
+            current = commit
+                .parent_hashes
+                .first()
+                .and_then(|parent| self.get(parent))
+                // Guard against malformed cycles
+                .filter(|parent| !lineage.iter().any(|seen| seen.hash == parent.hash));
+        }
+
+        lineage.reverse();
+        lineage
+    }
+
+    /// Lineage of the most recently recorded commit
+    ///
+    /// Without any parent links the whole history is returned as-is.
+    pub fn latest_lineage(&self) -> Vec<&'a CommitNode> {
+        match self.commits.last() {
+            Some(latest) if self.has_links() => self.lineage(&latest.hash),
+            _ => self.commits.iter().collect(),
+        }
+    }
+
+    /// Commits without known children, in chronological order
+    pub fn tips(&self) -> Vec<&'a CommitNode> {
+        self.commits
+            .iter()
+            .filter(|commit| {
+                let index = self.node_indices[commit.hash.as_str()];
+                self.graph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .next()
+                    .is_none()
+            })
+            .collect()
+    }
+
+    /// Compare sibling branches at every fork point
+    ///
+    /// A fork point is a commit that is the first parent of two or more
+    /// commits. Each tip whose lineage passes through it is summarised
+    /// against the fork's score.
+    pub fn compare_siblings(&self) -> Vec<SiblingComparison> {
+        let mut first_parent_children: HashMap<&str, usize> = HashMap::new();
+        for commit in self.commits {
+            if let Some(parent) = commit.parent_hashes.first() {
+                if let Some(parent) = self.get(parent) {
+                    *first_parent_children.entry(parent.hash.as_str()).or_default() += 1;
+                }
+            }
+        }
+
+        let tip_lineages: Vec<Vec<&CommitNode>> = self
+            .tips()
+            .into_iter()
+            .map(|tip| self.lineage(&tip.hash))
+            .collect();
+
+        self.commits
+            .iter()
+            .filter(|base| first_parent_children.get(base.hash.as_str()).copied().unwrap_or(0) >= 2)
+            .map(|base| {
+                let branches: Vec<BranchLineage> = tip_lineages
+                    .iter()
+                    .filter_map(|lineage| {
+                        let fork = lineage.iter().position(|c| c.hash == base.hash)?;
+                        let since_base = &lineage[fork + 1..];
+                        let tip = since_base.last()?;
+                        Some(BranchLineage {
+                            tip_hash: tip.hash.clone(),
+                            commits_since_base: since_base.len(),
+                            latest_score: tip.health_score,
+                            mean_score: since_base.iter().map(|c| c.health_score).sum::<f64>()
+                                / since_base.len() as f64,
+                            delta_from_base: tip.health_score - base.health_score,
+                        })
+                    })
+                    .collect();
+
+                let best_tip = branches
+                    .iter()
+                    .filter(|branch| branch.delta_from_base > 0.0)
+                    .max_by(|a, b| a.latest_score.total_cmp(&b.latest_score))
+                    .map(|branch| branch.tip_hash.clone());
+
+                SiblingComparison {
+                    base_hash: base.hash.clone(),
+                    base_score: base.health_score,
+                    branches,
+                    best_tip,
+                }
+            })
+            .collect()
+    }
+}
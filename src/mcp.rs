@@ -0,0 +1,426 @@
+//! Model Context Protocol server for GDK
+//!
+//! Exposes GDK to MCP-speaking agents over the stdio transport
+//! (newline-delimited JSON-RPC 2.0, see [`crate::rpc`]):
+//! - **Tools**: commit with quality analysis, create or revert checkpoints,
+//!   run a validation suite, read thread colors, render the commit tree
+//! - **Resources**: per-file thread state and the convergence report
+//!
+//! Tools take an optional `agent_id`; sessions are started on first use so
+//! agents need no setup calls beyond the MCP handshake.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::mcp::McpServer;
+//! use tokio::io::BufReader;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     let mut server = McpServer::new(controller);
+//!     server
+//!         .serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+//!         .await
+//! }
+//! ```
+
+use crate::agent::AgentWorkflowController;
+use crate::core::GitWorkflowManager;
+use crate::rpc::{
+    handle_line, serve_lines, Notifier, RpcError, RpcHandler, RpcRequest, INVALID_PARAMS, METHOD_NOT_FOUND,
+};
+use crate::validation::ValidationSuite;
+use crate::validation_cache::ValidationCacheConfig;
+use crate::visualization::{
+    export_tree_ascii, export_tree_html, export_tree_svg, VisualizationConfig,
+};
+use crate::{GdkError, GdkResult, GitWorkflow};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use tokio::io::{AsyncBufRead, AsyncWrite};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// MCP protocol revision implemented by this server
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Agent id used when a tool call does not name one
+pub const DEFAULT_AGENT_ID: &str = "mcp-agent";
+
+const CONVERGENCE_URI: &str = "gdk://convergence";
+const THREADS_URI: &str = "gdk://threads";
+
+#[derive(Deserialize)]
+struct ToolCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+#[derive(Deserialize)]
+struct ResourceRead {
+    uri: String,
+}
+
+#[derive(Deserialize)]
+struct CommitArgs {
+    agent_id: Option<String>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CheckpointArgs {
+    agent_id: Option<String>,
+    #[serde(default = "default_reason")]
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct AgentArgs {
+    agent_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ValidateArgs {
+    #[serde(default = "default_preset")]
+    preset: String,
+    #[serde(default)]
+    cache: bool,
+}
+
+#[derive(Deserialize)]
+struct ThreadColorArgs {
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RenderArgs {
+    #[serde(default = "default_format")]
+    format: String,
+    #[serde(default = "default_true")]
+    show_health: bool,
+    #[serde(default = "default_true")]
+    show_threads: bool,
+}
+
+fn default_reason() -> String {
+    "mcp checkpoint".to_string()
+}
+
+fn default_preset() -> String {
+    "rust".to_string()
+}
+
+fn default_format() -> String {
+    "ascii".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Serves MCP requests against an agent workflow controller
+pub struct McpServer {
+    /// Controller that tool calls are dispatched to
+    pub controller: AgentWorkflowController<GitWorkflowManager>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl RpcHandler for McpServer {
+    async fn handle(&mut self, request: &RpcRequest, _notifier: &Notifier) -> Result<Value, RpcError> {
+        match request.method.as_str() {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {}, "resources": {} },
+                "serverInfo": { "name": "gdk", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "notifications/initialized" | "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => {
+                let call: ToolCall = params(&request.params)?;
+                // Tool failures are results the model can see, not protocol errors
+                Ok(match self.call_tool(&call.name, &call.arguments).await? {
+                    Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+                    Err(error) => json!({
+                        "content": [{ "type": "text", "text": error.to_string() }],
+                        "isError": true,
+                    }),
+                })
+            }
+            "resources/list" => Ok(json!({ "resources": self.resource_list() })),
+            "resources/read" => {
+                let ResourceRead { uri } = params(&request.params)?;
+                let text = self.read_resource(&uri).await?;
+                Ok(json!({
+                    "contents": [{ "uri": uri, "mimeType": "application/json", "text": text }],
+                }))
+            }
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {other}"),
+            )),
+        }
+    }
+}
+
+impl McpServer {
+    pub fn new(controller: AgentWorkflowController<GitWorkflowManager>) -> Self {
+        Self { controller }
+    }
+
+    /// Serve newline-delimited MCP messages until the reader reaches EOF
+    pub async fn serve<R, W>(&mut self, reader: R, writer: W) -> GdkResult<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        serve_lines(self, reader, writer).await
+    }
+
+    /// Handle one line of input, returning the serialized reply (if any)
+    pub async fn handle_line(&mut self, line: &str, notifications: &UnboundedSender<String>) -> Option<String> {
+        handle_line(self, line, &Notifier::new(notifications.clone())).await
+    }
+
+    /// Run a tool, separating protocol errors (outer) from tool failures (inner)
+    async fn call_tool(&mut self, name: &str, arguments: &Value) -> Result<GdkResult<String>, RpcError> {
+        let result = match name {
+            "gdk_commit" => {
+                let args: CommitArgs = params(arguments)?;
+                let agent_id = self.ensure_session(args.agent_id).await?;
+                self.controller
+                    .validate_and_commit(&agent_id, &args.message)
+                    .await
+                    .map(|commit| pretty(&commit))
+            }
+            "gdk_checkpoint" => {
+                let args: CheckpointArgs = params(arguments)?;
+                let agent_id = self.ensure_session(args.agent_id).await?;
+                self.controller
+                    .create_spiral_checkpoint(&agent_id, &args.reason)
+                    .await
+                    .map(|point| pretty(&point))
+            }
+            "gdk_revert" => {
+                let args: AgentArgs = params(arguments)?;
+                let agent_id = self.ensure_session(args.agent_id).await?;
+                self.controller
+                    .revert_to_last_checkpoint(&agent_id)
+                    .await
+                    .map(|()| "Reverted to last checkpoint".to_string())
+            }
+            "gdk_validate" => {
+                let args: ValidateArgs = params(arguments)?;
+                self.validate(&args.preset, args.cache).await.map(|result| pretty(&result))
+            }
+            "gdk_thread_colors" => {
+                let args: ThreadColorArgs = params(arguments)?;
+                let colors: BTreeMap<String, Value> = self
+                    .controller
+                    .workflow
+                    .latest_threads()
+                    .into_iter()
+                    .filter(|(path, _)| args.files.is_empty() || args.files.contains(path))
+                    .map(|(path, thread)| {
+                        let entry = json!({
+                            "color": thread.color_status,
+                            "lint_score": thread.lint_score,
+                            "type_check_score": thread.type_check_score,
+                            "test_coverage": thread.test_coverage,
+                            "functionality_score": thread.functionality_score,
+                        });
+                        (path, entry)
+                    })
+                    .collect();
+                Ok(pretty(&colors))
+            }
+            "gdk_render_tree" => {
+                let args: RenderArgs = params(arguments)?;
+                let commits = &self.controller.workflow.commit_history;
+                let config = VisualizationConfig {
+                    show_health_scores: args.show_health,
+                    show_thread_colors: args.show_threads,
+                    ..Default::default()
+                };
+                let rendered = match args.format.as_str() {
+                    "ascii" | "txt" => export_tree_ascii(commits, Some(config)),
+                    "svg" => export_tree_svg(commits, Some(config)),
+                    "html" => export_tree_html(commits, Some(config)),
+                    other => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            format!("Unsupported format: {other}. Use 'ascii', 'svg', or 'html'"),
+                        ))
+                    }
+                };
+                rendered.map_err(|e| {
+                    GdkError::VisualizationError {
+                        format: args.format.clone(),
+                        operation: "render tree".to_string(),
+                        node_count: commits.len(),
+                        source: e.into(),
+                    }
+                })
+            }
+            other => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Unknown tool: {other}"),
+                ))
+            }
+        };
+        Ok(result)
+    }
+
+    async fn ensure_session(&mut self, agent_id: Option<String>) -> Result<String, RpcError> {
+        let agent_id = agent_id.unwrap_or_else(|| DEFAULT_AGENT_ID.to_string());
+        if !self.controller.has_live_session(&agent_id) {
+            self.controller.start_agent_session(&agent_id).await?;
+        }
+        Ok(agent_id)
+    }
+
+    async fn validate(&self, preset: &str, cache: bool) -> GdkResult<crate::validation::ValidationResult> {
+        let repo_path = &self.controller.workflow.repo_path;
+        let mut suite = ValidationSuite::from_preset(preset, repo_path)?;
+        if cache {
+            suite.set_cache(ValidationCacheConfig::default());
+        }
+        suite.validate(repo_path).await
+    }
+
+    fn resource_list(&self) -> Vec<Value> {
+        let mut resources = vec![
+            json!({
+                "uri": CONVERGENCE_URI,
+                "name": "Convergence report",
+                "description": "Convergence metrics for the lineage checked out at HEAD",
+                "mimeType": "application/json",
+            }),
+            json!({
+                "uri": THREADS_URI,
+                "name": "Thread state",
+                "description": "Latest quality thread for every tracked file",
+                "mimeType": "application/json",
+            }),
+        ];
+        resources.extend(self.controller.workflow.latest_threads().into_keys().map(|path| {
+            json!({
+                "uri": format!("{THREADS_URI}/{path}"),
+                "name": format!("Thread: {path}"),
+                "mimeType": "application/json",
+            })
+        }));
+        resources
+    }
+
+    async fn read_resource(&self, uri: &str) -> Result<String, RpcError> {
+        if uri == CONVERGENCE_URI {
+            return Ok(pretty(&self.controller.workflow.analyze_convergence().await?));
+        }
+        if uri == THREADS_URI {
+            return Ok(pretty(&self.controller.workflow.latest_threads()));
+        }
+        if let Some(path) = uri.strip_prefix(&format!("{THREADS_URI}/")) {
+            if let Some(thread) = self.controller.workflow.latest_threads().get(path) {
+                return Ok(pretty(thread));
+            }
+        }
+        Err(RpcError::new(
+            INVALID_PARAMS,
+            format!("Resource not found: {uri}"),
+        ))
+    }
+}
+
+fn tool_definitions() -> Value {
+    let agent_id = json!({ "type": "string", "description": "Agent session id (default: mcp-agent)" });
+    json!([
+        {
+            "name": "gdk_commit",
+            "description": "Commit the working tree with quality thread analysis and CI validation",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "agent_id": agent_id,
+                    "message": { "type": "string", "description": "Commit message" },
+                },
+                "required": ["message"],
+            },
+        },
+        {
+            "name": "gdk_checkpoint",
+            "description": "Create a revert checkpoint at the current commit",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "agent_id": agent_id,
+                    "reason": { "type": "string", "description": "Why the checkpoint is taken" },
+                },
+            },
+        },
+        {
+            "name": "gdk_revert",
+            "description": "Hard-reset the working tree to the agent's last checkpoint",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "agent_id": agent_id },
+            },
+        },
+        {
+            "name": "gdk_validate",
+            "description": "Run a validation suite (lint, type check, tests) and score the result",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "preset": {
+                        "type": "string",
+                        "enum": ["rust", "python", "node", "go", "polyglot"],
+                        "default": "rust",
+                    },
+                    "cache": { "type": "boolean", "default": false },
+                },
+            },
+        },
+        {
+            "name": "gdk_thread_colors",
+            "description": "Quality thread colors and scores per file",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "files": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Files to report (default: all tracked files)",
+                    },
+                },
+            },
+        },
+        {
+            "name": "gdk_render_tree",
+            "description": "Render the commit tree with health scores and thread colors",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "format": { "type": "string", "enum": ["ascii", "svg", "html"], "default": "ascii" },
+                    "show_health": { "type": "boolean", "default": true },
+                    "show_threads": { "type": "boolean", "default": true },
+                },
+            },
+        },
+    ])
+}
+
+fn params<P: DeserializeOwned>(params: &Value) -> Result<P, RpcError> {
+    serde_json::from_value(params.clone())
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid params: {e}")))
+}
+
+fn pretty(value: &impl serde::Serialize) -> String {
+    format!("{:#}", json!(value))
+}
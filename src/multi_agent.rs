@@ -0,0 +1,586 @@
+//! Concurrent multi-agent coordination with per-agent git worktrees
+//!
+//! [`AgentWorkflowController`] drives every agent through one
+//! [`GitWorkflowManager`] and one working directory, so one agent's revert
+//! resets everybody's files. This module isolates agents instead:
+//! - Each agent works in its own linked worktree on branch `gdk/agents/<id>`
+//! - Worktrees live under `<git common dir>/gdk/worktrees`
+//! - Ref updates from all worktrees are serialized through a [`RefLock`]
+//! - Every agent is bound by the repository's commit and access policies,
+//!   and its converged spirals wait in the repository's approval queue
+//! - Each agent's controller runs on its own thread behind an [`AgentHandle`]
+//! - The controller is `Send + Sync` and its methods take `&self`, so
+//!   sessions can be `tokio::spawn`ed and run in parallel
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::multi_agent::MultiAgentController;
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let controller = Arc::new(MultiAgentController::new("./repo")?);
+//!     controller.start_agent_session("agent-1").await?;
+//!     controller.start_agent_session("agent-2").await?;
+//!
+//!     // Both agents iterate at the same time in separate worktrees
+//!     let first = tokio::spawn({
+//!         let controller = controller.clone();
+//!         async move { controller.execute_infinite_monkey_workflow("agent-1", 0.8).await }
+//!     });
+//!     let second = controller.execute_infinite_monkey_workflow("agent-2", 0.8).await;
+//!     println!("agent-1: {:?}, agent-2: {:?}", first.await?.is_ok(), second.is_ok());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::agent::{AgentAction, AgentStatistics, AgentWorkflowController};
+use crate::approval::ApprovalGate;
+use crate::core::GitWorkflowManager;
+use crate::policy::{AccessPolicy, CommitPolicy};
+use crate::storage::{LockFile, LockFileGuard, LOCK_RETRY, LOCK_TIMEOUT};
+use crate::validation_cache::git_common_dir;
+use crate::{CommitNode, ConvergenceMetrics, GdkError, GdkResult, GdkResultExt, GitWorkflow, RevertPoint};
+use futures::future::LocalBoxFuture;
+use git2::{BranchType, Repository, WorktreeAddOptions, WorktreePruneOptions};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{mpsc, oneshot, Mutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+/// Branch namespace for agent worktrees
+const AGENT_BRANCH_PREFIX: &str = "gdk/agents";
+
+/// Lock serializing ref updates across worktrees of one repository
+///
+/// Inside a process the lock is a tokio mutex shared by every clone. Across
+/// processes it is a `refs.lock` [`LockFile`] in the git common directory.
+#[derive(Debug, Clone)]
+pub struct RefLock {
+    file: LockFile,
+    local: Arc<Mutex<()>>,
+}
+
+/// Held while ref updates are in progress; releases the lock on drop
+#[derive(Debug)]
+pub struct RefLockGuard {
+    _file: LockFileGuard,
+    _local: OwnedMutexGuard<()>,
+}
+
+impl RefLock {
+    /// Create a lock backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: LockFile::new(path),
+            local: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Lock file used to exclude other processes
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Wait until no other task or process holds the lock
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::FileSystemError`] if the lock file cannot be
+    /// created, or stays held by another process for 30 seconds
+    pub async fn acquire(&self) -> GdkResult<RefLockGuard> {
+        let local = self.local.clone().lock_owned().await;
+        let started = Instant::now();
+        loop {
+            if let Some(file) = self.file.try_acquire()? {
+                return Ok(RefLockGuard {
+                    _file: file,
+                    _local: local,
+                });
+            }
+            if started.elapsed() >= LOCK_TIMEOUT {
+                return Err(self.file.held_too_long());
+            }
+            tokio::time::sleep(LOCK_RETRY).await;
+        }
+    }
+}
+
+/// [`GitWorkflowManager`] for one agent worktree
+///
+/// Operations that move refs (commits and reverts) hold the shared
+/// [`RefLock`]; everything else runs without it.
+pub struct WorktreeWorkflow {
+    /// Workflow manager opened on the agent's worktree
+    pub manager: GitWorkflowManager,
+    /// Lock shared with every other worktree of the repository
+    pub ref_lock: RefLock,
+}
+
+#[async_trait::async_trait(?Send)]
+impl GitWorkflow for WorktreeWorkflow {
+    async fn create_commit_node(&mut self, message: &str) -> GdkResult<CommitNode> {
+        let _guard = self.ref_lock.acquire().await?;
+        self.manager.create_commit_node(message).await
+    }
+
+    async fn create_revert_point(&mut self, reason: &str) -> GdkResult<RevertPoint> {
+        self.manager.create_revert_point(reason).await
+    }
+
+    async fn begin_spiral(&mut self) -> GdkResult<RevertPoint> {
+        self.manager.begin_spiral().await
+    }
+
+    async fn revert_to_point(&mut self, point: &RevertPoint) -> GdkResult<()> {
+        let _guard = self.ref_lock.acquire().await?;
+        self.manager.revert_to_point(point).await
+    }
+
+    async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics> {
+        self.manager.analyze_convergence().await
+    }
+
+    async fn update_thread_colors(&mut self) -> GdkResult<()> {
+        self.manager.update_thread_colors().await
+    }
+
+    async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool> {
+        self.manager.validate_ci_cd(commit_hash).await
+    }
+}
+
+/// Controller for one agent working in its own worktree
+pub type WorktreeController = AgentWorkflowController<WorktreeWorkflow>;
+
+type AgentCall = Box<dyn for<'a> FnOnce(&'a mut WorktreeController) -> LocalBoxFuture<'a, ()> + Send>;
+
+/// Cloneable, `Send + Sync` handle to one agent's controller
+///
+/// [`GitWorkflow`] futures are not `Send`, so the controller is confined to
+/// a dedicated `gdk-agent` thread with a single-threaded runtime, the same
+/// way [`WorkflowHandle`](crate::actor::WorkflowHandle) confines a manager.
+/// Calls run one at a time in the order they were sent, and the thread
+/// exits once every handle has been dropped.
+#[derive(Clone)]
+pub struct AgentHandle {
+    agent_id: String,
+    sender: mpsc::UnboundedSender<AgentCall>,
+}
+
+impl AgentHandle {
+    /// Start the agent's thread and session in the worktree at `worktree_path`
+    async fn spawn(
+        agent_id: &str,
+        worktree_path: PathBuf,
+        ref_lock: RefLock,
+        caller: Option<String>,
+    ) -> GdkResult<(Self, Uuid)> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (sender, mut receiver) = mpsc::unbounded_channel::<AgentCall>();
+        let (started, session) = oneshot::channel();
+        let id = agent_id.to_string();
+
+        std::thread::Builder::new()
+            .name("gdk-agent".to_string())
+            .spawn(move || {
+                runtime.block_on(async {
+                    let mut controller = match start_controller(&id, &worktree_path, ref_lock, caller).await {
+                        Ok((controller, session_id)) => {
+                            let _ = started.send(Ok(session_id));
+                            controller
+                        }
+                        Err(e) => {
+                            let _ = started.send(Err(e));
+                            return;
+                        }
+                    };
+                    while let Some(call) = receiver.recv().await {
+                        call(&mut controller).await;
+                    }
+                });
+                tracing::debug!("Controller for agent {} stopped", id);
+            })?;
+
+        let session_id = session
+            .await
+            .map_err(|_| agent_stopped(agent_id, "session_start"))??;
+        Ok((
+            Self {
+                agent_id: agent_id.to_string(),
+                sender,
+            },
+            session_id,
+        ))
+    }
+
+    /// Agent this handle drives
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    /// Run `f` against the agent's controller on its thread
+    ///
+    /// Use this for operations not wrapped by [`MultiAgentController`]:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(controller: &gdk::multi_agent::MultiAgentController) -> gdk::GdkResult<()> {
+    /// use gdk::GitWorkflow;
+    ///
+    /// let agent = controller.agent("agent-1")?;
+    /// let node = agent
+    ///     .call(|controller| Box::pin(async move { controller.workflow.create_commit_node("wip").await }))
+    ///     .await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent's thread has stopped
+    pub async fn call<R, F>(&self, f: F) -> GdkResult<R>
+    where
+        R: Send + 'static,
+        F: for<'a> FnOnce(&'a mut WorktreeController) -> LocalBoxFuture<'a, R> + Send + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        let call: AgentCall = Box::new(move |controller| {
+            Box::pin(async move {
+                let _ = reply.send(f(controller).await);
+            })
+        });
+        self.sender
+            .send(call)
+            .map_err(|_| agent_stopped(&self.agent_id, "call"))?;
+        response.await.map_err(|_| agent_stopped(&self.agent_id, "call"))
+    }
+}
+
+impl std::fmt::Debug for AgentHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgentHandle")
+            .field("agent_id", &self.agent_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Open the agent's worktree and start its session; runs on the agent thread
+async fn start_controller(
+    agent_id: &str,
+    worktree_path: &Path,
+    ref_lock: RefLock,
+    caller: Option<String>,
+) -> GdkResult<(WorktreeController, Uuid)> {
+    // Worktrees share the main repository's policies and approval queue
+    let manager = GitWorkflowManager::new(&worktree_path.to_string_lossy())?
+        .with_commit_policy(CommitPolicy::for_repo(worktree_path)?);
+    let mut controller = AgentWorkflowController::new(WorktreeWorkflow { manager, ref_lock })
+        .with_approval_gate(ApprovalGate::for_repo(worktree_path)?)
+        .with_policy(AccessPolicy::for_repo(worktree_path)?);
+    controller.caller = caller;
+    let session_id = controller.start_agent_session(agent_id).await?;
+    Ok((controller, session_id))
+}
+
+fn agent_stopped(agent_id: &str, operation: &str) -> GdkError {
+    GdkError::agent_error(agent_id, operation, None, "Agent controller thread has stopped")
+}
+
+/// Runs several agent sessions concurrently, each in its own worktree
+///
+/// The controller is `Send + Sync` and every method takes `&self`: each
+/// agent's controller runs on its own thread behind an [`AgentHandle`], and
+/// ref updates go through the repository-wide [`RefLock`], so one
+/// controller can drive many agents at once from `tokio::spawn`ed tasks,
+/// `tokio::join!` or [`futures::future::join_all`]. The lock file also
+/// excludes other processes working on the same repository.
+pub struct MultiAgentController {
+    /// Path of the main working tree
+    pub repo_path: PathBuf,
+    /// Directory holding the agent worktrees
+    pub worktree_root: PathBuf,
+    ref_lock: RefLock,
+    caller: Option<String>,
+    agents: RwLock<HashMap<String, AgentHandle>>,
+}
+
+impl MultiAgentController {
+    /// Create a controller for the repository at `repo_path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository cannot be opened
+    pub fn new(repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let repo = Repository::open(repo_path.as_ref()).with_git_context("opening repository")?;
+        let gdk_dir = git_common_dir(&repo).join("gdk");
+
+        Ok(Self {
+            repo_path: repo_path.as_ref().to_path_buf(),
+            worktree_root: gdk_dir.join("worktrees"),
+            ref_lock: RefLock::new(gdk_dir.join("refs.lock")),
+            caller: None,
+            agents: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Authorize every agent's actions by `identity`'s role
+    ///
+    /// Applies to sessions started afterwards; see
+    /// [`AgentWorkflowController::with_caller`].
+    pub fn with_caller(mut self, identity: &str) -> Self {
+        self.caller = Some(identity.to_string());
+        self
+    }
+
+    /// Branch an agent's worktree has checked out
+    pub fn agent_branch(agent_id: &str) -> String {
+        format!("{AGENT_BRANCH_PREFIX}/{agent_id}")
+    }
+
+    /// Lock shared by every agent worktree of this repository
+    pub fn ref_lock(&self) -> &RefLock {
+        &self.ref_lock
+    }
+
+    /// Agents with an active session, sorted by id
+    pub fn agent_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.agents.read().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Worktree an agent is working in
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent has no session
+    pub fn worktree_path(&self, agent_id: &str) -> GdkResult<PathBuf> {
+        self.agent(agent_id)?;
+        Ok(self.worktree_root.join(agent_id))
+    }
+
+    /// Handle to an agent's controller, for operations not wrapped here
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent has no session
+    pub fn agent(&self, agent_id: &str) -> GdkResult<AgentHandle> {
+        self.agents.read().get(agent_id).cloned().ok_or_else(|| {
+            GdkError::agent_error(
+                agent_id,
+                "session_lookup",
+                None,
+                format!("No active session for agent {agent_id}"),
+            )
+        })
+    }
+
+    /// Start a session for `agent_id` in its own worktree
+    ///
+    /// The worktree checks out `gdk/agents/<agent_id>`, branched from the
+    /// main working tree's HEAD the first time the agent is seen. A
+    /// worktree left behind by an earlier session is reused, so an agent
+    /// picks up where its branch left off.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent already has a session,
+    /// [`GdkError::ConfigurationError`] if the id contains anything other
+    /// than ASCII letters, digits, `-` and `_`, or [`GdkError::GitError`] if
+    /// the worktree cannot be created
+    pub async fn start_agent_session(&self, agent_id: &str) -> GdkResult<Uuid> {
+        if self.agents.read().contains_key(agent_id) {
+            return Err(GdkError::agent_error(
+                agent_id,
+                "session_start",
+                None,
+                format!("Agent {agent_id} already has an active session"),
+            ));
+        }
+        // The id doubles as the worktree name, so anything that would need
+        // escaping could make two agents share a worktree
+        let usable = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if agent_id.is_empty() || !agent_id.chars().all(usable) {
+            return Err(GdkError::configuration_error(
+                "agent_id",
+                format!("'{agent_id}' cannot be used as an agent worktree name"),
+                Some("Use letters, digits, '-' and '_' in agent ids".to_string()),
+            ));
+        }
+        let branch = Self::agent_branch(agent_id);
+
+        let worktree_path = {
+            let _guard = self.ref_lock.acquire().await?;
+            self.add_worktree(agent_id, &branch)?
+        };
+        let (handle, session_id) =
+            AgentHandle::spawn(agent_id, worktree_path.clone(), self.ref_lock.clone(), self.caller.clone()).await?;
+
+        // Another task may have started the same agent while we were waiting
+        let mut agents = self.agents.write();
+        if agents.contains_key(agent_id) {
+            return Err(GdkError::agent_error(
+                agent_id,
+                "session_start",
+                None,
+                format!("Agent {agent_id} already has an active session"),
+            ));
+        }
+        agents.insert(agent_id.to_string(), handle);
+        drop(agents);
+
+        tracing::info!("Agent {} working in {}", agent_id, worktree_path.display());
+        Ok(session_id)
+    }
+
+    /// End an agent's session and remove its worktree
+    ///
+    /// Waits for the agent's in-flight operation to finish. The agent's
+    /// branch is kept so its commits can be reviewed or merged.
+    ///
+    /// # Returns
+    ///
+    /// Name of the branch holding the agent's work
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::AgentError`] if the agent has no session, or
+    /// [`GdkError::GitError`] if the worktree cannot be pruned
+    pub async fn end_agent_session(&self, agent_id: &str) -> GdkResult<String> {
+        let agent = self.agent(agent_id)?;
+        // Let queued calls finish before pulling the worktree away
+        agent.call(|_| Box::pin(async {})).await?;
+        self.agents.write().remove(agent_id);
+        drop(agent);
+
+        let _guard = self.ref_lock.acquire().await?;
+        let repo = Repository::open(&self.repo_path).with_git_context("opening repository")?;
+        if let Ok(worktree) = repo.find_worktree(agent_id) {
+            worktree
+                .prune(Some(WorktreePruneOptions::new().valid(true).working_tree(true)))
+                .with_git_context("removing agent worktree")?;
+        }
+        Ok(Self::agent_branch(agent_id))
+    }
+
+    /// Run the infinite monkey workflow for one agent
+    ///
+    /// See [`AgentWorkflowController::execute_infinite_monkey_workflow`].
+    pub async fn execute_infinite_monkey_workflow(
+        &self,
+        agent_id: &str,
+        target_convergence: f64,
+    ) -> GdkResult<CommitNode> {
+        let id = agent_id.to_string();
+        self.agent(agent_id)?
+            .call(move |controller| {
+                Box::pin(async move {
+                    controller
+                        .execute_infinite_monkey_workflow(&id, target_convergence)
+                        .await
+                })
+            })
+            .await?
+    }
+
+    /// Create a checkpoint in the agent's worktree
+    pub async fn create_spiral_checkpoint(&self, agent_id: &str, reason: &str) -> GdkResult<RevertPoint> {
+        let (id, reason) = (agent_id.to_string(), reason.to_string());
+        self.agent(agent_id)?
+            .call(move |controller| Box::pin(async move { controller.create_spiral_checkpoint(&id, &reason).await }))
+            .await?
+    }
+
+    /// Revert the agent's worktree to its last checkpoint
+    pub async fn revert_to_last_checkpoint(&self, agent_id: &str) -> GdkResult<()> {
+        let id = agent_id.to_string();
+        self.agent(agent_id)?
+            .call(move |controller| Box::pin(async move { controller.revert_to_last_checkpoint(&id).await }))
+            .await?
+    }
+
+    /// Validate and commit the agent's worktree
+    pub async fn validate_and_commit(&self, agent_id: &str, message: &str) -> GdkResult<CommitNode> {
+        let (id, message) = (agent_id.to_string(), message.to_string());
+        self.agent(agent_id)?
+            .call(move |controller| Box::pin(async move { controller.validate_and_commit(&id, &message).await }))
+            .await?
+    }
+
+    /// Analyze convergence of the agent's branch
+    pub async fn get_convergence_status(&self, agent_id: &str) -> GdkResult<ConvergenceMetrics> {
+        let id = agent_id.to_string();
+        self.agent(agent_id)?
+            .call(move |controller| Box::pin(async move { controller.get_convergence_status(&id).await }))
+            .await?
+    }
+
+    /// Statistics for one agent
+    pub async fn get_agent_statistics(&self, agent_id: &str) -> GdkResult<AgentStatistics> {
+        let id = agent_id.to_string();
+        self.agent(agent_id)?
+            .call(move |controller| Box::pin(async move { controller.get_agent_statistics(&id) }))
+            .await?
+    }
+
+    /// Actions of every active agent, oldest first
+    pub async fn action_history(&self) -> Vec<AgentAction> {
+        let agents: Vec<AgentHandle> = self.agents.read().values().cloned().collect();
+        let mut actions = Vec::new();
+        for agent in agents {
+            let history = agent
+                .call(|controller| Box::pin(async move { controller.action_history.clone() }))
+                .await;
+            actions.extend(history.into_iter().flatten());
+        }
+        actions.sort_by_key(|action| action.timestamp);
+        actions
+    }
+
+    /// Create or reuse the agent's worktree; the caller holds the ref lock
+    fn add_worktree(&self, agent_id: &str, branch: &str) -> GdkResult<PathBuf> {
+        let repo = Repository::open(&self.repo_path).with_git_context("opening repository")?;
+        let path = self.worktree_root.join(agent_id);
+
+        if let Ok(worktree) = repo.find_worktree(agent_id) {
+            if worktree.validate().is_ok() {
+                return Ok(path);
+            }
+            worktree
+                .prune(Some(WorktreePruneOptions::new().valid(true).working_tree(true)))
+                .with_git_context("pruning stale agent worktree")?;
+        }
+
+        let branch = match repo.find_branch(branch, BranchType::Local) {
+            Ok(existing) => existing,
+            Err(_) => {
+                let head = repo
+                    .head()
+                    .and_then(|head| head.peel_to_commit())
+                    .with_git_context("resolving HEAD for agent branch")?;
+                repo.branch(branch, &head, false)
+                    .with_git_context("creating agent branch")?
+            }
+        };
+
+        fs::create_dir_all(&self.worktree_root).map_err(|e| {
+            GdkError::file_system_error(
+                self.worktree_root.display().to_string(),
+                "creating worktree directory",
+                e,
+            )
+        })?;
+        let mut options = WorktreeAddOptions::new();
+        options.reference(Some(branch.get()));
+        repo.worktree(agent_id, &path, Some(&options))
+            .with_git_context("adding agent worktree")?;
+        Ok(path)
+    }
+}
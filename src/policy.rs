@@ -0,0 +1,425 @@
+//! Role-based permissions and commit limits for agents
+//!
+//! By default every identity may perform every action. An
+//! [`AccessPolicy`] instead assigns each identity a [`Role`] and requires a
+//! minimum role for each [`ActionType`]:
+//!
+//! | Role          | Adds                                                          |
+//! |---------------|---------------------------------------------------------------|
+//! | `observer`    | convergence checks                                            |
+//! | `contributor` | commits, validation, spirals, approval requests               |
+//! | `integrator`  | checkpoints and reverts, spiral branches, approval decisions  |
+//! | `admin`       | anything a policy reserves for admins                         |
+//!
+//! Each role can do everything the roles above it can. A spiral reverts
+//! between attempts, so running one also needs `RevertToPoint`. Denied
+//! operations fail with [`GdkError::PermissionDenied`] and are logged as
+//! failed actions.
+//!
+//! The role checked is that of the controller's authenticated
+//! [`caller`](crate::agent::AgentWorkflowController::caller): the bearer
+//! token's identity on the HTTP API and the OS user in the CLI. Without a
+//! caller, each agent is authorized as itself.
+//!
+//! Policies are JSON, read from `.git/gdk/policy.json` by
+//! [`AccessPolicy::for_repo`]:
+//!
+//! ```json
+//! {
+//!   "default_role": "contributor",
+//!   "roles": { "lead@example.com": "admin", "ci-bot": "observer" },
+//!   "permissions": { "ApprovalDecision": "admin" }
+//! }
+//! ```
+//!
+//! A policy file without `default_role` denies identities it does not list.
+//!
+//! The same file's `commits` section is a [`CommitPolicy`] limiting what a
+//! commit may change, whoever makes it:
+//!
+//! ```json
+//! {
+//!   "commits": {
+//!     "protected_paths": [".github/", "Cargo.lock", "LICENSE*"],
+//!     "protected_branches": ["main", "release/*"],
+//!     "max_diff_lines": 500
+//!   }
+//! }
+//! ```
+//!
+//! Path patterns follow `.gitignore`: `*` and `?` stay within a path
+//! component, `**` spans components, a pattern without `/` matches at any
+//! depth and a trailing `/` protects a whole directory.
+
+use crate::agent::ActionType;
+use crate::validation_cache::git_common_dir;
+use crate::{GdkError, GdkResult, GdkResultExt};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Permission level of an identity, from least to most privileged
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Observer,
+    Contributor,
+    Integrator,
+    Admin,
+}
+
+impl Role {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Observer => "observer",
+            Self::Contributor => "contributor",
+            Self::Integrator => "integrator",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Least privileged role allowed to perform `action` by default
+    pub fn default_minimum(action: &ActionType) -> Self {
+        match action {
+            ActionType::ConvergenceCheck => Self::Observer,
+            ActionType::CommitCreate
+            | ActionType::QualityValidation
+            | ActionType::CiCdValidation
+            | ActionType::InfiniteMonkeyIteration
+            | ActionType::ApprovalRequest => Self::Contributor,
+            ActionType::RevertToPoint | ActionType::SpiralBranch | ActionType::ApprovalDecision => {
+                Self::Integrator
+            }
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Roles of identities and the role each action requires
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessPolicy {
+    /// Role of identities missing from `roles` (`None` denies them)
+    #[serde(default)]
+    pub default_role: Option<Role>,
+    /// Role of each identity
+    #[serde(default)]
+    pub roles: BTreeMap<String, Role>,
+    /// Minimum role per action, overriding [`Role::default_minimum`]
+    #[serde(default)]
+    pub permissions: BTreeMap<ActionType, Role>,
+}
+
+impl Default for AccessPolicy {
+    /// Policy allowing every identity every action
+    fn default() -> Self {
+        Self {
+            default_role: Some(Role::Admin),
+            roles: BTreeMap::new(),
+            permissions: BTreeMap::new(),
+        }
+    }
+}
+
+impl AccessPolicy {
+    /// Policy denying every identity that is not given a role
+    pub fn deny_by_default() -> Self {
+        Self {
+            default_role: None,
+            ..Self::default()
+        }
+    }
+
+    /// Give `identity` a role
+    pub fn with_role(mut self, identity: impl Into<String>, role: Role) -> Self {
+        self.roles.insert(identity.into(), role);
+        self
+    }
+
+    /// Require at least `role` for `action`
+    pub fn with_permission(mut self, action: ActionType, role: Role) -> Self {
+        self.permissions.insert(action, role);
+        self
+    }
+
+    /// Read a policy file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_file_context(&path.to_string_lossy(), "reading access policy")?;
+        serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: format!("access policy {}", path.display()),
+            source: e,
+        })
+    }
+
+    /// Policy of the repository at `repo_path`, or the allow-all default if
+    /// it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be opened or its policy
+    /// file cannot be parsed
+    pub fn for_repo(repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = Self::repo_path(repo_path)?;
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Where [`Self::for_repo`] looks for the policy
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository cannot be opened
+    pub fn repo_path(repo_path: impl AsRef<Path>) -> GdkResult<PathBuf> {
+        let repo = Repository::discover(repo_path.as_ref()).with_git_context("opening repository for access policy")?;
+        Ok(git_common_dir(&repo).join("gdk").join("policy.json"))
+    }
+
+    /// Role of `identity`, if it has one
+    pub fn role_of(&self, identity: &str) -> Option<Role> {
+        self.roles.get(identity).copied().or(self.default_role)
+    }
+
+    /// Least privileged role allowed to perform `action`
+    pub fn required_role(&self, action: &ActionType) -> Role {
+        self.permissions
+            .get(action)
+            .copied()
+            .unwrap_or_else(|| Role::default_minimum(action))
+    }
+
+    /// Whether `identity` may perform `action`
+    pub fn allows(&self, identity: &str, action: &ActionType) -> bool {
+        self.role_of(identity)
+            .is_some_and(|role| role >= self.required_role(action))
+    }
+
+    /// Check that `identity` may perform `action`, returning its role
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::PermissionDenied`] naming the role required
+    pub fn authorize(&self, identity: &str, action: &ActionType) -> GdkResult<Role> {
+        let required = self.required_role(action);
+        match self.role_of(identity) {
+            Some(role) if role >= required => Ok(role),
+            Some(role) => Err(GdkError::permission_denied(
+                identity,
+                format!("{action:?}"),
+                format!("requires {required}, has {role}"),
+            )),
+            None => Err(GdkError::permission_denied(
+                identity,
+                format!("{action:?}"),
+                format!("requires {required}, has no role"),
+            )),
+        }
+    }
+}
+
+/// A file staged for commit and the size of its change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedFile {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl StagedFile {
+    /// Lines added plus lines removed
+    pub fn lines_changed(&self) -> usize {
+        self.insertions + self.deletions
+    }
+}
+
+/// Paths, branches and diff sizes commits may not touch or exceed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CommitPolicy {
+    /// Patterns of paths no commit may add, modify or delete
+    #[serde(default)]
+    pub protected_paths: Vec<String>,
+    /// Patterns of branches no commit may land on
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+    /// Most lines a single commit may add and remove
+    #[serde(default)]
+    pub max_diff_lines: Option<usize>,
+}
+
+/// The parts of the policy file read by [`CommitPolicy`]
+#[derive(Deserialize)]
+struct PolicyFile {
+    #[serde(default)]
+    commits: CommitPolicy,
+}
+
+impl CommitPolicy {
+    /// Protect paths matching `pattern`
+    pub fn with_protected_path(mut self, pattern: impl Into<String>) -> Self {
+        self.protected_paths.push(pattern.into());
+        self
+    }
+
+    /// Protect branches matching `pattern`
+    pub fn with_protected_branch(mut self, pattern: impl Into<String>) -> Self {
+        self.protected_branches.push(pattern.into());
+        self
+    }
+
+    /// Limit the lines a commit may add and remove
+    pub fn with_max_diff_lines(mut self, lines: usize) -> Self {
+        self.max_diff_lines = Some(lines);
+        self
+    }
+
+    /// Whether the policy allows every commit
+    pub fn is_empty(&self) -> bool {
+        self.protected_paths.is_empty() && self.protected_branches.is_empty() && self.max_diff_lines.is_none()
+    }
+
+    /// Read the `commits` section of a policy file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_file_context(&path.to_string_lossy(), "reading commit policy")?;
+        let file: PolicyFile = serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: format!("commit policy {}", path.display()),
+            source: e,
+        })?;
+        Ok(file.commits)
+    }
+
+    /// Commit policy of the repository at `repo_path`, or an empty policy if
+    /// it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be opened or its policy
+    /// file cannot be parsed
+    pub fn for_repo(repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = AccessPolicy::repo_path(repo_path)?;
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Whether `path` matches a protected path pattern
+    pub fn is_protected_path(&self, path: &str) -> bool {
+        self.protected_paths.iter().any(|pattern| path_matches(pattern, path))
+    }
+
+    /// Whether `branch` matches a protected branch pattern
+    pub fn is_protected_branch(&self, branch: &str) -> bool {
+        self.protected_branches.iter().any(|pattern| glob_matches(pattern, branch))
+    }
+
+    /// Check a commit of `files` onto `branch`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ValidationError`] for rule `commit_policy`, listing
+    /// every violation and the files causing it
+    pub fn check(&self, branch: &str, files: &[StagedFile]) -> GdkResult<()> {
+        let mut violations = Vec::new();
+
+        if self.is_protected_branch(branch) {
+            violations.push(format!("branch '{branch}' is protected ({})", join_paths(files.iter())));
+        }
+
+        let protected: Vec<_> = files.iter().filter(|file| self.is_protected_path(&file.path)).collect();
+        if !protected.is_empty() {
+            violations.push(format!("protected paths changed ({})", join_paths(protected.into_iter())));
+        }
+
+        let lines: usize = files.iter().map(StagedFile::lines_changed).sum();
+        if let Some(max) = self.max_diff_lines.filter(|max| lines > *max) {
+            let mut largest: Vec<_> = files.iter().filter(|file| file.lines_changed() > 0).collect();
+            largest.sort_by(|a, b| b.lines_changed().cmp(&a.lines_changed()).then(a.path.cmp(&b.path)));
+            let sizes: Vec<_> = largest
+                .iter()
+                .map(|file| format!("{}: {}", file.path, file.lines_changed()))
+                .collect();
+            violations.push(format!("diff of {lines} lines exceeds {max} ({})", sizes.join(", ")));
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(GdkError::validation_error(
+            "commit_policy",
+            violations.join("; "),
+            "Revert the listed changes or split the commit; the limits live in the commits section of .git/gdk/policy.json",
+        ))
+    }
+}
+
+fn join_paths<'a>(files: impl Iterator<Item = &'a StagedFile>) -> String {
+    files.map(|file| file.path.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// Match `path` against a `.gitignore`-style pattern
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let directory = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+
+    let mut full = String::new();
+    if !anchored {
+        full.push_str("**/");
+    }
+    full.push_str(trimmed);
+    if directory {
+        full.push_str("/**");
+    }
+    glob_matches(&full, path)
+}
+
+/// Match `/`-separated `text` against a glob where `**` spans separators
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<_> = pattern.split('/').collect();
+    let text: Vec<_> = text.split('/').collect();
+    match_components(&pattern, &text)
+}
+
+fn match_components(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((&"**", rest)) => (0..=text.len()).any(|skip| match_components(rest, &text[skip..])),
+        Some((component, rest)) => text.split_first().is_some_and(|(name, text_rest)| {
+            let component: Vec<_> = component.chars().collect();
+            let name: Vec<_> = name.chars().collect();
+            match_component(&component, &name) && match_components(rest, text_rest)
+        }),
+    }
+}
+
+fn match_component(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| match_component(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && match_component(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_component(rest, &name[1..]),
+    }
+}
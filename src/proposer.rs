@@ -0,0 +1,392 @@
+//! Change proposers for the infinite monkey workflow
+//!
+//! The spiral loop commits whatever is in the working tree and then reverts
+//! it. A [`ChangeProposer`] produces the next candidate change before each
+//! commit, so successive attempts actually differ:
+//! - [`CommandProposer`]: run an external command that edits the tree
+//! - [`PatchProposer`]: apply the next patch from a directory or mbox file
+//! - [`ClosureProposer`]: call a user-supplied Rust closure
+//!
+//! Every proposer sees a [`ProposalContext`] with the previous attempts'
+//! scores and the file threads that were still failing.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::proposer::PatchProposer;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let mut controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     controller.start_agent_session("agent-1").await?;
+//!     controller.set_change_proposer("agent-1", PatchProposer::from_dir("./repo", "./patches")?)?;
+//!
+//!     let result = controller.execute_infinite_monkey_workflow("agent-1", 0.8).await?;
+//!     println!("Converged with health score: {}", result.health_score);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{CommitNode, FileThread, GdkError, GdkResult, ThreadColor};
+use git2::{ApplyLocation, Diff, Repository};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// What a proposer knows about earlier attempts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProposalContext {
+    /// Agent the proposal is made for
+    pub agent_id: String,
+    /// Number of the attempt about to be committed (1-based)
+    pub attempt: u32,
+    /// Health scores of the previous attempts in this run, oldest first
+    pub previous_scores: Vec<f64>,
+    /// Threads of the previous attempt that were below light green
+    pub failing_threads: Vec<FileThread>,
+}
+
+impl ProposalContext {
+    /// Health score of the most recent attempt
+    pub fn previous_score(&self) -> Option<f64> {
+        self.previous_scores.last().copied()
+    }
+
+    /// Record a finished attempt before proposing the next one
+    pub fn record_attempt(&mut self, commit_node: &CommitNode) {
+        self.previous_scores.push(commit_node.health_score);
+        self.failing_threads = commit_node
+            .file_threads
+            .values()
+            .filter(|thread| {
+                matches!(
+                    thread.color_status,
+                    ThreadColor::Red | ThreadColor::Orange | ThreadColor::Yellow
+                )
+            })
+            .cloned()
+            .collect();
+        self.failing_threads
+            .sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    }
+}
+
+/// Result of asking a proposer for a change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Proposal {
+    /// The working tree now holds a new candidate change
+    Applied { description: String },
+    /// The proposer has nothing left to try
+    Exhausted,
+}
+
+/// Produces a candidate change in the working tree before each attempt
+#[async_trait::async_trait]
+pub trait ChangeProposer: Send + fmt::Debug {
+    /// Edit the working tree for the next attempt
+    async fn propose(&mut self, context: &ProposalContext) -> GdkResult<Proposal>;
+}
+
+/// Runs an external command that edits the working tree
+///
+/// The command runs in the repository directory and receives the
+/// [`ProposalContext`] as JSON on stdin, plus `GDK_AGENT_ID`, `GDK_ATTEMPT`,
+/// `GDK_PREVIOUS_SCORE` and `GDK_FAILING_FILES` (newline separated) in its
+/// environment. A non-zero exit fails the proposal; exit code 3 reports that
+/// the command has nothing left to try. Commands still running after the
+/// timeout (10 minutes by default) are killed and fail the proposal.
+#[derive(Debug, Clone)]
+pub struct CommandProposer {
+    program: String,
+    args: Vec<String>,
+    workdir: PathBuf,
+    timeout: Duration,
+}
+
+impl CommandProposer {
+    /// Exit code a command uses to report that it is exhausted
+    pub const EXHAUSTED_EXIT_CODE: i32 = 3;
+
+    /// Time a command may run before it is killed, unless overridden
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+    pub fn new(program: impl Into<String>, workdir: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            workdir: workdir.into(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl ChangeProposer for CommandProposer {
+    async fn propose(&mut self, context: &ProposalContext) -> GdkResult<Proposal> {
+        let failing_files: Vec<&str> = context
+            .failing_threads
+            .iter()
+            .map(|thread| thread.file_path.as_str())
+            .collect();
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(&self.workdir)
+            .env("GDK_AGENT_ID", &context.agent_id)
+            .env("GDK_ATTEMPT", context.attempt.to_string())
+            .env(
+                "GDK_PREVIOUS_SCORE",
+                context.previous_score().map(|s| s.to_string()).unwrap_or_default(),
+            )
+            .env("GDK_FAILING_FILES", failing_files.join("\n"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                GdkError::file_system_error(&self.program, "Failed to start change proposer", e)
+            })?;
+
+        let payload = serde_json::to_vec(context).map_err(|e| GdkError::SerializationError {
+            format: "json".to_string(),
+            context: "proposal context".to_string(),
+            source: e,
+        })?;
+        // Feed stdin while draining stdout and stderr, so a command that
+        // writes before reading cannot fill a pipe and deadlock
+        let stdin = child.stdin.take();
+        let write_input = async move {
+            if let Some(mut stdin) = stdin {
+                // The command may exit without reading its input
+                let _ = stdin.write_all(&payload).await;
+            }
+        };
+        let run = async { tokio::join!(write_input, child.wait_with_output()).1 };
+
+        // Dropping the unfinished future kills the command
+        let output = tokio::time::timeout(self.timeout, run)
+            .await
+            .map_err(|_| {
+                GdkError::agent_error(
+                    &context.agent_id,
+                    "propose_change",
+                    None,
+                    format!("'{}' timed out after {}s", self.program, self.timeout.as_secs()),
+                )
+            })?
+            .map_err(|e| {
+                GdkError::file_system_error(&self.program, "Failed to wait for change proposer", e)
+            })?;
+
+        match output.status.code() {
+            Some(0) => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let description = stdout
+                    .lines()
+                    .rev()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| line.trim().to_string())
+                    .unwrap_or_else(|| self.program.clone());
+                Ok(Proposal::Applied { description })
+            }
+            Some(Self::EXHAUSTED_EXIT_CODE) => Ok(Proposal::Exhausted),
+            code => Err(GdkError::agent_error(
+                &context.agent_id,
+                "propose_change",
+                None,
+                format!(
+                    "'{}' exited with {}: {}",
+                    self.program,
+                    code.map(|c| c.to_string()).unwrap_or_else(|| "signal".to_string()),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            )),
+        }
+    }
+}
+
+/// Applies queued patches to the working tree, one per attempt
+///
+/// Patches are applied with libgit2 on top of whatever the previous attempt
+/// reverted to, so each patch should be relative to the spiral's start point.
+pub struct PatchProposer {
+    repo: Repository,
+    patches: Vec<(String, Vec<u8>)>,
+    next: usize,
+}
+
+impl fmt::Debug for PatchProposer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatchProposer")
+            .field("repo", &self.repo.path())
+            .field("patches", &self.patches.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl PatchProposer {
+    /// Queue every `*.patch` and `*.diff` file in `patch_dir`, sorted by name
+    pub fn from_dir(repo_path: impl AsRef<Path>, patch_dir: impl AsRef<Path>) -> GdkResult<Self> {
+        let patch_dir = patch_dir.as_ref();
+        let read_error = |e| {
+            GdkError::file_system_error(patch_dir.display().to_string(), "Failed to read patch directory", e)
+        };
+
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(patch_dir).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            if matches!(path.extension().and_then(|e| e.to_str()), Some("patch" | "diff")) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let patches = paths
+            .into_iter()
+            .map(|path| {
+                let content = std::fs::read(&path).map_err(|e| {
+                    GdkError::file_system_error(path.display().to_string(), "Failed to read patch", e)
+                })?;
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                Ok((name, content))
+            })
+            .collect::<GdkResult<Vec<_>>>()?;
+
+        Self::from_patches(repo_path, patches)
+    }
+
+    /// Queue every message of an mbox file (e.g. `git format-patch --stdout`)
+    pub fn from_mbox(repo_path: impl AsRef<Path>, mbox_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let mbox_path = mbox_path.as_ref();
+        let content = std::fs::read_to_string(mbox_path).map_err(|e| {
+            GdkError::file_system_error(mbox_path.display().to_string(), "Failed to read mbox", e)
+        })?;
+
+        let patches = split_mbox(&content)
+            .into_iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let name = message
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Subject: "))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{} #{}", mbox_path.display(), index + 1));
+                (name, message.into_bytes())
+            })
+            .collect();
+
+        Self::from_patches(repo_path, patches)
+    }
+
+    /// Queue named patches in order
+    pub fn from_patches(repo_path: impl AsRef<Path>, patches: Vec<(String, Vec<u8>)>) -> GdkResult<Self> {
+        let repo = Repository::open(repo_path.as_ref())
+            .map_err(|e| GdkError::git_error("open repository for patches", e))?;
+        Ok(Self {
+            repo,
+            patches,
+            next: 0,
+        })
+    }
+
+    /// Number of patches not yet applied
+    pub fn remaining(&self) -> usize {
+        self.patches.len() - self.next
+    }
+}
+
+#[async_trait::async_trait]
+impl ChangeProposer for PatchProposer {
+    async fn propose(&mut self, _context: &ProposalContext) -> GdkResult<Proposal> {
+        let Some((name, content)) = self.patches.get(self.next) else {
+            return Ok(Proposal::Exhausted);
+        };
+        self.next += 1;
+
+        let diff = Diff::from_buffer(content)
+            .map_err(|e| GdkError::git_error(format!("parse patch {name}"), e))?;
+        self.repo
+            .apply(&diff, ApplyLocation::WorkDir, None)
+            .map_err(|e| GdkError::git_error(format!("apply patch {name}"), e))?;
+
+        Ok(Proposal::Applied {
+            description: name.clone(),
+        })
+    }
+}
+
+/// Split an mbox into messages at `From ` lines that follow a blank line
+fn split_mbox(content: &str) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current = String::new();
+
+    for line in content.split_inclusive('\n') {
+        if line.starts_with("From ") && !current.trim().is_empty() && current.ends_with("\n\n") {
+            messages.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+    }
+    if !current.trim().is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+type ProposerFn = dyn FnMut(&ProposalContext) -> GdkResult<Proposal> + Send;
+
+/// Calls a user-supplied closure to edit the working tree
+pub struct ClosureProposer {
+    propose: Box<ProposerFn>,
+}
+
+impl ClosureProposer {
+    pub fn new(propose: impl FnMut(&ProposalContext) -> GdkResult<Proposal> + Send + 'static) -> Self {
+        Self {
+            propose: Box::new(propose),
+        }
+    }
+}
+
+impl fmt::Debug for ClosureProposer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClosureProposer").finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl ChangeProposer for ClosureProposer {
+    async fn propose(&mut self, context: &ProposalContext) -> GdkResult<Proposal> {
+        (self.propose)(context)
+    }
+}
@@ -5,7 +5,7 @@
 //! - Historical trend analysis with statistical methods
 //! - Quality gate enforcement with configurable thresholds
 //! - Performance impact analysis of quality changes
-//! - Predictive quality modeling using machine learning techniques
+//! - Predictive quality modeling with confidence intervals (see [`crate::forecast`])
 //! - Technical debt measurement and tracking
 //! - Code complexity analysis and recommendations
 //!
@@ -18,6 +18,7 @@
 //! - **Reliability**: Error handling, edge case coverage
 //! - **Usability**: API design, documentation quality
 
+use crate::forecast::{ForecastConfig, ForecastUnavailable, Forecaster, QualityForecast};
 use crate::{CommitNode, GdkResult, GdkError};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -99,6 +100,20 @@ pub struct TrendConfig {
     pub trend_sensitivity: f64,
     /// Enable predictive modeling
     pub enable_prediction: bool,
+    /// Commits ahead at which quality is forecast
+    #[serde(default = "default_prediction_horizons")]
+    pub prediction_horizons: Vec<u32>,
+    /// Confidence level of forecast intervals
+    #[serde(default = "default_prediction_confidence")]
+    pub prediction_confidence: f64,
+}
+
+fn default_prediction_horizons() -> Vec<u32> {
+    vec![1, 5, 20]
+}
+
+fn default_prediction_confidence() -> f64 {
+    0.9
 }
 
 /// Comprehensive quality metrics
@@ -123,7 +138,7 @@ pub struct QualityMetrics {
 }
 
 /// Quality scores across different dimensions
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct QualityDimensions {
     /// Correctness score (compilation, type safety, tests)
     pub correctness: f64,
@@ -139,6 +154,95 @@ pub struct QualityDimensions {
     pub usability: f64,
 }
 
+impl QualityDimensions {
+    /// Same score in every dimension, e.g. for uniform floors
+    pub fn uniform(score: f64) -> Self {
+        Self {
+            correctness: score,
+            maintainability: score,
+            security: score,
+            performance: score,
+            reliability: score,
+            usability: score,
+        }
+    }
+
+    /// Derive dimension scores from the file threads of a commit
+    ///
+    /// Each dimension is averaged over all threads; a commit without threads
+    /// scores zero everywhere.
+    pub fn from_commit(commit: &CommitNode) -> Self {
+        let mut total_scores = Self::default();
+
+        let file_count = commit.file_threads.len();
+        if file_count == 0 {
+            return total_scores;
+        }
+
+        // Aggregate scores from all file threads
+        for thread in commit.file_threads.values() {
+            // Correctness: based on type checking and test coverage
+            let correctness = (thread.type_check_score + thread.test_coverage) / 2.0;
+            total_scores.correctness += correctness;
+
+            // Maintainability: based on lint score and structure
+            let maintainability = thread.lint_score;
+            total_scores.maintainability += maintainability;
+
+            // Security: derive from lint and functionality scores
+            let security = (thread.lint_score + thread.functionality_score) / 2.0;
+            total_scores.security += security;
+
+            // Performance: based on functionality score (proxy for efficiency)
+            let performance = thread.functionality_score;
+            total_scores.performance += performance;
+
+            // Reliability: combination of test coverage and functionality
+            let reliability = (thread.test_coverage + thread.functionality_score) / 2.0;
+            total_scores.reliability += reliability;
+
+            // Usability: based on documentation and API design (proxy: lint score)
+            let usability = thread.lint_score;
+            total_scores.usability += usability;
+        }
+
+        // Average the scores
+        total_scores.correctness /= file_count as f64;
+        total_scores.maintainability /= file_count as f64;
+        total_scores.security /= file_count as f64;
+        total_scores.performance /= file_count as f64;
+        total_scores.reliability /= file_count as f64;
+        total_scores.usability /= file_count as f64;
+
+        total_scores
+    }
+
+    /// Named dimension scores in a fixed order
+    pub fn values(&self) -> [(&'static str, f64); 6] {
+        [
+            ("correctness", self.correctness),
+            ("maintainability", self.maintainability),
+            ("security", self.security),
+            ("performance", self.performance),
+            ("reliability", self.reliability),
+            ("usability", self.usability),
+        ]
+    }
+
+    /// Pareto dominance: at least as good everywhere and strictly better somewhere
+    pub fn dominates(&self, other: &Self) -> bool {
+        let pairs = self.values().into_iter().zip(other.values());
+        let mut strictly_better = false;
+        for ((_, mine), (_, theirs)) in pairs {
+            if mine < theirs {
+                return false;
+            }
+            strictly_better |= mine > theirs;
+        }
+        strictly_better
+    }
+}
+
 /// Technical debt measurement and tracking
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TechnicalDebtMetrics {
@@ -398,8 +502,11 @@ pub struct QualityAnalysisResult {
     pub trends: QualityTrends,
     /// Recommendations for improvement
     pub recommendations: Vec<QualityRecommendation>,
-    /// Predicted future quality (if enabled)
+    /// Predicted future quality (if enabled and enough history exists)
     pub predictions: Option<QualityPrediction>,
+    /// Why no prediction was made despite prediction being enabled
+    #[serde(default)]
+    pub prediction_unavailable: Option<ForecastUnavailable>,
 }
 
 /// Quality trends analysis
@@ -451,16 +558,12 @@ pub struct QualityRecommendation {
     pub expected_impact: f64,
 }
 
-/// Quality prediction based on trends
+/// Quality prediction fitted on the overall score history
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct QualityPrediction {
-    /// Predicted quality score in 1 week
-    pub one_week: f64,
-    /// Predicted quality score in 1 month
-    pub one_month: f64,
-    /// Predicted quality score in 3 months
-    pub three_months: f64,
-    /// Confidence level of prediction (0.0-1.0)
+    /// Forecast overall score at each configured horizon
+    pub forecasts: Vec<QualityForecast>,
+    /// Confidence level of the forecast intervals (0.0-1.0)
     pub confidence: f64,
     /// Key factors influencing prediction
     pub factors: Vec<String>,
@@ -511,6 +614,8 @@ impl Default for TrendConfig {
             min_data_points: 3,
             trend_sensitivity: 0.05,
             enable_prediction: true,
+            prediction_horizons: default_prediction_horizons(),
+            prediction_confidence: default_prediction_confidence(),
         }
     }
 }
@@ -549,10 +654,13 @@ impl QualityMetricsAnalyzer {
         let recommendations = self.generate_recommendations(&metrics, &trends)?;
         
         // Predict future quality if enabled
-        let predictions = if self.config.trend_config.enable_prediction {
-            Some(self.predict_quality_trends()?)
+        let (predictions, prediction_unavailable) = if self.config.trend_config.enable_prediction {
+            match self.predict_quality_trends(&metrics) {
+                Ok(prediction) => (Some(prediction), None),
+                Err(reason) => (None, Some(reason)),
+            }
         } else {
-            None
+            (None, None)
         };
         
         // Update history
@@ -565,6 +673,7 @@ impl QualityMetricsAnalyzer {
             trends,
             recommendations,
             predictions,
+            prediction_unavailable,
         })
     }
 
@@ -610,56 +719,7 @@ impl QualityMetricsAnalyzer {
 
     /// Calculate quality scores for each dimension
     fn calculate_dimension_scores(&self, commit: &CommitNode) -> GdkResult<QualityDimensions> {
-        let mut total_scores = QualityDimensions {
-            correctness: 0.0,
-            maintainability: 0.0,
-            security: 0.0,
-            performance: 0.0,
-            reliability: 0.0,
-            usability: 0.0,
-        };
-
-        let file_count = commit.file_threads.len();
-        if file_count == 0 {
-            return Ok(total_scores);
-        }
-
-        // Aggregate scores from all file threads
-        for thread in commit.file_threads.values() {
-            // Correctness: based on type checking and test coverage
-            let correctness = (thread.type_check_score + thread.test_coverage) / 2.0;
-            total_scores.correctness += correctness;
-
-            // Maintainability: based on lint score and structure
-            let maintainability = thread.lint_score;
-            total_scores.maintainability += maintainability;
-
-            // Security: derive from lint and functionality scores
-            let security = (thread.lint_score + thread.functionality_score) / 2.0;
-            total_scores.security += security;
-
-            // Performance: based on functionality score (proxy for efficiency)
-            let performance = thread.functionality_score;
-            total_scores.performance += performance;
-
-            // Reliability: combination of test coverage and functionality
-            let reliability = (thread.test_coverage + thread.functionality_score) / 2.0;
-            total_scores.reliability += reliability;
-
-            // Usability: based on documentation and API design (proxy: lint score)
-            let usability = thread.lint_score;
-            total_scores.usability += usability;
-        }
-
-        // Average the scores
-        total_scores.correctness /= file_count as f64;
-        total_scores.maintainability /= file_count as f64;
-        total_scores.security /= file_count as f64;
-        total_scores.performance /= file_count as f64;
-        total_scores.reliability /= file_count as f64;
-        total_scores.usability /= file_count as f64;
-
-        Ok(total_scores)
+        Ok(QualityDimensions::from_commit(commit))
     }
 
     /// Calculate weighted overall score
@@ -878,14 +938,42 @@ impl QualityMetricsAnalyzer {
         Ok(Vec::new())
     }
 
-    fn predict_quality_trends(&self) -> GdkResult<QualityPrediction> {
-        // Simplified prediction model
+    /// Forecast the overall score from history plus the current measurement
+    pub fn predict_quality_trends(
+        &self,
+        current: &QualityMetrics,
+    ) -> Result<QualityPrediction, ForecastUnavailable> {
+        let trend_config = &self.config.trend_config;
+        let scores: Vec<f64> = self
+            .history
+            .iter()
+            .map(|snapshot| snapshot.metrics.overall_score)
+            .chain(std::iter::once(current.overall_score))
+            .collect();
+
+        let model = Forecaster::with_config(ForecastConfig {
+            confidence_level: trend_config.prediction_confidence,
+            min_points: trend_config.min_data_points,
+            window: trend_config.trend_window,
+        })
+        .fit(&scores)?;
+
+        let slope = model.slope_interval();
         Ok(QualityPrediction {
-            one_week: 0.80,
-            one_month: 0.82,
-            three_months: 0.85,
-            confidence: 0.70,
-            factors: vec!["test_coverage_improvement".to_string(), "complexity_reduction".to_string()],
+            forecasts: trend_config
+                .prediction_horizons
+                .iter()
+                .map(|&steps| model.predict(steps))
+                .collect(),
+            confidence: trend_config.prediction_confidence,
+            factors: vec![
+                format!(
+                    "slope {:+.4} per commit ({:+.4}..{:+.4})",
+                    model.slope, slope.lower, slope.upper
+                ),
+                format!("residual std {:.4}", model.residual_std),
+                format!("r_squared {:.3} over {} commits", model.r_squared, model.observations),
+            ],
         })
     }
 
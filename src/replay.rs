@@ -0,0 +1,638 @@
+//! Session recording and deterministic replay
+//!
+//! A spiral that produced a surprising result is hard to reproduce: commit
+//! hashes, UUIDs and timestamps change on every run, and validators may not
+//! be deterministic. This module records every [`GitWorkflow`] call a
+//! session makes and replays the sequence later:
+//! - [`RecordingWorkflow`] wraps a workflow and captures each call's inputs
+//!   (commit messages, committed trees, revert points) and outputs (tree
+//!   OIDs, validator scores, convergence results, CI outcomes)
+//! - [`Recording`] is the captured sequence, stored as JSON
+//! - [`scratch_clone`] prepares a clone positioned where the recording began
+//! - [`replay`] re-executes the sequence there and reports every
+//!   [`Divergence`] between recorded and replayed outputs
+//!
+//! Replay compares only what should be reproducible: trees rather than
+//! commit hashes, and scores rather than timestamps. Commits made during the
+//! recording are mapped to their replayed counterparts, so reverts target
+//! the equivalent commit in the scratch clone. Start recording with a fresh
+//! workflow so both runs begin with an empty commit history.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::replay::{self, RecordingWorkflow};
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let workflow = RecordingWorkflow::new(GitWorkflowManager::new("./repo")?, "./repo")?;
+//!     let mut controller = AgentWorkflowController::new(workflow);
+//!     controller.start_agent_session("agent-1").await?;
+//!     let _ = controller.execute_infinite_monkey_workflow("agent-1", 0.8).await;
+//!     controller.workflow.recording().save("spiral.json")?;
+//!
+//!     // Later, or on another machine with the same repository
+//!     let recording = replay::Recording::load("spiral.json")?;
+//!     let mut scratch = replay::scratch_clone(&recording, "./repo", "/tmp/gdk-replay")?;
+//!     let report = replay::replay(&recording, &mut scratch).await?;
+//!     for divergence in &report.divergences {
+//!         println!("{divergence}");
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core::GitWorkflowManager;
+use crate::validation_cache::git_common_dir;
+use crate::{CommitNode, ConvergenceMetrics, GdkError, GdkResult, GdkResultExt, GitWorkflow, RevertPoint};
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{Oid, Repository};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A [`GitWorkflow`] call and the inputs needed to repeat it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum RecordedCall {
+    /// Commit the working tree; `tree` is what was committed
+    CreateCommitNode { message: String, tree: Option<String> },
+    CreateRevertPoint { reason: String },
+    BeginSpiral,
+    RevertToPoint { point: Box<RevertPoint> },
+    AnalyzeConvergence,
+    UpdateThreadColors,
+    ValidateCiCd { commit_hash: String },
+}
+
+impl RecordedCall {
+    /// Snake-case name of the call
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecordedCall::CreateCommitNode { .. } => "create_commit_node",
+            RecordedCall::CreateRevertPoint { .. } => "create_revert_point",
+            RecordedCall::BeginSpiral => "begin_spiral",
+            RecordedCall::RevertToPoint { .. } => "revert_to_point",
+            RecordedCall::AnalyzeConvergence => "analyze_convergence",
+            RecordedCall::UpdateThreadColors => "update_thread_colors",
+            RecordedCall::ValidateCiCd { .. } => "validate_ci_cd",
+        }
+    }
+}
+
+/// Validator scores for one file of a commit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ThreadScores {
+    pub lint: f64,
+    pub type_check: f64,
+    pub test_coverage: f64,
+    pub functionality: f64,
+}
+
+/// What a [`GitWorkflow`] call produced
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum RecordedOutcome {
+    Commit {
+        commit_hash: String,
+        tree: String,
+        health_score: f64,
+        threads: BTreeMap<String, ThreadScores>,
+    },
+    RevertPoint {
+        commit_hash: String,
+    },
+    Convergence {
+        attempts: u32,
+        test_pass_rate: f64,
+        is_converged: bool,
+        is_stalled: bool,
+    },
+    Validation {
+        passed: bool,
+    },
+    /// The call succeeded without a result worth comparing
+    Done,
+    Failed {
+        category: String,
+        message: String,
+    },
+}
+
+impl RecordedOutcome {
+    fn failed(error: &GdkError) -> Self {
+        RecordedOutcome::Failed {
+            category: error.category().to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    fn commit(repo: &Repository, node: &CommitNode) -> GdkResult<Self> {
+        let tree = repo
+            .find_commit(Oid::from_str(&node.hash)?)
+            .with_git_context("looking up recorded commit")?
+            .tree_id();
+        let threads = node
+            .file_threads
+            .iter()
+            .map(|(path, thread)| {
+                let scores = ThreadScores {
+                    lint: thread.lint_score,
+                    type_check: thread.type_check_score,
+                    test_coverage: thread.test_coverage,
+                    functionality: thread.functionality_score,
+                };
+                (path.clone(), scores)
+            })
+            .collect();
+        Ok(RecordedOutcome::Commit {
+            commit_hash: node.hash.clone(),
+            tree: tree.to_string(),
+            health_score: node.health_score,
+            threads,
+        })
+    }
+
+    fn convergence(metrics: &ConvergenceMetrics) -> Self {
+        RecordedOutcome::Convergence {
+            attempts: metrics.attempts,
+            test_pass_rate: metrics.test_pass_rate,
+            is_converged: metrics.is_converged,
+            is_stalled: metrics.is_stalled,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            RecordedOutcome::Commit { .. } => "commit",
+            RecordedOutcome::RevertPoint { .. } => "revert_point",
+            RecordedOutcome::Convergence { .. } => "convergence",
+            RecordedOutcome::Validation { .. } => "validation",
+            RecordedOutcome::Done => "done",
+            RecordedOutcome::Failed { .. } => "failed",
+        }
+    }
+
+    /// Reproducible fields, with commit hashes translated through `hashes`
+    ///
+    /// Commit hashes, error messages and anything time-dependent are left
+    /// out; scores are rounded so formatting noise never counts.
+    fn comparable_fields(&self, hashes: &HashMap<String, String>) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        fields.insert("result".to_string(), self.kind().to_string());
+        match self {
+            RecordedOutcome::Commit {
+                tree,
+                health_score,
+                threads,
+                ..
+            } => {
+                fields.insert("tree".to_string(), tree.clone());
+                fields.insert("health_score".to_string(), format!("{health_score:.6}"));
+                for (path, scores) in threads {
+                    fields.insert(format!("{path}.lint"), format!("{:.6}", scores.lint));
+                    fields.insert(format!("{path}.type_check"), format!("{:.6}", scores.type_check));
+                    fields.insert(format!("{path}.test_coverage"), format!("{:.6}", scores.test_coverage));
+                    fields.insert(format!("{path}.functionality"), format!("{:.6}", scores.functionality));
+                }
+            }
+            RecordedOutcome::RevertPoint { commit_hash } => {
+                fields.insert("commit_hash".to_string(), translate(hashes, commit_hash));
+            }
+            RecordedOutcome::Convergence {
+                attempts,
+                test_pass_rate,
+                is_converged,
+                is_stalled,
+            } => {
+                fields.insert("attempts".to_string(), attempts.to_string());
+                fields.insert("test_pass_rate".to_string(), format!("{test_pass_rate:.6}"));
+                fields.insert("is_converged".to_string(), is_converged.to_string());
+                fields.insert("is_stalled".to_string(), is_stalled.to_string());
+            }
+            RecordedOutcome::Validation { passed } => {
+                fields.insert("passed".to_string(), passed.to_string());
+            }
+            RecordedOutcome::Done => {}
+            RecordedOutcome::Failed { category, .. } => {
+                fields.insert("category".to_string(), category.clone());
+            }
+        }
+        fields
+    }
+}
+
+/// One recorded [`GitWorkflow`] call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordedStep {
+    pub index: usize,
+    #[serde(flatten)]
+    pub call: RecordedCall,
+    pub outcome: RecordedOutcome,
+}
+
+/// Sequence of workflow calls captured by a [`RecordingWorkflow`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Recording {
+    /// HEAD when recording started
+    pub base_commit: String,
+    /// Branch checked out when recording started (`HEAD` if detached)
+    pub branch: String,
+    pub steps: Vec<RecordedStep>,
+}
+
+impl Recording {
+    /// Read a recording saved with [`Recording::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_file_context(&path.to_string_lossy(), "reading recording")?;
+        serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "session recording".to_string(),
+            source: e,
+        })
+    }
+
+    /// Write the recording as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written
+    pub fn save(&self, path: impl AsRef<Path>) -> GdkResult<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec_pretty(self).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "session recording".to_string(),
+            source: e,
+        })?;
+        fs::write(path, json).with_file_context(&path.to_string_lossy(), "writing recording")
+    }
+}
+
+/// [`GitWorkflow`] that records every call made through it
+///
+/// Failed calls are recorded too, with the error's category.
+pub struct RecordingWorkflow<T: GitWorkflow> {
+    /// Workflow doing the actual work
+    pub inner: T,
+    repo: Repository,
+    // `analyze_convergence` and `validate_ci_cd` take `&self`
+    recording: RefCell<Recording>,
+}
+
+impl<T: GitWorkflow> RecordingWorkflow<T> {
+    /// Record calls to `inner`, which works on the repository at `repo_path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository cannot be opened or
+    /// has no commits
+    pub fn new(inner: T, repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let repo = Repository::open(repo_path.as_ref()).with_git_context("opening repository for recording")?;
+        let (base_commit, branch) = {
+            let head = repo.head().with_git_context("reading HEAD for recording")?;
+            let commit = head.peel_to_commit().with_git_context("reading HEAD for recording")?;
+            let branch = if head.is_branch() {
+                head.shorthand().unwrap_or("HEAD").to_string()
+            } else {
+                "HEAD".to_string()
+            };
+            (commit.id().to_string(), branch)
+        };
+
+        Ok(Self {
+            inner,
+            repo,
+            recording: RefCell::new(Recording {
+                base_commit,
+                branch,
+                steps: Vec::new(),
+            }),
+        })
+    }
+
+    /// Copy of the calls recorded so far
+    pub fn recording(&self) -> Recording {
+        self.recording.borrow().clone()
+    }
+
+    /// Stop recording and return the captured calls
+    pub fn into_recording(self) -> Recording {
+        self.recording.into_inner()
+    }
+
+    fn record(&self, call: RecordedCall, outcome: RecordedOutcome) {
+        let mut recording = self.recording.borrow_mut();
+        let index = recording.steps.len();
+        recording.steps.push(RecordedStep { index, call, outcome });
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<T: GitWorkflow> GitWorkflow for RecordingWorkflow<T> {
+    async fn create_commit_node(&mut self, message: &str) -> GdkResult<CommitNode> {
+        let result = self.inner.create_commit_node(message).await;
+        let outcome = match &result {
+            Ok(node) => RecordedOutcome::commit(&self.repo, node)?,
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        let tree = match &outcome {
+            RecordedOutcome::Commit { tree, .. } => Some(tree.clone()),
+            _ => None,
+        };
+        let message = message.to_string();
+        self.record(RecordedCall::CreateCommitNode { message, tree }, outcome);
+        result
+    }
+
+    async fn create_revert_point(&mut self, reason: &str) -> GdkResult<RevertPoint> {
+        let result = self.inner.create_revert_point(reason).await;
+        let outcome = match &result {
+            Ok(point) => RecordedOutcome::RevertPoint {
+                commit_hash: point.commit_hash.clone(),
+            },
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        let reason = reason.to_string();
+        self.record(RecordedCall::CreateRevertPoint { reason }, outcome);
+        result
+    }
+
+    async fn begin_spiral(&mut self) -> GdkResult<RevertPoint> {
+        let result = self.inner.begin_spiral().await;
+        let outcome = match &result {
+            Ok(point) => RecordedOutcome::RevertPoint {
+                commit_hash: point.commit_hash.clone(),
+            },
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        self.record(RecordedCall::BeginSpiral, outcome);
+        result
+    }
+
+    async fn revert_to_point(&mut self, point: &RevertPoint) -> GdkResult<()> {
+        let result = self.inner.revert_to_point(point).await;
+        let outcome = match &result {
+            Ok(()) => RecordedOutcome::Done,
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        let point = Box::new(point.clone());
+        self.record(RecordedCall::RevertToPoint { point }, outcome);
+        result
+    }
+
+    async fn analyze_convergence(&self) -> GdkResult<ConvergenceMetrics> {
+        let result = self.inner.analyze_convergence().await;
+        let outcome = match &result {
+            Ok(metrics) => RecordedOutcome::convergence(metrics),
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        self.record(RecordedCall::AnalyzeConvergence, outcome);
+        result
+    }
+
+    async fn update_thread_colors(&mut self) -> GdkResult<()> {
+        let result = self.inner.update_thread_colors().await;
+        let outcome = match &result {
+            Ok(()) => RecordedOutcome::Done,
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        self.record(RecordedCall::UpdateThreadColors, outcome);
+        result
+    }
+
+    async fn validate_ci_cd(&self, commit_hash: &str) -> GdkResult<bool> {
+        let result = self.inner.validate_ci_cd(commit_hash).await;
+        let outcome = match &result {
+            Ok(passed) => RecordedOutcome::Validation { passed: *passed },
+            Err(e) => RecordedOutcome::failed(e),
+        };
+        let commit_hash = commit_hash.to_string();
+        self.record(RecordedCall::ValidateCiCd { commit_hash }, outcome);
+        result
+    }
+}
+
+/// Difference between a recorded and a replayed output
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Divergence {
+    /// Index of the step in the recording
+    pub step: usize,
+    /// Name of the [`GitWorkflow`] call
+    pub call: String,
+    pub field: String,
+    /// Recorded value, or `None` if the field was not recorded
+    pub recorded: Option<String>,
+    /// Replayed value, or `None` if the replay did not produce the field
+    pub replayed: Option<String>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {} ({}): {} recorded {} but replayed {}",
+            self.step,
+            self.call,
+            self.field,
+            self.recorded.as_deref().unwrap_or("<none>"),
+            self.replayed.as_deref().unwrap_or("<none>"),
+        )
+    }
+}
+
+/// Result of replaying a [`Recording`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReplayReport {
+    pub steps_replayed: usize,
+    /// Every field that differed, in step order
+    pub divergences: Vec<Divergence>,
+}
+
+impl ReplayReport {
+    /// Whether every step reproduced its recorded output
+    pub fn is_faithful(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Step at which the replay first diverged
+    pub fn first_divergent_step(&self) -> Option<usize> {
+        self.divergences.first().map(|d| d.step)
+    }
+}
+
+/// Clone `source_repo` into `scratch_dir`, positioned where `recording` began
+///
+/// The clone borrows the source's object database through
+/// `objects/info/alternates`, so commits that are no longer reachable from
+/// any ref (such as reverted attempts) are still available to replay.
+///
+/// # Errors
+///
+/// Returns an error if cloning fails or the base commit is missing
+pub fn scratch_clone(
+    recording: &Recording,
+    source_repo: impl AsRef<Path>,
+    scratch_dir: impl AsRef<Path>,
+) -> GdkResult<GitWorkflowManager> {
+    let scratch_dir = scratch_dir.as_ref();
+    let source = Repository::discover(source_repo.as_ref()).with_git_context("opening replay source")?;
+    let source_objects = fs::canonicalize(git_common_dir(&source).join("objects"))
+        .with_file_context(&source.path().to_string_lossy(), "locating source objects")?;
+
+    let url = source_repo.as_ref().to_string_lossy();
+    let repo = RepoBuilder::new()
+        .clone(&url, scratch_dir)
+        .with_git_context("cloning replay scratch repository")?;
+    let alternates = repo.path().join("objects").join("info").join("alternates");
+    fs::write(&alternates, format!("{}\n", source_objects.display()))
+        .with_file_context(&alternates.to_string_lossy(), "linking source objects")?;
+    drop(repo);
+
+    // Reopen so the alternates are picked up
+    let repo = Repository::open(scratch_dir).with_git_context("opening replay scratch repository")?;
+    let base = Oid::from_str(&recording.base_commit)?;
+    let commit = repo.find_commit(base).with_git_context("finding recording base commit")?;
+    repo.set_head_detached(base).with_git_context("detaching replay HEAD")?;
+    if recording.branch != "HEAD" {
+        repo.branch(&recording.branch, &commit, true)
+            .with_git_context("creating replay branch")?;
+        repo.set_head(&format!("refs/heads/{}", recording.branch))
+            .with_git_context("checking out replay branch")?;
+    }
+    repo.checkout_head(Some(CheckoutBuilder::new().force().remove_untracked(true)))
+        .with_git_context("checking out recording base")?;
+
+    GitWorkflowManager::new(&scratch_dir.to_string_lossy())
+}
+
+/// Re-execute `recording` against `scratch` and compare the outputs
+///
+/// Before each commit the recorded tree is checked out, so the replay
+/// commits exactly what the agent committed. Failed calls are replayed too;
+/// replay continues after a divergence so the report shows every step
+/// that differed.
+///
+/// # Errors
+///
+/// Returns an error only if the scratch repository itself cannot be
+/// prepared for a step; failures of replayed calls are compared instead
+pub async fn replay(recording: &Recording, scratch: &mut GitWorkflowManager) -> GdkResult<ReplayReport> {
+    // Recorded commit hash -> replayed commit hash
+    let mut hashes: HashMap<String, String> = HashMap::new();
+    let mut report = ReplayReport::default();
+
+    for step in &recording.steps {
+        let outcome = match &step.call {
+            RecordedCall::CreateCommitNode { message, tree } => {
+                if let Some(tree) = tree {
+                    checkout_tree(&scratch.repo, tree)?;
+                }
+                match scratch.create_commit_node(message).await {
+                    Ok(node) => RecordedOutcome::commit(&scratch.repo, &node)?,
+                    Err(e) => RecordedOutcome::failed(&e),
+                }
+            }
+            RecordedCall::CreateRevertPoint { reason } => match scratch.create_revert_point(reason).await {
+                Ok(point) => RecordedOutcome::RevertPoint {
+                    commit_hash: point.commit_hash,
+                },
+                Err(e) => RecordedOutcome::failed(&e),
+            },
+            RecordedCall::BeginSpiral => match scratch.begin_spiral().await {
+                Ok(point) => RecordedOutcome::RevertPoint {
+                    commit_hash: point.commit_hash,
+                },
+                Err(e) => RecordedOutcome::failed(&e),
+            },
+            RecordedCall::RevertToPoint { point } => {
+                let mut point = (**point).clone();
+                point.commit_hash = translate(&hashes, &point.commit_hash);
+                match scratch.revert_to_point(&point).await {
+                    Ok(()) => RecordedOutcome::Done,
+                    Err(e) => RecordedOutcome::failed(&e),
+                }
+            }
+            RecordedCall::AnalyzeConvergence => match scratch.analyze_convergence().await {
+                Ok(metrics) => RecordedOutcome::convergence(&metrics),
+                Err(e) => RecordedOutcome::failed(&e),
+            },
+            RecordedCall::UpdateThreadColors => match scratch.update_thread_colors().await {
+                Ok(()) => RecordedOutcome::Done,
+                Err(e) => RecordedOutcome::failed(&e),
+            },
+            RecordedCall::ValidateCiCd { commit_hash } => {
+                match scratch.validate_ci_cd(&translate(&hashes, commit_hash)).await {
+                    Ok(passed) => RecordedOutcome::Validation { passed },
+                    Err(e) => RecordedOutcome::failed(&e),
+                }
+            }
+        };
+
+        if let (
+            RecordedOutcome::Commit { commit_hash: recorded, .. },
+            RecordedOutcome::Commit { commit_hash: replayed, .. },
+        ) = (&step.outcome, &outcome)
+        {
+            hashes.insert(recorded.clone(), replayed.clone());
+        }
+
+        let mut recorded = step.outcome.comparable_fields(&hashes);
+        let replayed = outcome.comparable_fields(&HashMap::new());
+        for (field, replayed_value) in replayed {
+            let recorded_value = recorded.remove(&field);
+            if recorded_value.as_ref() != Some(&replayed_value) {
+                report.divergences.push(Divergence {
+                    step: step.index,
+                    call: step.call.name().to_string(),
+                    field,
+                    recorded: recorded_value,
+                    replayed: Some(replayed_value),
+                });
+            }
+        }
+        for (field, recorded_value) in recorded {
+            report.divergences.push(Divergence {
+                step: step.index,
+                call: step.call.name().to_string(),
+                field,
+                recorded: Some(recorded_value),
+                replayed: None,
+            });
+        }
+        report.steps_replayed += 1;
+    }
+
+    Ok(report)
+}
+
+/// Commit hash in the replay corresponding to a recorded one
+///
+/// Commits that predate the recording exist in both repositories unchanged.
+fn translate(hashes: &HashMap<String, String>, commit_hash: &str) -> String {
+    hashes
+        .get(commit_hash)
+        .cloned()
+        .unwrap_or_else(|| commit_hash.to_string())
+}
+
+/// Make the working tree and index match `tree` without moving HEAD
+fn checkout_tree(repo: &Repository, tree: &str) -> GdkResult<()> {
+    let tree = repo
+        .find_tree(Oid::from_str(tree)?)
+        .with_git_context("finding recorded tree")?;
+    repo.checkout_tree(
+        tree.as_object(),
+        Some(CheckoutBuilder::new().force().remove_untracked(true)),
+    )
+    .with_git_context("checking out recorded tree")
+}
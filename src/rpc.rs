@@ -0,0 +1,472 @@
+//! JSON-RPC 2.0 agent protocol for GDK
+//!
+//! Exposes [`AgentWorkflowController`] to external agents over a
+//! newline-delimited JSON-RPC 2.0 stream (typically stdin/stdout), so one
+//! warm process serves a whole agent run:
+//! - One request, response or notification per line
+//! - Batches, notifications (requests without `id`) and standard error codes
+//! - Server-to-client `gdk/progress` and `gdk/validation` notifications
+//!
+//! # Methods
+//!
+//! | Method                         | Params                          | Result                   |
+//! |--------------------------------|---------------------------------|--------------------------|
+//! | `session.start`                | `agent_id`                      | `{ session_id }`         |
+//! | `workflow.validate_and_commit` | `agent_id`, `message`           | [`CommitNode`]           |
+//! | `workflow.checkpoint`          | `agent_id`, `reason`            | [`RevertPoint`]          |
+//! | `workflow.revert`              | `agent_id`                      | `{ reverted }`           |
+//! | `workflow.validate`            | `preset` (`rust`), `cache`      | [`ValidationResult`]     |
+//! | `convergence.status`           | `agent_id`                      | [`ConvergenceMetrics`]   |
+//! | `agent.suggest`                | `agent_id`                      | `{ suggestion, recommendation, alternatives }` |
+//! | `agent.statistics`             | `agent_id`                      | [`AgentStatistics`]      |
+//!
+//! Workflow failures are reported with code [`SERVER_ERROR`] and the
+//! [`GdkError`] category and recoverability in `error.data`.
+//!
+//! [`CommitNode`]: crate::CommitNode
+//! [`RevertPoint`]: crate::RevertPoint
+//! [`ConvergenceMetrics`]: crate::ConvergenceMetrics
+//! [`ValidationResult`]: crate::validation::ValidationResult
+//! [`AgentStatistics`]: crate::agent::AgentStatistics
+//!
+//! `recommendation` and each of `alternatives` is an
+//! [`advisor::Recommendation`](crate::advisor::Recommendation); `suggestion`
+//! is the recommendation as text.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::rpc::RpcServer;
+//! use tokio::io::BufReader;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     let mut server = RpcServer::new(controller);
+//!     server
+//!         .serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+//!         .await
+//! }
+//! ```
+
+use crate::agent::AgentWorkflowController;
+use crate::core::GitWorkflowManager;
+use crate::validation::{ValidationEvent, ValidationSuite};
+use crate::validation_cache::ValidationCacheConfig;
+use crate::{GdkError, GdkResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+/// Invalid JSON was received
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// Internal JSON-RPC error
+pub const INTERNAL_ERROR: i64 = -32603;
+/// A workflow operation failed with a [`GdkError`]
+pub const SERVER_ERROR: i64 = -32000;
+
+/// JSON-RPC 2.0 request (or notification when `id` is absent)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// JSON-RPC 2.0 error object
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl From<GdkError> for RpcError {
+    fn from(error: GdkError) -> Self {
+        Self {
+            code: SERVER_ERROR,
+            message: error.to_string(),
+            data: Some(json!({
+                "category": error.category(),
+                "recoverable": error.is_recoverable(),
+            })),
+        }
+    }
+}
+
+/// JSON-RPC 2.0 response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Server-to-client JSON-RPC 2.0 notification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
+impl RpcNotification {
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// Stage reported by `gdk/progress` notifications
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStage {
+    Started,
+    Completed,
+    Failed,
+}
+
+#[derive(Deserialize)]
+struct AgentParams {
+    agent_id: String,
+}
+
+#[derive(Deserialize)]
+struct CommitParams {
+    agent_id: String,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CheckpointParams {
+    agent_id: String,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct ValidateParams {
+    #[serde(default = "default_preset")]
+    preset: String,
+    #[serde(default)]
+    cache: bool,
+}
+
+fn default_preset() -> String {
+    "rust".to_string()
+}
+
+/// Sends server-to-client notifications as serialized lines
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    outgoing: UnboundedSender<String>,
+}
+
+impl Notifier {
+    pub fn new(outgoing: UnboundedSender<String>) -> Self {
+        Self { outgoing }
+    }
+
+    /// Send a notification (dropped if the client has gone away)
+    pub fn notify(&self, method: &str, params: Value) {
+        let notification = RpcNotification::new(method, params);
+        let _ = self.outgoing.send(json!(notification).to_string());
+    }
+}
+
+/// Method dispatch behind a JSON-RPC 2.0 endpoint
+///
+/// Framing, batches, notifications and envelope errors are handled by
+/// [`handle_line`] and [`serve_lines`]; implementors only map a
+/// well-formed request to a result.
+#[async_trait::async_trait(?Send)]
+pub trait RpcHandler {
+    async fn handle(&mut self, request: &RpcRequest, notifier: &Notifier) -> Result<Value, RpcError>;
+}
+
+/// Serve newline-delimited messages until the reader reaches EOF
+///
+/// Responses and notifications are written one per line, in the order
+/// they are produced.
+pub async fn serve_lines<H, R, W>(handler: &mut H, reader: R, mut writer: W) -> GdkResult<()>
+where
+    H: RpcHandler,
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let (outgoing, mut outgoing_rx) = unbounded_channel::<String>();
+
+    let read_loop = async move {
+        let notifier = Notifier::new(outgoing.clone());
+        let mut lines = reader.lines();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| GdkError::file_system_error("stdin", "Failed to read request", e))?
+        {
+            if let Some(reply) = handle_line(handler, &line, &notifier).await {
+                let _ = outgoing.send(reply);
+            }
+        }
+        Ok::<(), GdkError>(())
+    };
+
+    let write_loop = async {
+        while let Some(message) = outgoing_rx.recv().await {
+            let write_error = |e| GdkError::file_system_error("stdout", "Failed to write response", e);
+            writer.write_all(message.as_bytes()).await.map_err(write_error)?;
+            writer.write_all(b"\n").await.map_err(write_error)?;
+            writer.flush().await.map_err(write_error)?;
+        }
+        Ok::<(), GdkError>(())
+    };
+
+    let (read_result, write_result) = tokio::join!(read_loop, write_loop);
+    read_result.and(write_result)
+}
+
+/// Handle one line of input, returning the serialized reply (if any)
+pub async fn handle_line<H: RpcHandler>(handler: &mut H, line: &str, notifier: &Notifier) -> Option<String> {
+    if line.trim().is_empty() {
+        return None;
+    }
+
+    let reply = match serde_json::from_str::<Value>(line) {
+        Err(e) => Some(json!(RpcResponse::failure(
+            Value::Null,
+            RpcError::new(PARSE_ERROR, format!("Parse error: {e}")),
+        ))),
+        Ok(Value::Array(batch)) if batch.is_empty() => Some(json!(RpcResponse::failure(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "Empty batch"),
+        ))),
+        Ok(Value::Array(batch)) => {
+            let mut replies = Vec::new();
+            for message in batch {
+                if let Some(reply) = handle_value(handler, message, notifier).await {
+                    replies.push(json!(reply));
+                }
+            }
+            (!replies.is_empty()).then_some(Value::Array(replies))
+        }
+        Ok(message) => handle_value(handler, message, notifier)
+            .await
+            .map(|reply| json!(reply)),
+    };
+
+    reply.map(|reply| reply.to_string())
+}
+
+async fn handle_value<H: RpcHandler>(handler: &mut H, message: Value, notifier: &Notifier) -> Option<RpcResponse> {
+    let request: RpcRequest = match serde_json::from_value(message) {
+        Ok(request) => request,
+        Err(e) => {
+            return Some(RpcResponse::failure(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, format!("Invalid request: {e}")),
+            ))
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        return Some(RpcResponse::failure(
+            request.id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+
+    let outcome = handler.handle(&request, notifier).await;
+
+    // Requests without an id are notifications and get no response
+    let id = request.id?;
+    Some(match outcome {
+        Ok(result) => RpcResponse::success(id, result),
+        Err(error) => RpcResponse::failure(id, error),
+    })
+}
+
+/// Serves JSON-RPC requests against an agent workflow controller
+pub struct RpcServer {
+    /// Controller that requests are dispatched to
+    pub controller: AgentWorkflowController<GitWorkflowManager>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl RpcHandler for RpcServer {
+    async fn handle(&mut self, request: &RpcRequest, notifier: &Notifier) -> Result<Value, RpcError> {
+        let request_id = request.id.clone().unwrap_or(Value::Null);
+        notifier.notify(
+            "gdk/progress",
+            json!({ "request_id": request_id, "method": request.method, "stage": ProgressStage::Started }),
+        );
+        let outcome = self.dispatch(request, notifier).await;
+        let stage = if outcome.is_ok() {
+            ProgressStage::Completed
+        } else {
+            ProgressStage::Failed
+        };
+        notifier.notify(
+            "gdk/progress",
+            json!({ "request_id": request_id, "method": request.method, "stage": stage }),
+        );
+        outcome
+    }
+}
+
+impl RpcServer {
+    pub fn new(controller: AgentWorkflowController<GitWorkflowManager>) -> Self {
+        Self { controller }
+    }
+
+    /// Serve newline-delimited messages until the reader reaches EOF
+    pub async fn serve<R, W>(&mut self, reader: R, writer: W) -> GdkResult<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        serve_lines(self, reader, writer).await
+    }
+
+    /// Handle one line of input, returning the serialized reply (if any)
+    ///
+    /// Notifications produced while handling are sent to `notifications`
+    /// as serialized lines.
+    pub async fn handle_line(&mut self, line: &str, notifications: &UnboundedSender<String>) -> Option<String> {
+        handle_line(self, line, &Notifier::new(notifications.clone())).await
+    }
+
+    async fn dispatch(
+        &mut self,
+        request: &RpcRequest,
+        notifier: &Notifier,
+    ) -> Result<Value, RpcError> {
+        let controller = &mut self.controller;
+        match request.method.as_str() {
+            "session.start" => {
+                let AgentParams { agent_id } = params(&request.params)?;
+                let session_id = controller.start_agent_session(&agent_id).await?;
+                Ok(json!({ "session_id": session_id }))
+            }
+            "workflow.validate_and_commit" => {
+                let CommitParams { agent_id, message } = params(&request.params)?;
+                to_value(controller.validate_and_commit(&agent_id, &message).await?)
+            }
+            "workflow.checkpoint" => {
+                let CheckpointParams { agent_id, reason } = params(&request.params)?;
+                to_value(controller.create_spiral_checkpoint(&agent_id, &reason).await?)
+            }
+            "workflow.revert" => {
+                let AgentParams { agent_id } = params(&request.params)?;
+                controller.revert_to_last_checkpoint(&agent_id).await?;
+                Ok(json!({ "reverted": true }))
+            }
+            "workflow.validate" => {
+                let ValidateParams { preset, cache } = params(&request.params)?;
+                let repo_path = controller.workflow.repo_path.clone();
+                let mut suite =
+                    ValidationSuite::from_preset(&preset, &repo_path).map_err(|e| match e {
+                        GdkError::ConfigurationError { .. } => {
+                            RpcError::new(INVALID_PARAMS, e.to_string())
+                        }
+                        e => e.into(),
+                    })?;
+                if cache {
+                    suite.set_cache(ValidationCacheConfig::default());
+                }
+
+                let request_id = request.id.clone().unwrap_or(Value::Null);
+                let (events, mut events_rx) = unbounded_channel::<ValidationEvent>();
+                let forward = async {
+                    while let Some(event) = events_rx.recv().await {
+                        notifier.notify(
+                            "gdk/validation",
+                            json!({ "request_id": request_id, "event": event }),
+                        );
+                    }
+                };
+                let (result, ()) =
+                    tokio::join!(suite.validate_with_progress(&repo_path, events), forward);
+                to_value(result?)
+            }
+            "convergence.status" => {
+                let AgentParams { agent_id } = params(&request.params)?;
+                to_value(controller.get_convergence_status(&agent_id).await?)
+            }
+            "agent.suggest" => {
+                let AgentParams { agent_id } = params(&request.params)?;
+                let mut recommendations = controller.recommend_next_actions(&agent_id)?;
+                let best = recommendations.remove(0);
+                Ok(json!({
+                    "suggestion": best.to_string(),
+                    "recommendation": best,
+                    "alternatives": recommendations,
+                }))
+            }
+            "agent.statistics" => {
+                let AgentParams { agent_id } = params(&request.params)?;
+                to_value(controller.get_agent_statistics(&agent_id)?)
+            }
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {other}"),
+            )),
+        }
+    }
+}
+
+fn params<P: DeserializeOwned>(params: &Value) -> Result<P, RpcError> {
+    serde_json::from_value(params.clone())
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid params: {e}")))
+}
+
+fn to_value(result: impl Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(result).map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))
+}
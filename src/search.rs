@@ -0,0 +1,281 @@
+//! Search strategies for the infinite monkey workflow
+//!
+//! After every attempt the agent loop asks a [`SearchStrategy`] where the
+//! next attempt should start. Strategies differ in how they use earlier
+//! attempts:
+//! - **Random restart**: always start over from the initial revert point
+//! - **Hill climbing**: continue from the best attempt so far
+//! - **Beam search**: rotate between the best `width` attempts (spiral branches)
+//! - **Simulated annealing**: sometimes accept worse attempts, less often as
+//!   the temperature cools
+//!
+//! Strategies are selected per agent session through the serializable
+//! [`SearchStrategyKind`].
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::search::SearchStrategyKind;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let mut controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     controller.start_agent_session("agent-1").await?;
+//!     controller.set_search_strategy("agent-1", SearchStrategyKind::BeamSearch { width: 3 })?;
+//!
+//!     let result = controller.execute_infinite_monkey_workflow("agent-1", 0.8).await?;
+//!     println!("Converged with health score: {}", result.health_score);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::RevertPoint;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A completed attempt that later attempts can start from
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchAttempt {
+    /// Revert point capturing the attempt's commit
+    pub point: RevertPoint,
+    /// Health score of the attempt
+    pub score: f64,
+}
+
+/// Chooses the starting point of the next infinite monkey attempt
+pub trait SearchStrategy: Send + Sync + fmt::Debug {
+    /// Configuration this strategy was built from
+    fn kind(&self) -> SearchStrategyKind;
+
+    /// Record an attempt and return the revert point the next attempt starts from
+    fn next_start(&mut self, initial: &RevertPoint, attempt: SearchAttempt) -> RevertPoint;
+
+    /// Best attempt recorded so far
+    fn best(&self) -> Option<&SearchAttempt>;
+}
+
+/// Serializable strategy selection
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum SearchStrategyKind {
+    /// Restart every attempt from the initial revert point
+    #[default]
+    RandomRestart,
+    /// Continue from the best attempt so far
+    HillClimbing,
+    /// Rotate between the best `width` attempts
+    BeamSearch { width: usize },
+    /// Accept worse attempts with probability `exp(Δ / T)`, cooling `T` geometrically
+    SimulatedAnnealing {
+        initial_temperature: f64,
+        cooling_rate: f64,
+        seed: u64,
+    },
+}
+
+impl SearchStrategyKind {
+    /// Instantiate the strategy
+    pub fn build(&self) -> Box<dyn SearchStrategy> {
+        match self {
+            SearchStrategyKind::RandomRestart => Box::new(RandomRestart::default()),
+            SearchStrategyKind::HillClimbing => Box::new(HillClimbing::default()),
+            SearchStrategyKind::BeamSearch { width } => Box::new(BeamSearch::new(*width)),
+            SearchStrategyKind::SimulatedAnnealing {
+                initial_temperature,
+                cooling_rate,
+                seed,
+            } => Box::new(SimulatedAnnealing::new(*initial_temperature, *cooling_rate, *seed)),
+        }
+    }
+
+    /// Short name for logs and statistics
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchStrategyKind::RandomRestart => "random_restart",
+            SearchStrategyKind::HillClimbing => "hill_climbing",
+            SearchStrategyKind::BeamSearch { .. } => "beam_search",
+            SearchStrategyKind::SimulatedAnnealing { .. } => "simulated_annealing",
+        }
+    }
+}
+
+fn keep_best(best: &mut Option<SearchAttempt>, attempt: &SearchAttempt) {
+    if best.as_ref().is_none_or(|b| attempt.score > b.score) {
+        *best = Some(attempt.clone());
+    }
+}
+
+/// Today's behavior: every attempt starts from the initial revert point
+#[derive(Debug, Default)]
+pub struct RandomRestart {
+    best: Option<SearchAttempt>,
+}
+
+impl SearchStrategy for RandomRestart {
+    fn kind(&self) -> SearchStrategyKind {
+        SearchStrategyKind::RandomRestart
+    }
+
+    fn next_start(&mut self, initial: &RevertPoint, attempt: SearchAttempt) -> RevertPoint {
+        keep_best(&mut self.best, &attempt);
+        initial.clone()
+    }
+
+    fn best(&self) -> Option<&SearchAttempt> {
+        self.best.as_ref()
+    }
+}
+
+/// Greedy hill climbing: keep the best attempt and build on it
+#[derive(Debug, Default)]
+pub struct HillClimbing {
+    best: Option<SearchAttempt>,
+}
+
+impl SearchStrategy for HillClimbing {
+    fn kind(&self) -> SearchStrategyKind {
+        SearchStrategyKind::HillClimbing
+    }
+
+    fn next_start(&mut self, _initial: &RevertPoint, attempt: SearchAttempt) -> RevertPoint {
+        keep_best(&mut self.best, &attempt);
+        self.best.as_ref().map(|b| b.point.clone()).unwrap_or(attempt.point)
+    }
+
+    fn best(&self) -> Option<&SearchAttempt> {
+        self.best.as_ref()
+    }
+}
+
+/// Beam search over the best `width` attempts
+///
+/// Successive attempts start from each beam member in turn, so `width`
+/// spiral branches are extended side by side.
+#[derive(Debug)]
+pub struct BeamSearch {
+    width: usize,
+    beam: Vec<SearchAttempt>,
+    cursor: usize,
+}
+
+impl BeamSearch {
+    pub fn new(width: usize) -> Self {
+        Self {
+            width: width.max(1),
+            beam: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Current beam, best first
+    pub fn beam(&self) -> &[SearchAttempt] {
+        &self.beam
+    }
+}
+
+impl SearchStrategy for BeamSearch {
+    fn kind(&self) -> SearchStrategyKind {
+        SearchStrategyKind::BeamSearch { width: self.width }
+    }
+
+    fn next_start(&mut self, initial: &RevertPoint, attempt: SearchAttempt) -> RevertPoint {
+        self.beam.push(attempt);
+        self.beam.sort_by(|a, b| b.score.total_cmp(&a.score));
+        self.beam.truncate(self.width);
+
+        // Keep the initial point as a branch until the beam is full
+        let slots = self.beam.len() + usize::from(self.beam.len() < self.width);
+        let slot = self.cursor % slots;
+        self.cursor += 1;
+
+        self.beam
+            .get(slot)
+            .map(|member| member.point.clone())
+            .unwrap_or_else(|| initial.clone())
+    }
+
+    fn best(&self) -> Option<&SearchAttempt> {
+        self.beam.first()
+    }
+}
+
+/// Simulated annealing with a geometric cooling schedule
+///
+/// Uses a seeded xorshift generator so runs are reproducible.
+#[derive(Debug)]
+pub struct SimulatedAnnealing {
+    initial_temperature: f64,
+    cooling_rate: f64,
+    seed: u64,
+    temperature: f64,
+    rng_state: u64,
+    current: Option<SearchAttempt>,
+    best: Option<SearchAttempt>,
+}
+
+impl SimulatedAnnealing {
+    pub fn new(initial_temperature: f64, cooling_rate: f64, seed: u64) -> Self {
+        Self {
+            initial_temperature,
+            cooling_rate,
+            seed,
+            temperature: initial_temperature,
+            // xorshift must not start at zero
+            rng_state: seed.max(1),
+            current: None,
+            best: None,
+        }
+    }
+
+    /// Current temperature
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    fn next_unit(&mut self) -> f64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl SearchStrategy for SimulatedAnnealing {
+    fn kind(&self) -> SearchStrategyKind {
+        SearchStrategyKind::SimulatedAnnealing {
+            initial_temperature: self.initial_temperature,
+            cooling_rate: self.cooling_rate,
+            seed: self.seed,
+        }
+    }
+
+    fn next_start(&mut self, initial: &RevertPoint, attempt: SearchAttempt) -> RevertPoint {
+        keep_best(&mut self.best, &attempt);
+
+        let accept = match &self.current {
+            None => true,
+            Some(current) => {
+                let delta = attempt.score - current.score;
+                delta >= 0.0
+                    || (self.temperature > 0.0 && self.next_unit() < (delta / self.temperature).exp())
+            }
+        };
+        if accept {
+            self.current = Some(attempt);
+        }
+        self.temperature *= self.cooling_rate;
+
+        self.current
+            .as_ref()
+            .map(|current| current.point.clone())
+            .unwrap_or_else(|| initial.clone())
+    }
+
+    fn best(&self) -> Option<&SearchAttempt> {
+        self.best.as_ref()
+    }
+}
@@ -0,0 +1,861 @@
+//! HTTP REST API server for GDK
+//!
+//! Hosts one or more repositories and exposes their agent sessions, commits,
+//! checkpoints, validation runs, thread state and visualizations as REST
+//! resources with JSON bodies:
+//! - `GET /healthz` for liveness and readiness probes
+//! - `GET /openapi.json` describing every route
+//! - `/repos/{repo}/...` resources per hosted repository, including the
+//!   approval queue for converged spirals
+//!
+//! The server speaks a minimal HTTP/1.1 subset (one request per
+//! connection, `Content-Length` bodies). Connections are read concurrently
+//! and `/healthz` is answered without waiting on the repositories, while
+//! repository requests are handled one at a time so they never interleave.
+//!
+//! Every `/repos` request must carry an `Authorization: Bearer <token>`
+//! header naming a caller in the server's [`ApiTokens`]. Each repository's
+//! access policy authorizes that caller, not the agent named in the path,
+//! and approval decisions are made as the caller.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::server::{ApiTokens, HttpServer};
+//! use tokio::net::TcpListener;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let mut server = HttpServer::new()
+//!         .with_tokens(ApiTokens::load("./tokens.json")?)
+//!         .with_repository("api", "./services/api")?
+//!         .with_repository("web", "./services/web")?;
+//!
+//!     let listener = TcpListener::bind("127.0.0.1:8080").await?;
+//!     server.serve(listener, async { tokio::signal::ctrl_c().await.ok(); }).await
+//! }
+//! ```
+
+use crate::agent::AgentWorkflowController;
+use crate::approval::{ApprovalGate, ApprovalRequest};
+use crate::core::GitWorkflowManager;
+use crate::policy::{AccessPolicy, CommitPolicy};
+use crate::validation::ValidationSuite;
+use crate::validation_cache::ValidationCacheConfig;
+use crate::visualization::{export_tree_ascii, export_tree_html, export_tree_svg};
+use crate::{GdkError, GdkResult, GdkResultExt, GitWorkflow};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+/// Largest accepted request head (request line and headers)
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// Largest accepted request body
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// Time allowed for a client to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Parsed HTTP request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn new(method: &str, target: &str, body: impl Into<Vec<u8>>) -> Self {
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, parse_query(query)),
+            None => (target, HashMap::new()),
+        };
+        Self {
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            query,
+            headers: HashMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// HTTP response
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, body: &impl Serialize) -> Self {
+        Self {
+            status,
+            content_type: "application/json".to_string(),
+            body: json!(body).to_string().into_bytes(),
+        }
+    }
+
+    pub fn text(status: u16, content_type: &str, body: String) -> Self {
+        Self {
+            status,
+            content_type: content_type.to_string(),
+            body: body.into_bytes(),
+        }
+    }
+
+    pub fn error(status: u16, category: &str, message: impl Into<String>) -> Self {
+        Self::json(
+            status,
+            &json!({ "error": { "category": category, "message": message.into() } }),
+        )
+    }
+
+    /// Body parsed as JSON
+    pub fn json_body(&self) -> serde_json::Result<Value> {
+        serde_json::from_slice(&self.body)
+    }
+
+    /// Serialize as an HTTP/1.1 message that closes the connection
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.content_type,
+            self.body.len()
+        )
+        .into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+impl From<GdkError> for HttpResponse {
+    fn from(error: GdkError) -> Self {
+        let status = match &error {
+            GdkError::ConfigurationError { .. } => 400,
+            GdkError::PermissionDenied { .. } => 403,
+            GdkError::AgentError { .. } => 409,
+            GdkError::ValidationError { .. } => 422,
+            GdkError::BudgetExhausted { .. } => 429,
+            _ => 500,
+        };
+        Self::json(
+            status,
+            &json!({ "error": {
+                "category": error.category(),
+                "message": error.to_string(),
+                "recoverable": error.is_recoverable(),
+            } }),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct StartSessionBody {
+    agent_id: String,
+}
+
+#[derive(Deserialize)]
+struct CommitBody {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CheckpointBody {
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct ValidationBody {
+    #[serde(default = "default_preset")]
+    preset: String,
+    #[serde(default)]
+    cache: bool,
+}
+
+#[derive(Deserialize)]
+struct RejectBody {
+    reason: String,
+}
+
+fn default_preset() -> String {
+    "rust".to_string()
+}
+
+/// Summary of a hosted repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RepositorySummary {
+    pub name: String,
+    pub path: String,
+    pub active_sessions: usize,
+    pub commits: usize,
+}
+
+/// Bearer tokens identifying API callers
+///
+/// Stored as JSON mapping each identity to the hex SHA-256 digest of its
+/// token, so the file never holds a usable token:
+///
+/// ```json
+/// { "lead@example.com": "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct ApiTokens {
+    digests: BTreeMap<String, String>,
+}
+
+impl ApiTokens {
+    /// Read tokens from a JSON file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(path: impl AsRef<Path>) -> GdkResult<Self> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).with_file_context(&path.to_string_lossy(), "reading API tokens")?;
+        serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: format!("API tokens {}", path.display()),
+            source: e,
+        })
+    }
+
+    /// Add `token` for `identity`, replacing any it had
+    pub fn with_token(mut self, identity: &str, token: &str) -> Self {
+        self.digests.insert(identity.to_string(), Self::digest(token));
+        self
+    }
+
+    /// Hex SHA-256 digest stored for `token`
+    pub fn digest(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Identity holding `token`, if any
+    pub fn identify(&self, token: &str) -> Option<&str> {
+        let digest = Self::digest(token);
+        self.digests
+            .iter()
+            .find(|(_, known)| known.eq_ignore_ascii_case(&digest))
+            .map(|(identity, _)| identity.as_str())
+    }
+
+    /// Identity of the bearer token `request` carries, if it is known
+    pub fn authenticate(&self, request: &HttpRequest) -> Option<&str> {
+        let token = request.headers.get("authorization")?.strip_prefix("Bearer ")?.trim();
+        if token.is_empty() {
+            return None;
+        }
+        self.identify(token)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+}
+
+/// REST server hosting named repositories
+#[derive(Default)]
+pub struct HttpServer {
+    /// Controllers for each hosted repository, by name
+    pub repositories: BTreeMap<String, AgentWorkflowController<GitWorkflowManager>>,
+    /// Tokens identifying callers
+    pub tokens: ApiTokens,
+}
+
+impl HttpServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Identify callers by these bearer tokens
+    pub fn with_tokens(mut self, tokens: ApiTokens) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// Host the repository at `path` under `/repos/{name}`
+    ///
+    /// Converged spirals wait for approval through `/repos/{name}/approvals`,
+    /// and the repository's access and commit policies apply to every session.
+    pub fn with_repository(mut self, name: &str, path: &str) -> GdkResult<Self> {
+        let workflow = GitWorkflowManager::new(path)?.with_commit_policy(CommitPolicy::for_repo(path)?);
+        let controller = AgentWorkflowController::new(workflow)
+            .with_approval_gate(ApprovalGate::for_repo(path)?)
+            .with_policy(AccessPolicy::for_repo(path)?);
+        self.add_repository(name, controller);
+        Ok(self)
+    }
+
+    /// Host an existing controller under `/repos/{name}`
+    pub fn add_repository(&mut self, name: &str, controller: AgentWorkflowController<GitWorkflowManager>) {
+        self.repositories.insert(name.to_string(), controller);
+    }
+
+    /// Accept connections until `shutdown` completes
+    ///
+    /// Each connection is read in its own task, and health checks are
+    /// answered there, so a slow client or a long-running commit never holds
+    /// up a probe. Repository operations are handled one at a time. On
+    /// shutdown, requests already read are still answered.
+    pub async fn serve(&mut self, listener: TcpListener, shutdown: impl Future<Output = ()>) -> GdkResult<()> {
+        let repositories = self.repositories.len();
+        let (requests, mut queue) = mpsc::channel::<(HttpRequest, oneshot::Sender<HttpResponse>)>(64);
+        let (stop, stopped) = watch::channel(false);
+        let mut connections = JoinSet::new();
+
+        let handler = async {
+            while let Some((request, reply)) = queue.recv().await {
+                // The client gave up while the request was queued
+                if reply.is_closed() {
+                    continue;
+                }
+                let _ = reply.send(self.handle(request).await);
+            }
+        };
+        tokio::pin!(handler);
+        tokio::pin!(shutdown);
+
+        let result = loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        connections.spawn(serve_connection(stream, peer, repositories, requests.clone(), stopped.clone()));
+                    }
+                    Err(e) => break Err(e.into()),
+                },
+                Some(_) = connections.join_next(), if !connections.is_empty() => {}
+                () = &mut handler => {}
+                () = &mut shutdown => break Ok(()),
+            }
+        };
+
+        // Stop reading new requests, then answer the ones already queued
+        let _ = stop.send(true);
+        drop(requests);
+        handler.await;
+        while connections.join_next().await.is_some() {}
+        result
+    }
+
+    /// Route a request to its handler
+    pub async fn handle(&mut self, request: HttpRequest) -> HttpResponse {
+        let segments: Vec<String> = request
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let no_route = || HttpResponse::error(404, "not_found", format!("No route for {} {}", request.method, request.path));
+
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["healthz"]) => health(self.repositories.len()),
+            ("GET", ["openapi.json"]) => HttpResponse::json(200, &openapi_document()),
+            (method, ["repos", rest @ ..]) => {
+                let Some(caller) = self.tokens.authenticate(&request) else {
+                    return HttpResponse::error(
+                        401,
+                        "authentication",
+                        "A valid 'Authorization: Bearer <token>' header is required",
+                    );
+                };
+                match (method, rest) {
+                    ("GET", []) => self.summaries(),
+                    (method, [name, rest @ ..]) => {
+                        let Some(controller) = self.repositories.get_mut(*name) else {
+                            return HttpResponse::error(404, "not_found", format!("Unknown repository: {name}"));
+                        };
+                        controller.caller = Some(caller.to_string());
+                        route_repository(controller, caller, method, rest, &request)
+                            .await
+                            .unwrap_or_else(|response| response)
+                    }
+                    _ => no_route(),
+                }
+            }
+            _ => no_route(),
+        }
+    }
+
+    fn summaries(&self) -> HttpResponse {
+        let summaries: Vec<RepositorySummary> = self
+            .repositories
+            .iter()
+            .map(|(name, controller)| RepositorySummary {
+                name: name.clone(),
+                path: controller.workflow.repo_path.clone(),
+                active_sessions: controller
+                    .active_sessions
+                    .values()
+                    .filter(|session| session.state.is_live())
+                    .count(),
+                commits: controller.workflow.commit_history.len(),
+            })
+            .collect();
+        HttpResponse::json(200, &summaries)
+    }
+}
+
+async fn route_repository(
+    controller: &mut AgentWorkflowController<GitWorkflowManager>,
+    caller: &str,
+    method: &str,
+    path: &[&str],
+    request: &HttpRequest,
+) -> Result<HttpResponse, HttpResponse> {
+    match (method, path) {
+        ("GET", ["sessions"]) => {
+            let mut sessions: Vec<_> = controller.active_sessions.values().collect();
+            sessions.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+            Ok(HttpResponse::json(200, &sessions))
+        }
+        ("POST", ["sessions"]) => {
+            let StartSessionBody { agent_id } = body(request)?;
+            let session_id = controller.start_agent_session(&agent_id).await?;
+            Ok(HttpResponse::json(201, &json!({ "agent_id": agent_id, "session_id": session_id })))
+        }
+        ("GET", ["sessions", agent_id]) => {
+            require_session(controller, agent_id)?;
+            Ok(HttpResponse::json(200, &controller.get_agent_statistics(agent_id)?))
+        }
+        ("POST", ["sessions", agent_id, "commits"]) => {
+            require_session(controller, agent_id)?;
+            let CommitBody { message } = body(request)?;
+            Ok(HttpResponse::json(201, &controller.validate_and_commit(agent_id, &message).await?))
+        }
+        ("POST", ["sessions", agent_id, "checkpoints"]) => {
+            require_session(controller, agent_id)?;
+            let CheckpointBody { reason } = body(request)?;
+            Ok(HttpResponse::json(201, &controller.create_spiral_checkpoint(agent_id, &reason).await?))
+        }
+        ("POST", ["sessions", agent_id, "revert"]) => {
+            require_session(controller, agent_id)?;
+            controller.revert_to_last_checkpoint(agent_id).await?;
+            Ok(HttpResponse::json(200, &json!({ "reverted": true })))
+        }
+        ("GET", ["commits"]) => Ok(HttpResponse::json(200, &controller.workflow.commit_history)),
+        ("GET", ["approvals"]) => {
+            let requests = if request.query.get("all").is_some_and(|all| all == "true") {
+                require_gate(controller)?.list()?
+            } else {
+                require_gate(controller)?.pending()?
+            };
+            Ok(HttpResponse::json(200, &requests))
+        }
+        ("GET", ["approvals", id]) => Ok(HttpResponse::json(200, &require_approval(controller, id)?)),
+        ("POST", ["approvals", id, "approve"]) => {
+            let id = require_approval(controller, id)?.id;
+            Ok(HttpResponse::json(200, &controller.approve_promotion(id, caller)?))
+        }
+        ("POST", ["approvals", id, "reject"]) => {
+            let id = require_approval(controller, id)?.id;
+            let RejectBody { reason } = body(request)?;
+            Ok(HttpResponse::json(200, &controller.reject_promotion(id, caller, &reason)?))
+        }
+        ("POST", ["validations"]) => {
+            let ValidationBody { preset, cache } = body(request)?;
+            let repo_path = &controller.workflow.repo_path;
+            let mut suite = ValidationSuite::from_preset(&preset, repo_path)?;
+            if cache {
+                suite.set_cache(ValidationCacheConfig::default());
+            }
+            Ok(HttpResponse::json(200, &suite.validate(repo_path).await?))
+        }
+        ("GET", ["threads"]) => Ok(HttpResponse::json(200, &controller.workflow.latest_threads())),
+        ("GET", ["convergence"]) => Ok(HttpResponse::json(200, &controller.workflow.analyze_convergence().await?)),
+        ("GET", ["visualization"]) => {
+            let commits = &controller.workflow.commit_history;
+            let format = request.query.get("format").map(String::as_str).unwrap_or("ascii");
+            let (rendered, content_type) = match format {
+                "ascii" | "txt" => (export_tree_ascii(commits, None), "text/plain; charset=utf-8"),
+                "svg" => (export_tree_svg(commits, None), "image/svg+xml"),
+                "html" => (export_tree_html(commits, None), "text/html; charset=utf-8"),
+                other => {
+                    return Err(HttpResponse::error(
+                        400,
+                        "configuration",
+                        format!("Unsupported format: {other}. Use 'ascii', 'svg', or 'html'"),
+                    ))
+                }
+            };
+            let rendered =
+                rendered.map_err(|e| HttpResponse::error(500, "visualization", e.to_string()))?;
+            Ok(HttpResponse::text(200, content_type, rendered))
+        }
+        _ => Err(HttpResponse::error(
+            404,
+            "not_found",
+            format!("No route for {} {}", request.method, request.path),
+        )),
+    }
+}
+
+fn require_session(
+    controller: &AgentWorkflowController<GitWorkflowManager>,
+    agent_id: &str,
+) -> Result<(), HttpResponse> {
+    if controller.active_sessions.contains_key(agent_id) {
+        Ok(())
+    } else {
+        Err(HttpResponse::error(404, "not_found", format!("Unknown session: {agent_id}")))
+    }
+}
+
+fn require_gate(controller: &AgentWorkflowController<GitWorkflowManager>) -> Result<&ApprovalGate, HttpResponse> {
+    controller
+        .approval_gate
+        .as_ref()
+        .ok_or_else(|| HttpResponse::error(404, "not_found", "Approvals are not enabled for this repository"))
+}
+
+fn require_approval(
+    controller: &AgentWorkflowController<GitWorkflowManager>,
+    id: &str,
+) -> Result<ApprovalRequest, HttpResponse> {
+    let unknown = || HttpResponse::error(404, "not_found", format!("Unknown approval: {id}"));
+    let id = Uuid::parse_str(id).map_err(|_| unknown())?;
+    require_gate(controller)?
+        .list()?
+        .into_iter()
+        .find(|request| request.id == id)
+        .ok_or_else(unknown)
+}
+
+fn body<B: DeserializeOwned>(request: &HttpRequest) -> Result<B, HttpResponse> {
+    serde_json::from_slice(&request.body)
+        .map_err(|e| HttpResponse::error(400, "serialization", format!("Invalid JSON body: {e}")))
+}
+
+/// Read one request from `stream` and write its response
+///
+/// Health checks are answered here; everything else goes through `requests`
+/// to the task that owns the repositories.
+async fn serve_connection(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    repositories: usize,
+    requests: mpsc::Sender<(HttpRequest, oneshot::Sender<HttpResponse>)>,
+    mut stopped: watch::Receiver<bool>,
+) {
+    let read = tokio::select! {
+        read = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)) => read,
+        Ok(()) = stopped.changed() => return,
+    };
+
+    let response = match read {
+        Ok(Ok(request)) if is_health_check(&request) => health(repositories),
+        Ok(Ok(request)) => {
+            tracing::debug!("{} {} {}", peer, request.method, request.path);
+            let (reply, response) = oneshot::channel();
+            if requests.send((request, reply)).await.is_err() {
+                return;
+            }
+            drop(requests);
+            match response.await {
+                Ok(response) => response,
+                Err(_) => return,
+            }
+        }
+        Ok(Err(response)) => response,
+        Err(_) => HttpResponse::error(408, "http", "Timed out reading request"),
+    };
+
+    if let Err(e) = stream.write_all(&response.to_bytes()).await {
+        tracing::warn!("Failed to write response to {}: {}", peer, e);
+    }
+    let _ = stream.shutdown().await;
+}
+
+fn is_health_check(request: &HttpRequest) -> bool {
+    request.method == "GET" && request.path.trim_matches('/') == "healthz"
+}
+
+fn health(repositories: usize) -> HttpResponse {
+    HttpResponse::json(200, &json!({ "status": "ok", "repositories": repositories }))
+}
+
+/// Read one request, or the error response to send instead
+async fn read_request<R: AsyncRead + Unpin>(stream: &mut R) -> Result<HttpRequest, HttpResponse> {
+    let bad_request = |message: &str| HttpResponse::error(400, "http", message);
+
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let head_end = loop {
+        if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(HttpResponse::error(431, "http", "Request head too large"));
+        }
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| bad_request(&e.to_string()))?;
+        if read == 0 {
+            return Err(bad_request("Connection closed before request was complete"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..head_end]).map_err(|_| bad_request("Request head is not UTF-8"))?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target), Some(_version)) =
+        (request_line.next(), request_line.next(), request_line.next())
+    else {
+        return Err(bad_request("Malformed request line"));
+    };
+
+    let mut request = HttpRequest::new(method, target, Vec::new());
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            request
+                .headers
+                .insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = match request.headers.get("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| bad_request("Invalid Content-Length"))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpResponse::error(413, "http", "Request body too large"));
+    }
+
+    let mut body = buffer.split_off(head_end + 4);
+    while body.len() < content_length {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| bad_request(&e.to_string()))?;
+        if read == 0 {
+            return Err(bad_request("Connection closed before body was complete"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    request.body = body;
+
+    Ok(request)
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+/// OpenAPI 3.0 description of the REST API
+pub fn openapi_document() -> Value {
+    let repo = json!({ "name": "repo", "in": "path", "required": true, "schema": { "type": "string" } });
+    let agent = json!({ "name": "agent_id", "in": "path", "required": true, "schema": { "type": "string" } });
+    let approval = json!({ "name": "id", "in": "path", "required": true, "schema": { "type": "string", "format": "uuid" } });
+    let json_body = |properties: Value, required: Value| {
+        json!({
+            "required": true,
+            "content": { "application/json": { "schema": {
+                "type": "object", "properties": properties, "required": required,
+            } } },
+        })
+    };
+    let ok = |description: &str| json!({ "description": description, "content": { "application/json": {} } });
+    let errors = json!({ "$ref": "#/components/responses/Error" });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "GDK REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Git workflow sessions, commits, checkpoints, validation and thread state",
+        },
+        "security": [{ "bearer": [] }],
+        "paths": {
+            "/healthz": { "get": { "summary": "Liveness and readiness probe", "security": [], "responses": { "200": ok("Server is healthy") } } },
+            "/openapi.json": { "get": { "summary": "This document", "security": [], "responses": { "200": ok("OpenAPI document") } } },
+            "/repos": { "get": { "summary": "List hosted repositories", "responses": { "200": ok("Repository summaries") } } },
+            "/repos/{repo}/sessions": {
+                "parameters": [repo],
+                "get": { "summary": "List agent sessions", "responses": { "200": ok("Agent sessions"), "404": errors } },
+                "post": {
+                    "summary": "Start an agent session",
+                    "requestBody": json_body(json!({ "agent_id": { "type": "string" } }), json!(["agent_id"])),
+                    "responses": { "201": ok("Session started"), "400": errors, "404": errors, "409": errors },
+                },
+            },
+            "/repos/{repo}/sessions/{agent_id}": {
+                "parameters": [repo, agent],
+                "get": { "summary": "Agent statistics", "responses": { "200": ok("Agent statistics"), "404": errors } },
+            },
+            "/repos/{repo}/sessions/{agent_id}/commits": {
+                "parameters": [repo, agent],
+                "post": {
+                    "summary": "Validate the working tree and commit it with quality analysis",
+                    "requestBody": json_body(json!({ "message": { "type": "string" } }), json!(["message"])),
+                    "responses": { "201": ok("Commit node"), "400": errors, "403": errors, "404": errors, "422": errors, "429": errors },
+                },
+            },
+            "/repos/{repo}/sessions/{agent_id}/checkpoints": {
+                "parameters": [repo, agent],
+                "post": {
+                    "summary": "Create a revert checkpoint",
+                    "requestBody": json_body(json!({ "reason": { "type": "string" } }), json!(["reason"])),
+                    "responses": { "201": ok("Revert point"), "400": errors, "403": errors, "404": errors },
+                },
+            },
+            "/repos/{repo}/sessions/{agent_id}/revert": {
+                "parameters": [repo, agent],
+                "post": { "summary": "Revert to the last checkpoint", "responses": { "200": ok("Reverted"), "403": errors, "404": errors, "422": errors, "429": errors } },
+            },
+            "/repos/{repo}/commits": {
+                "parameters": [repo],
+                "get": { "summary": "Recorded commit nodes", "responses": { "200": ok("Commit nodes"), "404": errors } },
+            },
+            "/repos/{repo}/approvals": {
+                "parameters": [repo, { "name": "all", "in": "query", "schema": { "type": "boolean", "default": false } }],
+                "get": { "summary": "Converged results awaiting approval (every request with all=true)", "responses": { "200": ok("Approval requests"), "404": errors } },
+            },
+            "/repos/{repo}/approvals/{id}": {
+                "parameters": [repo, approval],
+                "get": { "summary": "Approval request with diff stats, quality delta and gate results", "responses": { "200": ok("Approval request"), "404": errors } },
+            },
+            "/repos/{repo}/approvals/{id}/approve": {
+                "parameters": [repo, approval],
+                "post": {
+                    "summary": "Approve a converged result and fast-forward its target branch",
+                    "responses": { "200": ok("Approved request"), "401": errors, "403": errors, "404": errors, "422": errors },
+                },
+            },
+            "/repos/{repo}/approvals/{id}/reject": {
+                "parameters": [repo, approval],
+                "post": {
+                    "summary": "Reject a converged result with a reason",
+                    "requestBody": json_body(json!({ "reason": { "type": "string" } }), json!(["reason"])),
+                    "responses": { "200": ok("Rejected request"), "400": errors, "401": errors, "403": errors, "404": errors, "422": errors },
+                },
+            },
+            "/repos/{repo}/validations": {
+                "parameters": [repo],
+                "post": {
+                    "summary": "Run a validation suite",
+                    "requestBody": json_body(
+                        json!({
+                            "preset": { "type": "string", "enum": ["rust", "python", "node", "go", "polyglot"], "default": "rust" },
+                            "cache": { "type": "boolean", "default": false },
+                        }),
+                        json!([]),
+                    ),
+                    "responses": { "200": ok("Validation result"), "400": errors, "404": errors },
+                },
+            },
+            "/repos/{repo}/threads": {
+                "parameters": [repo],
+                "get": { "summary": "Latest quality thread per file", "responses": { "200": ok("Threads by path"), "404": errors } },
+            },
+            "/repos/{repo}/convergence": {
+                "parameters": [repo],
+                "get": { "summary": "Convergence report for HEAD's lineage", "responses": { "200": ok("Convergence metrics"), "404": errors } },
+            },
+            "/repos/{repo}/visualization": {
+                "parameters": [repo, { "name": "format", "in": "query", "schema": { "type": "string", "enum": ["ascii", "svg", "html"], "default": "ascii" } }],
+                "get": {
+                    "summary": "Render the commit tree",
+                    "responses": {
+                        "200": { "description": "Rendered tree", "content": { "text/plain": {}, "image/svg+xml": {}, "text/html": {} } },
+                        "400": errors,
+                        "404": errors,
+                    },
+                },
+            },
+        },
+        "components": {
+            "responses": {
+                "Error": {
+                    "description": "Error with category and message",
+                    "content": { "application/json": { "schema": {
+                        "type": "object",
+                        "properties": { "error": {
+                            "type": "object",
+                            "properties": {
+                                "category": { "type": "string" },
+                                "message": { "type": "string" },
+                                "recoverable": { "type": "boolean" },
+                            },
+                        } },
+                    } } },
+                },
+            },
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
@@ -0,0 +1,232 @@
+//! Agent session lifecycle and persistence
+//!
+//! Sessions move through explicit [`SessionState`]s:
+//! - `Active` sessions accept workflow operations
+//! - `Paused` sessions keep their state but reject operations until resumed
+//! - `Completed` and `Abandoned` sessions were ended by the agent
+//! - `Expired` sessions sat idle longer than the controller's idle timeout
+//!
+//! Ending a session produces a [`SessionSummary`]. The [`SessionStore`]
+//! keeps sessions and their actions in `<git common dir>/gdk/sessions.json`,
+//! so separate CLI invocations (and every worktree of the repository) see
+//! the same sessions. Saving merges per agent, so invocations running at the
+//! same time only overwrite the sessions they changed.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::agent::AgentWorkflowController;
+//! use gdk::core::GitWorkflowManager;
+//! use gdk::session::SessionStore;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let store = SessionStore::for_repo("./repo")?;
+//!     let mut controller = AgentWorkflowController::new(GitWorkflowManager::new("./repo")?);
+//!     let loaded = store.load()?;
+//!     controller.restore_sessions(loaded.clone());
+//!
+//!     controller.start_agent_session("agent-1").await?;
+//!     let summary = controller.complete_agent_session("agent-1")?;
+//!     println!("{} finished after {} actions", summary.agent_id, summary.total_actions);
+//!
+//!     store.save(&controller.session_records().changes_since(&loaded))?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::agent::{AgentAction, AgentSession};
+use crate::storage::{write_atomic, LockFile};
+use crate::validation_cache::git_common_dir;
+use crate::{GdkError, GdkResult, GdkResultExt};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Idle time after which a session expires unless configured otherwise
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Lifecycle state of an agent session
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    /// Accepting workflow operations
+    #[default]
+    Active,
+    /// Suspended by the agent; resumable
+    Paused,
+    /// Ended by the agent after finishing its work
+    Completed,
+    /// Ended by the agent without finishing its work
+    Abandoned,
+    /// Ended because the session was idle for too long
+    Expired,
+}
+
+impl SessionState {
+    /// Whether the session can still be used or resumed
+    pub fn is_live(&self) -> bool {
+        matches!(self, SessionState::Active | SessionState::Paused)
+    }
+
+    /// Lowercase name used in output and serialization
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionState::Active => "active",
+            SessionState::Paused => "paused",
+            SessionState::Completed => "completed",
+            SessionState::Abandoned => "abandoned",
+            SessionState::Expired => "expired",
+        }
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Outcome of a session, produced when it ends and on request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionSummary {
+    pub session_id: Uuid,
+    pub agent_id: String,
+    pub state: SessionState,
+    pub start_time: u64,
+    pub last_activity: u64,
+    /// When the session was completed, abandoned or expired
+    pub end_time: Option<u64>,
+    /// Last commit the agent produced or reverted to
+    pub final_commit: Option<String>,
+    /// Whether the latest convergence check reported convergence
+    pub converged: bool,
+    pub spiral_attempts: u32,
+    pub total_actions: usize,
+    pub successful_actions: usize,
+    pub best_attempt_score: Option<f64>,
+}
+
+/// Sessions and their action history, as persisted by [`SessionStore`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionRecords {
+    pub sessions: HashMap<String, AgentSession>,
+    pub action_history: Vec<AgentAction>,
+}
+
+impl SessionRecords {
+    /// Sessions that differ from `base` and actions missing from it
+    ///
+    /// Saving the changes since the records were loaded leaves sessions that
+    /// another invocation updated in the meantime alone.
+    pub fn changes_since(&self, base: &SessionRecords) -> SessionRecords {
+        let known: HashSet<Uuid> = base.action_history.iter().map(|action| action.action_id).collect();
+        SessionRecords {
+            sessions: self
+                .sessions
+                .iter()
+                .filter(|(agent_id, session)| base.sessions.get(*agent_id) != Some(*session))
+                .map(|(agent_id, session)| (agent_id.clone(), session.clone()))
+                .collect(),
+            action_history: self
+                .action_history
+                .iter()
+                .filter(|action| !known.contains(&action.action_id))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// JSON file holding the sessions of one repository
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    path: PathBuf,
+}
+
+impl SessionStore {
+    /// Store at an explicit path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Store shared by every worktree of the repository at `repo_path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if the repository cannot be opened
+    pub fn for_repo(repo_path: impl AsRef<Path>) -> GdkResult<Self> {
+        let repo = Repository::discover(repo_path.as_ref()).with_git_context("opening repository for session store")?;
+        Ok(Self::new(git_common_dir(&repo).join("gdk").join("sessions.json")))
+    }
+
+    /// File the sessions are stored in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the stored sessions; a missing file means no sessions
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed
+    pub fn load(&self) -> GdkResult<SessionRecords> {
+        let json = match fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(SessionRecords::default()),
+            Err(e) => {
+                return Err(GdkError::file_system_error(
+                    self.path.to_string_lossy(),
+                    "reading session store",
+                    e,
+                ))
+            }
+        };
+        serde_json::from_str(&json).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "session store".to_string(),
+            source: e,
+        })
+    }
+
+    /// Merge `records` into the stored sessions
+    ///
+    /// Each session in `records` replaces the stored session of the same
+    /// agent; other agents' sessions are kept. Actions not yet stored are
+    /// appended. The read-modify-write holds `sessions.json.lock`, so
+    /// concurrent invocations saving different agents both keep their
+    /// changes. Pass [`SessionRecords::changes_since`] the loaded records to
+    /// write back only what this invocation changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be locked, read or written
+    pub fn save(&self, records: &SessionRecords) -> GdkResult<()> {
+        let _lock = LockFile::beside(&self.path).acquire()?;
+
+        let mut stored = self.load()?;
+        stored
+            .sessions
+            .extend(records.sessions.iter().map(|(agent_id, session)| (agent_id.clone(), session.clone())));
+        let known: HashSet<Uuid> = stored.action_history.iter().map(|action| action.action_id).collect();
+        stored.action_history.extend(
+            records
+                .action_history
+                .iter()
+                .filter(|action| !known.contains(&action.action_id))
+                .cloned(),
+        );
+
+        let json = serde_json::to_vec_pretty(&stored).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "session store".to_string(),
+            source: e,
+        })?;
+        write_atomic(&self.path, &json, "session store")
+    }
+}
@@ -0,0 +1,140 @@
+//! Files shared by concurrent GDK processes
+//!
+//! Sessions, approvals and cached validation results are JSON files under
+//! `<git common dir>/gdk`, read and written by every CLI invocation and
+//! worktree of the repository:
+//! - [`write_atomic`] replaces a file so readers never observe it partially
+//!   written
+//! - [`LockFile`] excludes other writers for the length of a
+//!   read-modify-write, using the same exclusive `.lock` file scheme git
+//!   uses for its own files
+
+use crate::{GdkError, GdkResult, GdkResultExt};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long to wait for another process to release a lock file
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often to retry a lock file held by another process
+pub const LOCK_RETRY: Duration = Duration::from_millis(10);
+
+/// Replace the file at `path` with `contents`, creating its directory
+///
+/// The contents go to a unique temp file beside `path` that is then renamed
+/// over it. `what` names the file in error messages.
+///
+/// # Errors
+///
+/// Returns [`GdkError::FileSystemError`] if the directory, temp file or
+/// rename fails
+pub fn write_atomic(path: &Path, contents: &[u8], what: &str) -> GdkResult<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir).with_file_context(&dir.to_string_lossy(), &format!("creating {what} directory"))?;
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp_path = dir.join(format!(".{name}.{}.tmp", Uuid::new_v4()));
+    fs::write(&tmp_path, contents).with_file_context(&tmp_path.to_string_lossy(), &format!("writing {what}"))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        GdkError::file_system_error(path.to_string_lossy(), format!("committing {what}"), e)
+    })
+}
+
+/// Lock held by creating a file exclusively, shared between processes
+///
+/// The file is removed when the [`LockFileGuard`] is dropped. Every thread
+/// and process contends for the same file, so no in-process lock is needed
+/// alongside it.
+#[derive(Debug, Clone)]
+pub struct LockFile {
+    path: PathBuf,
+}
+
+/// Held while the lock file exists; removes it on drop
+#[derive(Debug)]
+pub struct LockFileGuard {
+    path: PathBuf,
+}
+
+impl LockFile {
+    /// Lock backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Lock guarding the file at `path`, backed by `<path>.lock`
+    pub fn beside(path: &Path) -> Self {
+        let mut lock_path = path.as_os_str().to_owned();
+        lock_path.push(".lock");
+        Self::new(lock_path)
+    }
+
+    /// File whose existence means the lock is held
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Take the lock if nobody holds it
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::FileSystemError`] if the lock file cannot be
+    /// created for any reason other than being held
+    pub fn try_acquire(&self) -> GdkResult<Option<LockFileGuard>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_file_context(&parent.to_string_lossy(), "creating lock directory")?;
+        }
+
+        match OpenOptions::new().write(true).create_new(true).open(&self.path) {
+            Ok(mut file) => {
+                // The pid only helps humans diagnose a stale lock
+                let _ = writeln!(file, "{}", std::process::id());
+                Ok(Some(LockFileGuard {
+                    path: self.path.clone(),
+                }))
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(None),
+            Err(e) => Err(GdkError::file_system_error(self.path.to_string_lossy(), "creating lock file", e)),
+        }
+    }
+
+    /// Wait until the lock is free, blocking the current thread
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::FileSystemError`] if the lock file cannot be
+    /// created, or stays held for [`LOCK_TIMEOUT`]
+    pub fn acquire(&self) -> GdkResult<LockFileGuard> {
+        let started = Instant::now();
+        loop {
+            if let Some(guard) = self.try_acquire()? {
+                return Ok(guard);
+            }
+            if started.elapsed() >= LOCK_TIMEOUT {
+                return Err(self.held_too_long());
+            }
+            std::thread::sleep(LOCK_RETRY);
+        }
+    }
+
+    /// Error for a lock that stayed held for [`LOCK_TIMEOUT`]
+    pub(crate) fn held_too_long(&self) -> GdkError {
+        GdkError::file_system_error(
+            self.path.to_string_lossy(),
+            "lock is held by another process; remove it if that process is gone",
+            std::io::Error::from(ErrorKind::TimedOut),
+        )
+    }
+}
+
+impl Drop for LockFileGuard {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            tracing::warn!("Failed to release lock {}: {}", self.path.display(), e);
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub struct ThreadManager {
     pub active_threads: HashMap<String, FileThread>,
     pub thread_history: Vec<ThreadSnapshot>,
     pub color_rules: ColorRules,
+    #[serde(default)]
+    pub convergence_rules: ThreadConvergenceRules,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,12 +50,96 @@ impl Default for ColorRules {
     }
 }
 
+/// Convergence criteria for a single file thread
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ThreadThreshold {
+    /// Every recent quality score must reach this value
+    pub min_quality: f64,
+    /// Recent quality scores must vary less than this
+    pub max_variance: f64,
+    /// Number of most recent scores considered
+    pub window: usize,
+}
+
+impl Default for ThreadThreshold {
+    fn default() -> Self {
+        Self {
+            min_quality: 0.8,
+            max_variance: 0.01,
+            window: 3,
+        }
+    }
+}
+
+impl ThreadThreshold {
+    /// Whether the most recent `window` scores (oldest first) are converged
+    ///
+    /// A thread with fewer than `window` scores has not converged yet.
+    pub fn is_converged(&self, scores: &[f64]) -> bool {
+        let window = self.window.max(1);
+        if scores.len() < window {
+            return false;
+        }
+
+        let recent = &scores[scores.len() - window..];
+        if recent.iter().any(|&score| score < self.min_quality) {
+            return false;
+        }
+
+        let mean = recent.iter().sum::<f64>() / recent.len() as f64;
+        let variance = recent
+            .iter()
+            .map(|&score| (score - mean).powi(2))
+            .sum::<f64>()
+            / recent.len() as f64;
+
+        variance < self.max_variance
+    }
+}
+
+/// Threshold applied to files under a path prefix
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PathThreshold {
+    /// Path prefix such as `src/` or `examples/`
+    pub prefix: String,
+    pub threshold: ThreadThreshold,
+}
+
+/// Per-path thread convergence thresholds
+///
+/// The longest matching prefix wins; files matching no prefix use `default`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ThreadConvergenceRules {
+    pub default: ThreadThreshold,
+    pub path_overrides: Vec<PathThreshold>,
+}
+
+impl ThreadConvergenceRules {
+    pub fn threshold_for(&self, file_path: &str) -> &ThreadThreshold {
+        self.path_overrides
+            .iter()
+            .filter(|rule| file_path.starts_with(&rule.prefix))
+            .max_by_key(|rule| rule.prefix.len())
+            .map(|rule| &rule.threshold)
+            .unwrap_or(&self.default)
+    }
+
+    pub fn with_path_threshold(mut self, prefix: &str, threshold: ThreadThreshold) -> Self {
+        self.path_overrides.push(PathThreshold {
+            prefix: prefix.to_string(),
+            threshold,
+        });
+        self
+    }
+}
+
 impl ThreadManager {
     pub fn new() -> Self {
         Self {
             active_threads: HashMap::new(),
             thread_history: Vec::new(),
             color_rules: ColorRules::default(),
+            convergence_rules: ThreadConvergenceRules::default(),
         }
     }
 
@@ -256,31 +342,18 @@ impl ThreadManager {
             return Ok(false);
         }
 
-        let recent_scores: Vec<f64> = thread
+        let scores: Vec<f64> = thread
             .history
             .iter()
-            .rev()
-            .take(window_size)
             .map(|state| state.metrics.quality_score)
             .collect();
 
-        // Check for convergence: all recent scores above threshold and trend is stable/improving
-        let threshold = 0.8;
-        let all_above_threshold = recent_scores.iter().all(|&score| score >= threshold);
-
-        if !all_above_threshold {
-            return Ok(false);
-        }
-
-        // Check trend stability (variance should be low)
-        let mean = recent_scores.iter().sum::<f64>() / recent_scores.len() as f64;
-        let variance = recent_scores
-            .iter()
-            .map(|&score| (score - mean).powi(2))
-            .sum::<f64>()
-            / recent_scores.len() as f64;
-
-        Ok(variance < 0.01) // Low variance indicates convergence
+        // All recent scores above the path's threshold with low variance
+        let threshold = ThreadThreshold {
+            window: window_size,
+            ..self.convergence_rules.threshold_for(file_path).clone()
+        };
+        Ok(threshold.is_converged(&scores))
     }
 
     pub fn get_thread_statistics(&self) -> ThreadStatistics {
@@ -28,11 +28,15 @@
 //! }
 //! ```
 
+use crate::validation_cache::{self, CacheKey, ValidationCache, ValidationCacheConfig};
 use crate::{GdkResult, GdkError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use tokio::process::Command;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::watch;
 
 /// Comprehensive validation suite for code quality assessment
 ///
@@ -48,12 +52,16 @@ use tokio::process::Command;
 /// - **Timeout Protection**: Prevent hanging on problematic code
 /// - **Fail Fast**: Stop execution on critical failures
 /// - **Detailed Output**: Capture stdout/stderr for analysis
+/// - **Result Caching**: Reuse results for identical trees across agents
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ValidationSuite {
     /// List of validators to execute
     pub validators: Vec<Validator>,
     /// Rules governing validation behavior
     pub validation_rules: ValidationRules,
+    /// Content-addressed result cache (disabled when `None`)
+    #[serde(default)]
+    pub cache: Option<ValidationCacheConfig>,
 }
 
 /// Individual validator configuration
@@ -64,7 +72,7 @@ pub struct ValidationSuite {
 /// # Example
 ///
 /// ```rust
-/// use gdk::validation::Validator;
+/// use gdk::validation::{OutputParser, RetryPolicy, Validator};
 ///
 /// let clippy = Validator {
 ///     name: "cargo_clippy".to_string(),
@@ -74,6 +82,9 @@ pub struct ValidationSuite {
 ///     timeout_seconds: 120,
 ///     weight: 0.25,
 ///     is_required: false,
+///     parser: OutputParser::CargoClippy,
+///     retry: RetryPolicy::default(),
+///     version_command: Some(vec!["cargo".to_string(), "clippy".to_string(), "--version".to_string()]),
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -92,6 +103,132 @@ pub struct Validator {
     pub weight: f64,
     /// Whether this validator must pass for overall success
     pub is_required: bool,
+    /// Parser used to score the validator output
+    #[serde(default)]
+    pub parser: OutputParser,
+    /// Retry policy for transient failures (external kills, allocation
+    /// failures, lock contention and, if enabled, timeouts)
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Command printing the version of the tool that produces the result,
+    /// used to key cached results; `None` runs `<command> --version`
+    #[serde(default)]
+    pub version_command: Option<Vec<String>>,
+}
+
+impl Validator {
+    /// Command whose output identifies the tool behind this validator
+    pub fn version_probe(&self) -> Vec<String> {
+        self.version_command
+            .clone()
+            .unwrap_or_else(|| vec![self.command.clone(), "--version".to_string()])
+    }
+}
+
+/// Output parser used to turn validator output into a score
+///
+/// Each parser understands the summary format of one tool. [`OutputParser::Auto`]
+/// infers the parser from the validator name, which keeps hand-written
+/// validators named after the Rust presets (e.g. `cargo_clippy`) working.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum OutputParser {
+    /// Infer the parser from the validator name
+    #[default]
+    Auto,
+    /// Binary pass/fail on exit code
+    ExitCode,
+    /// `cargo check` compilation
+    CargoCheck,
+    /// `cargo clippy` warnings and errors
+    CargoClippy,
+    /// `cargo test` result summary
+    CargoTest,
+    /// `cargo fmt --check` formatting
+    CargoFmt,
+    /// `cargo audit` vulnerability report
+    CargoAudit,
+    /// `ruff check` lint findings
+    Ruff,
+    /// `mypy` type errors
+    Mypy,
+    /// `pytest` pass/fail summary
+    Pytest,
+    /// `eslint` problem summary
+    Eslint,
+    /// `tsc --noEmit` type errors
+    Tsc,
+    /// Node test runners (jest, vitest, `node --test`)
+    NodeTest,
+    /// `go vet` findings
+    GoVet,
+    /// `go build` compilation
+    GoBuild,
+    /// `go test` package results
+    GoTest,
+}
+
+impl OutputParser {
+    /// Resolve [`OutputParser::Auto`] from the validator name
+    ///
+    /// Names scoped to a project root (e.g. `services/api:pytest`) are
+    /// matched on the part after the last `:`.
+    pub fn resolve(self, validator_name: &str) -> OutputParser {
+        if self != OutputParser::Auto {
+            return self;
+        }
+
+        let base_name = validator_name.rsplit(':').next().unwrap_or(validator_name);
+        match base_name {
+            "cargo_check" => OutputParser::CargoCheck,
+            "cargo_clippy" => OutputParser::CargoClippy,
+            "cargo_test" => OutputParser::CargoTest,
+            "cargo_fmt" => OutputParser::CargoFmt,
+            "cargo_audit" => OutputParser::CargoAudit,
+            "ruff" => OutputParser::Ruff,
+            "mypy" => OutputParser::Mypy,
+            "pytest" => OutputParser::Pytest,
+            "eslint" => OutputParser::Eslint,
+            "tsc" => OutputParser::Tsc,
+            "node_test" => OutputParser::NodeTest,
+            "go_vet" => OutputParser::GoVet,
+            "go_build" => OutputParser::GoBuild,
+            "go_test" => OutputParser::GoTest,
+            _ => OutputParser::ExitCode,
+        }
+    }
+}
+
+/// Language ecosystem detected at a project root
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ProjectKind {
+    /// Cargo project (`Cargo.toml`)
+    Rust,
+    /// Python project (`pyproject.toml`, `setup.py`, `setup.cfg`, `requirements.txt`)
+    Python,
+    /// Node/TypeScript project (`package.json`)
+    Node,
+    /// Go module (`go.mod`)
+    Go,
+}
+
+impl ProjectKind {
+    fn markers(self) -> &'static [&'static str] {
+        match self {
+            ProjectKind::Rust => &["Cargo.toml"],
+            ProjectKind::Python => &["pyproject.toml", "setup.py", "setup.cfg", "requirements.txt"],
+            ProjectKind::Node => &["package.json"],
+            ProjectKind::Go => &["go.mod"],
+        }
+    }
+}
+
+/// A project root detected inside a repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectRoot {
+    /// Path relative to the repository root (`.` for the root itself)
+    pub relative_path: String,
+    /// Ecosystem of the project
+    pub kind: ProjectKind,
 }
 
 /// Rules governing validation suite behavior
@@ -144,10 +281,274 @@ pub struct ValidatorResult {
     pub output: String,
     /// Standard error output from validator execution
     pub error_output: String,
-    /// Execution time in milliseconds
+    /// Execution time in milliseconds, summed over attempts (backoff excluded)
     pub execution_time_ms: u64,
     /// Process exit code
     pub exit_code: i32,
+    /// Whether this result was served from the validation cache
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// Every execution attempt, including transient failures that were retried
+    #[serde(default)]
+    pub attempts: Vec<ValidatorAttempt>,
+}
+
+/// Record of a single validator execution attempt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidatorAttempt {
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// Process exit code (-1 if killed or timed out)
+    pub exit_code: i32,
+    /// Signal that terminated the process, if any
+    pub signal: Option<i32>,
+    /// Whether the attempt exceeded the validator timeout
+    pub timed_out: bool,
+    /// Execution time of this attempt in milliseconds
+    pub execution_time_ms: u64,
+    /// Classification of the failure (`None` if the attempt passed)
+    pub failure: Option<FailureClass>,
+    /// Delay before the next attempt (0 if no retry followed)
+    pub backoff_ms: u64,
+}
+
+/// Retry policy for transient validator failures
+///
+/// Only failures classified as [`FailureClass::Transient`] are retried; real
+/// failures (lint errors, failing tests) are reported on the first attempt.
+/// Timeouts are only retried when `retry_timeouts` is set, since each retry
+/// costs another full timeout.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts including the first (1 disables retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds
+    pub initial_backoff_ms: u64,
+    /// Multiplier applied to the delay after each retry
+    pub backoff_multiplier: f64,
+    /// Upper bound for the delay in milliseconds
+    pub max_backoff_ms: u64,
+    /// Whether timeouts count as transient failures
+    #[serde(default)]
+    pub retry_timeouts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1_000,
+            backoff_multiplier: 2.0,
+            max_backoff_ms: 30_000,
+            retry_timeouts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Whether `failure` should be retried under this policy
+    pub fn retries(&self, failure: &FailureClass) -> bool {
+        match failure {
+            FailureClass::Transient(TransientReason::Timeout) => self.retry_timeouts,
+            other => other.is_transient(),
+        }
+    }
+
+    /// Delay after the given (1-based) failed attempt
+    pub fn backoff_ms(&self, attempt: u32) -> u64 {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let delay = self.initial_backoff_ms as f64 * self.backoff_multiplier.powi(exponent);
+        (delay as u64).min(self.max_backoff_ms)
+    }
+}
+
+/// Classification of a failed validator attempt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FailureClass {
+    /// Infrastructure failure that is likely to succeed on retry
+    Transient(TransientReason),
+    /// Genuine failure reported by the tool
+    Permanent,
+}
+
+/// Why a failure was considered transient
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TransientReason {
+    /// Process exceeded its timeout
+    Timeout,
+    /// Process failed to allocate memory
+    OutOfMemory,
+    /// Process was killed by SIGKILL or SIGTERM from outside GDK, such as
+    /// the OOM killer
+    Signal(i32),
+    /// Build tool gave up waiting for a file or package cache lock
+    LockContention,
+}
+
+impl FailureClass {
+    /// Whether the failure should be retried
+    pub fn is_transient(&self) -> bool {
+        matches!(self, FailureClass::Transient(_))
+    }
+}
+
+impl std::fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailureClass::Transient(TransientReason::Timeout) => write!(f, "timeout"),
+            FailureClass::Transient(TransientReason::OutOfMemory) => write!(f, "out of memory"),
+            FailureClass::Transient(TransientReason::Signal(signal)) => {
+                write!(f, "terminated by signal {signal}")
+            }
+            FailureClass::Transient(TransientReason::LockContention) => {
+                write!(f, "lock contention")
+            }
+            FailureClass::Permanent => write!(f, "permanent failure"),
+        }
+    }
+}
+
+/// Outcome of a single validator process run
+#[derive(Debug, Clone)]
+struct AttemptRun {
+    success: bool,
+    exit_code: i32,
+    signal: Option<i32>,
+    timed_out: bool,
+    stdout: String,
+    stderr: String,
+    execution_time_ms: u64,
+}
+
+/// Separate transient infrastructure failures from real validator failures
+///
+/// Returns `None` for a successful attempt. Anything not recognised as
+/// transient is permanent, so a genuine failure is never retried:
+/// - Only SIGKILL and SIGTERM are transient, since GDK itself only kills on
+///   timeout; they come from the OOM killer or the machine's operator. A
+///   crash (SIGSEGV, SIGABRT) is the validated code's fault.
+/// - Out of memory needs an allocator or OS allocation failure message, not
+///   just the words, which test names and assertions also contain
+/// - Lock contention needs the lock message to be the last thing the tool
+///   printed. Cargo prints "Blocking waiting for file lock" before building
+///   and testing as usual, and those failures are real.
+fn classify_failure(run: &AttemptRun) -> Option<FailureClass> {
+    const LOCK_MARKERS: &[&str] = &[
+        "Blocking waiting for file lock",
+        "could not acquire package cache lock",
+        "Resource temporarily unavailable",
+    ];
+    const SIGKILL: i32 = 9;
+    const SIGTERM: i32 = 15;
+
+    if run.success {
+        return None;
+    }
+    if run.timed_out {
+        return Some(FailureClass::Transient(TransientReason::Timeout));
+    }
+    if let Some(signal @ (SIGKILL | SIGTERM)) = run.signal {
+        return Some(FailureClass::Transient(TransientReason::Signal(signal)));
+    }
+
+    let allocation_failed = run.stderr.lines().any(|line| {
+        let line = line.trim();
+        (line.starts_with("memory allocation of") && line.ends_with("failed"))
+            || line.contains("Cannot allocate memory (os error 12)")
+    });
+    if allocation_failed {
+        return Some(FailureClass::Transient(TransientReason::OutOfMemory));
+    }
+
+    let last_line = run.stderr.lines().rev().map(str::trim).find(|line| !line.is_empty());
+    let still_locked = run.stdout.trim().is_empty()
+        && last_line.is_some_and(|line| LOCK_MARKERS.iter().any(|m| line.contains(m)));
+    if still_locked {
+        return Some(FailureClass::Transient(TransientReason::LockContention));
+    }
+
+    Some(FailureClass::Permanent)
+}
+
+#[cfg(unix)]
+fn exit_signal(status: std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Progress event emitted while a validation suite runs
+///
+/// Subscribe by passing a channel to [`ValidationSuite::validate_with_progress`].
+/// Events for different validators interleave when validators run in parallel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ValidationEvent {
+    /// Validator process is about to run (or be looked up in the cache)
+    Started {
+        validator: String,
+    },
+    /// A line of output was produced by a running validator
+    OutputLine {
+        validator: String,
+        stream: OutputStream,
+        line: String,
+    },
+    /// Validator completed and was scored
+    Finished {
+        validator: String,
+        passed: bool,
+        score: f64,
+        execution_time_ms: u64,
+        cache_hit: bool,
+    },
+    /// Validator was not run (e.g. fail-fast stopped the suite)
+    Skipped {
+        validator: String,
+        reason: String,
+    },
+    /// Validator exceeded its timeout and was killed
+    TimedOut {
+        validator: String,
+        timeout_seconds: u64,
+    },
+    /// Validator failed transiently and will be run again after a backoff
+    Retrying {
+        validator: String,
+        attempt: u32,
+        reason: String,
+        backoff_ms: u64,
+    },
+}
+
+/// Output stream a [`ValidationEvent::OutputLine`] came from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Channel end that receives [`ValidationEvent`]s
+pub type ValidationEventSender = UnboundedSender<ValidationEvent>;
+
+/// Send an event if anyone is subscribed; a dropped receiver is not an error
+fn emit(events: Option<&ValidationEventSender>, event: ValidationEvent) {
+    if let Some(sender) = events {
+        let _ = sender.send(event);
+    }
 }
 
 impl Default for ValidationRules {
@@ -166,6 +567,7 @@ impl ValidationSuite {
         Self {
             validators: Vec::new(),
             validation_rules: ValidationRules::default(),
+            cache: None,
         }
     }
 
@@ -181,6 +583,9 @@ impl ValidationSuite {
             timeout_seconds: 60,
             weight: 0.25,
             is_required: true,
+            parser: OutputParser::CargoCheck,
+            retry: RetryPolicy::default(),
+            version_command: None,
         });
 
         // Cargo clippy (linting)
@@ -197,6 +602,9 @@ impl ValidationSuite {
             timeout_seconds: 120,
             weight: 0.25,
             is_required: false,
+            parser: OutputParser::CargoClippy,
+            retry: RetryPolicy::default(),
+            version_command: Some(vec!["cargo".to_string(), "clippy".to_string(), "--version".to_string()]),
         });
 
         // Cargo test
@@ -212,6 +620,9 @@ impl ValidationSuite {
             timeout_seconds: 300,
             weight: 0.3,
             is_required: true,
+            parser: OutputParser::CargoTest,
+            retry: RetryPolicy::default(),
+            version_command: None,
         });
 
         // Cargo fmt check
@@ -223,6 +634,9 @@ impl ValidationSuite {
             timeout_seconds: 30,
             weight: 0.1,
             is_required: false,
+            parser: OutputParser::CargoFmt,
+            retry: RetryPolicy::default(),
+            version_command: Some(vec!["cargo".to_string(), "fmt".to_string(), "--version".to_string()]),
         });
 
         // Security audit (if cargo-audit is available)
@@ -234,11 +648,233 @@ impl ValidationSuite {
             timeout_seconds: 60,
             weight: 0.1,
             is_required: false,
+            parser: OutputParser::CargoAudit,
+            retry: RetryPolicy::default(),
+            version_command: Some(vec!["cargo".to_string(), "audit".to_string(), "--version".to_string()]),
         });
 
         suite
     }
 
+    /// Python preset: ruff lint, mypy type checking and pytest
+    pub fn python_default(repo_path: &str) -> Self {
+        let mut suite = Self::new();
+
+        // Ruff (linting)
+        suite.add_validator(Validator {
+            name: "ruff".to_string(),
+            command: "ruff".to_string(),
+            args: vec!["check".to_string(), ".".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 60,
+            weight: 0.25,
+            is_required: false,
+            parser: OutputParser::Ruff,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        // Mypy (type checking)
+        suite.add_validator(Validator {
+            name: "mypy".to_string(),
+            command: "mypy".to_string(),
+            args: vec![".".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 120,
+            weight: 0.3,
+            is_required: true,
+            parser: OutputParser::Mypy,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        // Pytest
+        suite.add_validator(Validator {
+            name: "pytest".to_string(),
+            command: "pytest".to_string(),
+            args: vec!["-q".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 300,
+            weight: 0.45,
+            is_required: true,
+            parser: OutputParser::Pytest,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        suite
+    }
+
+    /// Node/TypeScript preset: eslint, tsc and the package test script
+    pub fn node_default(repo_path: &str) -> Self {
+        let mut suite = Self::new();
+
+        // ESLint (linting)
+        suite.add_validator(Validator {
+            name: "eslint".to_string(),
+            command: "npx".to_string(),
+            args: vec!["--no-install".to_string(), "eslint".to_string(), ".".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 120,
+            weight: 0.25,
+            is_required: false,
+            parser: OutputParser::Eslint,
+            retry: RetryPolicy::default(),
+            version_command: Some(vec![
+                "npx".to_string(),
+                "--no-install".to_string(),
+                "eslint".to_string(),
+                "--version".to_string(),
+            ]),
+        });
+
+        // TypeScript compiler (type checking)
+        suite.add_validator(Validator {
+            name: "tsc".to_string(),
+            command: "npx".to_string(),
+            args: vec!["--no-install".to_string(), "tsc".to_string(), "--noEmit".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 120,
+            weight: 0.3,
+            is_required: true,
+            parser: OutputParser::Tsc,
+            retry: RetryPolicy::default(),
+            version_command: Some(vec![
+                "npx".to_string(),
+                "--no-install".to_string(),
+                "tsc".to_string(),
+                "--version".to_string(),
+            ]),
+        });
+
+        // Test runner via the package's test script
+        suite.add_validator(Validator {
+            name: "node_test".to_string(),
+            command: "npm".to_string(),
+            args: vec!["test".to_string(), "--silent".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 300,
+            weight: 0.45,
+            is_required: true,
+            parser: OutputParser::NodeTest,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        suite
+    }
+
+    /// Go preset: go vet, go build and go test
+    pub fn go_default(repo_path: &str) -> Self {
+        let mut suite = Self::new();
+
+        // Go vet (static analysis)
+        suite.add_validator(Validator {
+            name: "go_vet".to_string(),
+            command: "go".to_string(),
+            args: vec!["vet".to_string(), "./...".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 120,
+            weight: 0.25,
+            is_required: false,
+            parser: OutputParser::GoVet,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        // Go build (compilation)
+        suite.add_validator(Validator {
+            name: "go_build".to_string(),
+            command: "go".to_string(),
+            args: vec!["build".to_string(), "./...".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 120,
+            weight: 0.3,
+            is_required: true,
+            parser: OutputParser::GoBuild,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        // Go test
+        suite.add_validator(Validator {
+            name: "go_test".to_string(),
+            command: "go".to_string(),
+            args: vec!["test".to_string(), "./...".to_string()],
+            working_dir: Some(repo_path.to_string()),
+            timeout_seconds: 300,
+            weight: 0.45,
+            is_required: true,
+            parser: OutputParser::GoTest,
+            retry: RetryPolicy::default(),
+            version_command: None,
+        });
+
+        suite
+    }
+
+    /// Preset for a single ecosystem rooted at `project_path`
+    pub fn preset_for(kind: ProjectKind, project_path: &str) -> Self {
+        match kind {
+            ProjectKind::Rust => Self::rust_default(project_path),
+            ProjectKind::Python => Self::python_default(project_path),
+            ProjectKind::Node => Self::node_default(project_path),
+            ProjectKind::Go => Self::go_default(project_path),
+        }
+    }
+
+    /// Composite preset for polyglot monorepos
+    ///
+    /// Detects project roots anywhere under `repo_path` (see
+    /// [`detect_project_roots`]) and adds the matching preset for each one,
+    /// with every validator scoped to its own root. Validator names are
+    /// prefixed with the root's relative path, e.g. `services/api:pytest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::FileSystemError`] if the repository cannot be walked.
+    pub fn polyglot_default(repo_path: &str) -> GdkResult<Self> {
+        let mut suite = Self::new();
+
+        for root in detect_project_roots(repo_path)? {
+            let project_path = if root.relative_path == "." {
+                repo_path.to_string()
+            } else {
+                std::path::Path::new(repo_path)
+                    .join(&root.relative_path)
+                    .to_string_lossy()
+                    .to_string()
+            };
+
+            for mut validator in Self::preset_for(root.kind, &project_path).validators {
+                validator.name = format!("{}:{}", root.relative_path, validator.name);
+                suite.add_validator(validator);
+            }
+        }
+
+        Ok(suite)
+    }
+
+    /// Build a preset suite by name: `rust`, `python`, `node`, `go` or `polyglot`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::ConfigurationError`] for unknown preset names.
+    pub fn from_preset(preset: &str, repo_path: &str) -> GdkResult<Self> {
+        match preset {
+            "rust" => Ok(Self::rust_default(repo_path)),
+            "python" => Ok(Self::python_default(repo_path)),
+            "node" => Ok(Self::node_default(repo_path)),
+            "go" => Ok(Self::go_default(repo_path)),
+            "polyglot" => Self::polyglot_default(repo_path),
+            other => Err(GdkError::configuration_error(
+                "preset",
+                format!("Unsupported preset: {other}"),
+                Some("Use 'rust', 'python', 'node', 'go' or 'polyglot'".to_string()),
+            )),
+        }
+    }
+
     pub fn add_validator(&mut self, validator: Validator) {
         self.validators.push(validator);
     }
@@ -247,13 +883,94 @@ impl ValidationSuite {
         self.validation_rules = rules;
     }
 
+    /// Enable the content-addressed result cache for this suite
+    pub fn set_cache(&mut self, config: ValidationCacheConfig) {
+        self.cache = Some(config);
+    }
+
+    /// Open the configured cache and compute a key for every validator
+    ///
+    /// Cache failures never fail validation: if the cache cannot be opened or
+    /// the tree cannot be hashed, validators simply run uncached.
+    async fn prepare_cache(&self, repo_path: &str) -> Option<(ValidationCache, HashMap<String, CacheKey>)> {
+        let config = self.cache.as_ref()?;
+
+        let cache = match ValidationCache::open(config, repo_path) {
+            Ok(cache) => cache,
+            Err(e) => {
+                tracing::warn!("Validation cache unavailable: {}", e);
+                return None;
+            }
+        };
+
+        match validation_cache::cache_keys(&self.validators, repo_path).await {
+            Ok(keys) => Some((cache, keys)),
+            Err(e) => {
+                tracing::warn!("Failed to compute validation cache keys: {}", e);
+                None
+            }
+        }
+    }
+
     pub async fn validate(&self, repo_path: &str) -> GdkResult<ValidationResult> {
+        self.run_validation(repo_path, None).await
+    }
+
+    /// Run the suite while streaming [`ValidationEvent`]s to `events`
+    ///
+    /// The returned [`ValidationResult`] is identical to [`Self::validate`];
+    /// events are purely informational and sending never blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use gdk::validation::{ValidationEvent, ValidationSuite};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> gdk::GdkResult<()> {
+    ///     let suite = ValidationSuite::rust_default(".");
+    ///     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    ///
+    ///     let printer = tokio::spawn(async move {
+    ///         while let Some(event) = rx.recv().await {
+    ///             if let ValidationEvent::Finished { validator, score, .. } = event {
+    ///                 println!("{validator}: {score:.2}");
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     let result = suite.validate_with_progress(".", tx).await?;
+    ///     printer.await?;
+    ///     println!("Overall: {:.3}", result.overall_score);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn validate_with_progress(
+        &self,
+        repo_path: &str,
+        events: ValidationEventSender,
+    ) -> GdkResult<ValidationResult> {
+        self.run_validation(repo_path, Some(events)).await
+    }
+
+    async fn run_validation(
+        &self,
+        repo_path: &str,
+        events: Option<ValidationEventSender>,
+    ) -> GdkResult<ValidationResult> {
         let start_time = std::time::Instant::now();
         let mut validator_results = HashMap::new();
         let mut total_weighted_score = 0.0;
         let mut total_weight = 0.0;
         let mut required_failed = false;
 
+        let cache = self.prepare_cache(repo_path).await;
+        let cache_entry = |name: &str| {
+            cache
+                .as_ref()
+                .and_then(|(cache, keys)| keys.get(name).map(|key| (cache.clone(), key.clone())))
+        };
+
         if self.validation_rules.parallel_execution {
             // Execute validators in parallel
             let mut handles = Vec::new();
@@ -261,15 +978,24 @@ impl ValidationSuite {
             for validator in &self.validators {
                 let validator_clone = validator.clone();
                 let repo_path_clone = repo_path.to_string();
+                let cache_clone = cache_entry(&validator.name);
+                let events_clone = events.clone();
 
                 let handle = tokio::spawn(async move {
-                    Self::execute_validator(&validator_clone, &repo_path_clone).await
+                    Self::execute_validator_cached(
+                        &validator_clone,
+                        &repo_path_clone,
+                        cache_clone,
+                        events_clone.as_ref(),
+                    )
+                    .await
                 });
 
                 handles.push((validator.name.clone(), handle));
             }
 
-            for (name, handle) in handles {
+            let mut handles = handles.into_iter();
+            while let Some((name, handle)) = handles.next() {
                 let result = handle.await??;
 
                 if self.validation_rules.fail_fast
@@ -301,13 +1027,27 @@ impl ValidationSuite {
                 validator_results.insert(name, result);
 
                 if required_failed && self.validation_rules.fail_fast {
+                    for (skipped, handle) in handles.by_ref() {
+                        handle.abort();
+                        emit(events.as_ref(), ValidationEvent::Skipped {
+                            validator: skipped,
+                            reason: "fail_fast: a required validator failed".to_string(),
+                        });
+                    }
                     break;
                 }
             }
         } else {
             // Execute validators sequentially
-            for validator in &self.validators {
-                let result = Self::execute_validator(validator, repo_path).await?;
+            let mut validators = self.validators.iter();
+            while let Some(validator) = validators.next() {
+                let result = Self::execute_validator_cached(
+                    validator,
+                    repo_path,
+                    cache_entry(&validator.name),
+                    events.as_ref(),
+                )
+                .await?;
 
                 if self.validation_rules.fail_fast && !result.passed && validator.is_required {
                     required_failed = true;
@@ -319,6 +1059,12 @@ impl ValidationSuite {
                 validator_results.insert(validator.name.clone(), result);
 
                 if required_failed && self.validation_rules.fail_fast {
+                    for skipped in validators.by_ref() {
+                        emit(events.as_ref(), ValidationEvent::Skipped {
+                            validator: skipped.name.clone(),
+                            reason: "fail_fast: a required validator failed".to_string(),
+                        });
+                    }
                     break;
                 }
             }
@@ -355,7 +1101,131 @@ impl ValidationSuite {
         })
     }
 
-    async fn execute_validator(validator: &Validator, repo_path: &str) -> GdkResult<ValidatorResult> {
+    async fn execute_validator_cached(
+        validator: &Validator,
+        repo_path: &str,
+        cache: Option<(ValidationCache, CacheKey)>,
+        events: Option<&ValidationEventSender>,
+    ) -> GdkResult<ValidatorResult> {
+        emit(events, ValidationEvent::Started {
+            validator: validator.name.clone(),
+        });
+
+        let cached = cache.as_ref().and_then(|(cache, key)| cache.get(key));
+        let result = match cached {
+            Some(result) => {
+                tracing::debug!("Validation cache hit for {}", validator.name);
+                result
+            }
+            None => {
+                let result = Self::execute_validator(validator, repo_path, events).await?;
+                // Transient failures say nothing about the tree, so never cache them
+                let transient = result
+                    .attempts
+                    .last()
+                    .and_then(|a| a.failure.as_ref())
+                    .is_some_and(FailureClass::is_transient);
+                if let Some((cache, key)) = cache.as_ref().filter(|_| !transient) {
+                    if let Err(e) = cache.put(key, &result) {
+                        tracing::warn!(
+                            "Failed to store validation cache entry for {}: {}",
+                            validator.name,
+                            e
+                        );
+                    }
+                }
+                result
+            }
+        };
+
+        emit(events, ValidationEvent::Finished {
+            validator: validator.name.clone(),
+            passed: result.passed,
+            score: result.score,
+            execution_time_ms: result.execution_time_ms,
+            cache_hit: result.cache_hit,
+        });
+
+        Ok(result)
+    }
+
+    async fn execute_validator(
+        validator: &Validator,
+        repo_path: &str,
+        events: Option<&ValidationEventSender>,
+    ) -> GdkResult<ValidatorResult> {
+        let max_attempts = validator.retry.max_attempts.max(1);
+        let mut attempts = Vec::new();
+
+        let mut attempt = 1;
+        let run = loop {
+            let run = Self::run_attempt(validator, repo_path, events).await?;
+            let failure = classify_failure(&run);
+            let retry = attempt < max_attempts
+                && failure.as_ref().is_some_and(|f| validator.retry.retries(f));
+            let backoff_ms = if retry { validator.retry.backoff_ms(attempt) } else { 0 };
+
+            attempts.push(ValidatorAttempt {
+                attempt,
+                exit_code: run.exit_code,
+                signal: run.signal,
+                timed_out: run.timed_out,
+                execution_time_ms: run.execution_time_ms,
+                failure: failure.clone(),
+                backoff_ms,
+            });
+            if !retry {
+                break run;
+            }
+
+            let reason = failure.map(|f| f.to_string()).unwrap_or_default();
+            tracing::warn!(
+                "Validator {} attempt {}/{} failed transiently ({}), retrying in {}ms",
+                validator.name,
+                attempt,
+                max_attempts,
+                reason,
+                backoff_ms
+            );
+            emit(events, ValidationEvent::Retrying {
+                validator: validator.name.clone(),
+                attempt: attempt + 1,
+                reason,
+                backoff_ms,
+            });
+            tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+            attempt += 1;
+        };
+
+        let passed = run.success;
+
+        // Calculate score based on exit code and output
+        let parser = validator.parser.resolve(&validator.name);
+        let score = if run.timed_out {
+            0.0
+        } else {
+            Self::calculate_validator_score(parser, run.exit_code, &run.stdout, &run.stderr)
+        };
+
+        Ok(ValidatorResult {
+            name: validator.name.clone(),
+            passed,
+            score,
+            output: run.stdout,
+            error_output: run.stderr,
+            execution_time_ms: attempts.iter().map(|a| a.execution_time_ms).sum(),
+            exit_code: run.exit_code,
+            cache_hit: false,
+            attempts,
+        })
+    }
+
+    /// Run a validator process once, killing it if it exceeds its timeout
+    async fn run_attempt(
+        validator: &Validator,
+        repo_path: &str,
+        events: Option<&ValidationEventSender>,
+    ) -> GdkResult<AttemptRun> {
         let start_time = std::time::Instant::now();
 
         let default_dir = repo_path.to_string();
@@ -365,10 +1235,12 @@ impl ValidationSuite {
         command
             .args(&validator.args)
             .current_dir(working_dir)
+            .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
-        let child = command
+        let mut child = command
             .spawn()
             .map_err(|e| GdkError::validation_error(
                 "spawn_error",
@@ -376,41 +1248,68 @@ impl ValidationSuite {
                 e.to_string(),
             ))?;
 
+        // Read both pipes line by line so output can be streamed as it arrives
+        let (stop_readers, stop) = watch::channel(false);
+        let stdout_reader = child.stdout.take().map(|pipe| {
+            tokio::spawn(collect_lines(pipe, validator.name.clone(), OutputStream::Stdout, events.cloned(), stop.clone()))
+        });
+        let stderr_reader = child.stderr.take().map(|pipe| {
+            tokio::spawn(collect_lines(pipe, validator.name.clone(), OutputStream::Stderr, events.cloned(), stop))
+        });
+
         let timeout_duration = std::time::Duration::from_secs(validator.timeout_seconds);
-        let output = tokio::time::timeout(timeout_duration, child.wait_with_output())
-            .await
-            .map_err(|_| GdkError::validation_error(
-                "timeout",
-                format!("Validator {} timed out after {} seconds", validator.name, validator.timeout_seconds),
-                "Command execution exceeded timeout limit".to_string(),
-            ))?
-            .map_err(|e| GdkError::validation_error(
+        let status = match tokio::time::timeout(timeout_duration, child.wait()).await {
+            Ok(status) => Some(status.map_err(|e| GdkError::validation_error(
                 "execution_failed",
                 format!("Validator {} execution failed", validator.name),
                 e.to_string(),
-            ))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let exit_code = output.status.code().unwrap_or(-1);
-        let passed = output.status.success();
+            ))?),
+            Err(_) => {
+                let _ = child.kill().await;
+                emit(events, ValidationEvent::TimedOut {
+                    validator: validator.name.clone(),
+                    timeout_seconds: validator.timeout_seconds,
+                });
+                None
+            }
+        };
 
-        // Calculate score based on exit code and output
-        let score = Self::calculate_validator_score(&validator.name, exit_code, &stdout, &stderr);
+        // Grandchildren (a test binary under cargo, anything under `sh -c`)
+        // can hold the pipes open after the validator exits or is killed, so
+        // only wait a moment for them to close
+        let readers = async {
+            let stdout = match stdout_reader {
+                Some(handle) => handle.await?,
+                None => String::new(),
+            };
+            let stderr = match stderr_reader {
+                Some(handle) => handle.await?,
+                None => String::new(),
+            };
+            GdkResult::Ok((stdout, stderr))
+        };
+        tokio::pin!(readers);
+        let (stdout, stderr) = tokio::select! {
+            output = &mut readers => output?,
+            _ = tokio::time::sleep(OUTPUT_DRAIN_GRACE) => {
+                let _ = stop_readers.send(true);
+                readers.await?
+            }
+        };
 
-        Ok(ValidatorResult {
-            name: validator.name.clone(),
-            passed,
-            score,
-            output: stdout,
-            error_output: stderr,
+        Ok(AttemptRun {
+            success: status.is_some_and(|s| s.success()),
+            exit_code: status.and_then(|s| s.code()).unwrap_or(-1),
+            signal: status.and_then(exit_signal),
+            timed_out: status.is_none(),
+            stdout,
+            stderr,
             execution_time_ms: start_time.elapsed().as_millis() as u64,
-            exit_code,
         })
     }
 
     fn calculate_validator_score(
-        validator_name: &str,
+        parser: OutputParser,
         exit_code: i32,
         stdout: &str,
         stderr: &str,
@@ -420,8 +1319,8 @@ impl ValidationSuite {
         }
 
         // Special handling for different validators
-        match validator_name {
-            "cargo_clippy" => {
+        match parser {
+            OutputParser::CargoClippy => {
                 // Count warnings and errors
                 let warning_count = stderr.matches("warning:").count();
                 let error_count = stderr.matches("error:").count();
@@ -435,7 +1334,7 @@ impl ValidationSuite {
                     (1.0 - (warning_count as f64 * 0.1)).max(0.0)
                 }
             }
-            "cargo_test" => {
+            OutputParser::CargoTest => {
                 // Parse test results
                 if let Some(line) = stdout.lines().find(|l| l.contains("test result:")) {
                     if let Some(passed_part) = line.split_whitespace().nth(2) {
@@ -450,7 +1349,7 @@ impl ValidationSuite {
                 }
                 0.0
             }
-            "cargo_audit" => {
+            OutputParser::CargoAudit => {
                 // Security audit scoring
                 if stderr.contains("vulnerabilities found") {
                     let vuln_count = stderr.matches("vulnerability").count();
@@ -459,7 +1358,89 @@ impl ValidationSuite {
                     1.0
                 }
             }
-            _ => {
+            OutputParser::Ruff => {
+                // "Found 3 errors."
+                let findings = summary_count(stdout, "Found", "error").unwrap_or(1);
+                (1.0 - (findings as f64 * 0.05)).max(0.0)
+            }
+            OutputParser::Mypy => {
+                // "Found 5 errors in 2 files (checked 10 source files)"
+                let errors = summary_count(stdout, "Found", "error").unwrap_or(1);
+                (1.0 - (errors as f64 * 0.1)).max(0.0)
+            }
+            OutputParser::Pytest => {
+                // "=== 2 failed, 8 passed, 1 error in 0.52s ==="
+                let summary = stdout
+                    .lines()
+                    .rev()
+                    .find(|l| l.contains(" passed") || l.contains(" failed") || l.contains(" error"));
+                summary
+                    .map(|line| {
+                        pass_ratio(
+                            count_labelled(line, "passed"),
+                            count_labelled(line, "failed") + count_labelled(line, "error"),
+                        )
+                    })
+                    .unwrap_or(0.0)
+            }
+            OutputParser::Eslint => {
+                // "✖ 12 problems (3 errors, 9 warnings)"
+                let output = format!("{stdout}\n{stderr}");
+                match output.lines().find(|l| l.contains("problem")) {
+                    Some(line) => {
+                        let errors = count_labelled(line, "error");
+                        let warnings = count_labelled(line, "warning");
+                        (1.0 - errors as f64 * 0.2 - warnings as f64 * 0.05).max(0.0)
+                    }
+                    None => 0.0,
+                }
+            }
+            OutputParser::Tsc => {
+                // One "error TS1234:" diagnostic per line
+                let errors = stdout.matches("error TS").count().max(1);
+                (1.0 - (errors as f64 * 0.1)).max(0.0)
+            }
+            OutputParser::NodeTest => {
+                let output = format!("{stdout}\n{stderr}");
+                if let Some(line) = output
+                    .lines()
+                    .find(|l| l.trim_start().starts_with("Tests:") || l.trim_start().starts_with("Tests "))
+                {
+                    // jest: "Tests: 1 failed, 3 passed, 4 total"; vitest: "Tests  1 failed | 3 passed (4)"
+                    pass_ratio(count_labelled(line, "passed"), count_labelled(line, "failed"))
+                } else {
+                    // node --test (TAP): "# pass 3" / "# fail 1"
+                    let tap_count = |label: &str| {
+                        output
+                            .lines()
+                            .find_map(|l| l.trim().strip_prefix(label))
+                            .and_then(|n| n.trim().parse::<u32>().ok())
+                            .unwrap_or(0)
+                    };
+                    pass_ratio(tap_count("# pass"), tap_count("# fail"))
+                }
+            }
+            OutputParser::GoVet => {
+                // One "file.go:line:col: message" diagnostic per line
+                let findings = stderr
+                    .lines()
+                    .filter(|l| !l.starts_with('#') && l.contains(".go:"))
+                    .count()
+                    .max(1);
+                (1.0 - (findings as f64 * 0.1)).max(0.0)
+            }
+            OutputParser::GoTest => {
+                // Package result lines: "ok  \tpkg 0.01s" / "FAIL\tpkg 0.02s"
+                let output = format!("{stdout}\n{stderr}");
+                let ok = output.lines().filter(|l| l.starts_with("ok ") || l.starts_with("ok\t")).count();
+                let failed = output.lines().filter(|l| l.starts_with("FAIL\t")).count();
+                pass_ratio(ok as u32, failed as u32)
+            }
+            OutputParser::Auto
+            | OutputParser::ExitCode
+            | OutputParser::CargoCheck
+            | OutputParser::CargoFmt
+            | OutputParser::GoBuild => {
                 // Default: binary pass/fail
                 if exit_code == 0 {
                     1.0
@@ -475,8 +1456,15 @@ impl ValidationSuite {
 
         for (name, result) in results {
             if !result.passed {
-                match name.as_str() {
-                    "cargo_check" => {
+                let parser = self
+                    .validators
+                    .iter()
+                    .find(|v| &v.name == name)
+                    .map_or(OutputParser::Auto, |v| v.parser)
+                    .resolve(name);
+
+                match parser {
+                    OutputParser::CargoCheck => {
                         recommendations.push(
                             "Fix compilation errors to improve type checking score.".to_string(),
                         );
@@ -487,7 +1475,7 @@ impl ValidationSuite {
                                 .push(format!("First errors: {}", error_lines.join("; ")));
                         }
                     }
-                    "cargo_clippy" => {
+                    OutputParser::CargoClippy => {
                         let warning_count = result.error_output.matches("warning:").count();
                         let error_count = result.error_output.matches("error:").count();
 
@@ -499,7 +1487,7 @@ impl ValidationSuite {
                             ));
                         }
                     }
-                    "cargo_test" => {
+                    OutputParser::CargoTest => {
                         recommendations
                             .push("Fix failing tests to improve test coverage.".to_string());
                         if result.error_output.contains("test result:") {
@@ -508,16 +1496,39 @@ impl ValidationSuite {
                             );
                         }
                     }
-                    "cargo_fmt" => {
+                    OutputParser::CargoFmt => {
                         recommendations
                             .push("Run 'cargo fmt' to fix code formatting issues.".to_string());
                     }
-                    "cargo_audit" => {
+                    OutputParser::CargoAudit => {
                         recommendations.push(
                             "Update dependencies to fix security vulnerabilities.".to_string(),
                         );
                     }
-                    _ => {
+                    OutputParser::Ruff => {
+                        recommendations.push(format!(
+                            "Run 'ruff check --fix' to resolve lint findings in {name}."
+                        ));
+                    }
+                    OutputParser::Mypy | OutputParser::Tsc => {
+                        recommendations.push(format!("Fix type errors reported by {name}."));
+                    }
+                    OutputParser::Pytest | OutputParser::NodeTest | OutputParser::GoTest => {
+                        recommendations.push(format!("Fix failing tests reported by {name}."));
+                    }
+                    OutputParser::Eslint => {
+                        recommendations.push(format!(
+                            "Run 'eslint --fix' and address remaining problems in {name}."
+                        ));
+                    }
+                    OutputParser::GoVet => {
+                        recommendations.push(format!("Address 'go vet' findings in {name}."));
+                    }
+                    OutputParser::GoBuild => {
+                        recommendations
+                            .push(format!("Fix compilation errors reported by {name}."));
+                    }
+                    OutputParser::Auto | OutputParser::ExitCode => {
                         recommendations.push(format!("Fix issues in {name} validator."));
                     }
                 }
@@ -541,6 +1552,163 @@ impl ValidationSuite {
     }
 }
 
+/// How long to keep reading a validator's pipes after the process ends
+const OUTPUT_DRAIN_GRACE: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Collect a child pipe into a string, emitting each line as an event
+///
+/// Stops at end of file, or early once `stop` is set.
+async fn collect_lines<R: AsyncRead + Unpin>(
+    pipe: R,
+    validator: String,
+    stream: OutputStream,
+    events: Option<ValidationEventSender>,
+    mut stop: watch::Receiver<bool>,
+) -> String {
+    let mut collected = String::new();
+    let mut reader = BufReader::new(pipe);
+    let mut buf = Vec::new();
+
+    // Decode each line lossily: tools print invalid UTF-8, and stopping at it
+    // would leave the pipe full and the validator blocked
+    loop {
+        let read = tokio::select! {
+            read = reader.read_until(b'\n', &mut buf) => read,
+            Ok(()) = stop.changed() => break,
+        };
+        if !matches!(read, Ok(n) if n > 0) {
+            break;
+        }
+        let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned();
+        buf.clear();
+        collected.push_str(&line);
+        collected.push('\n');
+        emit(events.as_ref(), ValidationEvent::OutputLine {
+            validator: validator.clone(),
+            stream,
+            line,
+        });
+    }
+
+    collected
+}
+
+/// Detect project roots under `repo_path` by their marker files
+///
+/// Walks up to four directory levels, skipping hidden directories and
+/// dependency/build output (`node_modules`, `target`, `vendor`, virtualenvs).
+/// A root is skipped when an ancestor is already a root of the same kind, so
+/// Cargo and npm workspace members are validated through their workspace root.
+///
+/// # Errors
+///
+/// Returns [`GdkError::FileSystemError`] if `repo_path` cannot be read.
+pub fn detect_project_roots(repo_path: &str) -> GdkResult<Vec<ProjectRoot>> {
+    const MAX_DEPTH: usize = 4;
+    const SKIPPED_DIRS: &[&str] = &[
+        "node_modules", "target", "vendor", "venv", "__pycache__", "dist", "build",
+    ];
+    const KINDS: [ProjectKind; 4] = [
+        ProjectKind::Rust,
+        ProjectKind::Python,
+        ProjectKind::Node,
+        ProjectKind::Go,
+    ];
+
+    let root = std::path::Path::new(repo_path);
+    std::fs::read_dir(root).map_err(|e| {
+        GdkError::file_system_error(repo_path, "Failed to read repository for project detection", e)
+    })?;
+
+    let mut roots: Vec<ProjectRoot> = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        let relative = dir
+            .strip_prefix(root)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let relative = if relative.is_empty() { ".".to_string() } else { relative };
+
+        for kind in KINDS {
+            if !kind.markers().iter().any(|marker| dir.join(marker).is_file()) {
+                continue;
+            }
+            let nested_in_same_kind = roots.iter().any(|r| {
+                r.kind == kind
+                    && (r.relative_path == "."
+                        || relative.starts_with(&format!("{}/", r.relative_path)))
+            });
+            if !nested_in_same_kind {
+                roots.push(ProjectRoot {
+                    relative_path: relative.clone(),
+                    kind,
+                });
+            }
+        }
+
+        if depth >= MAX_DEPTH {
+            continue;
+        }
+
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut children: Vec<_> = entries
+            .flatten()
+            .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|e| e.path())
+            .filter(|p| {
+                let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name)
+            })
+            .collect();
+        // Visit shallower and lexicographically smaller paths first so that
+        // ancestors are recorded before their descendants
+        children.sort();
+        children.reverse();
+        pending.extend(children.into_iter().map(|p| (p, depth + 1)));
+    }
+
+    roots.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(roots)
+}
+
+/// Find `<count> <label>` in a line, tolerating plurals and punctuation
+fn count_labelled(line: &str, label: &str) -> u32 {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    tokens
+        .windows(2)
+        .find_map(|pair| {
+            let word = pair[1].trim_matches(|c: char| !c.is_alphanumeric());
+            if word.starts_with(label) {
+                pair[0].trim_matches(|c: char| !c.is_ascii_digit()).parse().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0)
+}
+
+/// Find the count in a summary line such as `Found 3 errors.`
+fn summary_count(output: &str, prefix: &str, label: &str) -> Option<u32> {
+    output
+        .lines()
+        .find(|l| l.trim_start().starts_with(prefix))
+        .map(|line| count_labelled(line, label))
+}
+
+/// Ratio of passed to total, treating an empty run as a failure
+fn pass_ratio(passed: u32, failed: u32) -> f64 {
+    let total = passed + failed;
+    if total == 0 {
+        0.0
+    } else {
+        passed as f64 / total as f64
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorSummary {
     pub total_validators: usize,
@@ -0,0 +1,365 @@
+//! Content-addressed cache for validator results
+//!
+//! Agents frequently validate identical trees, for example after reverting to
+//! the same [`RevertPoint`](crate::RevertPoint). This module stores each
+//! [`ValidatorResult`] on disk keyed by:
+//! - The git tree OID of the working tree being validated
+//! - A hash of the validator definition (command, args, timeout, parser)
+//! - A fingerprint of the toolchain (the output of the validator's version
+//!   probe, `<command> --version` unless the validator names another)
+//!
+//! Entries are plain JSON files written atomically, so they can be shared
+//! across processes and agents. The default location is inside the git common
+//! directory, which all worktrees of a repository share.
+//!
+//! # Example Usage
+//!
+//! ```rust,no_run
+//! use gdk::validation::ValidationSuite;
+//! use gdk::validation_cache::ValidationCacheConfig;
+//!
+//! #[tokio::main]
+//! async fn main() -> gdk::GdkResult<()> {
+//!     let mut suite = ValidationSuite::rust_default("./my-project");
+//!     suite.set_cache(ValidationCacheConfig::default());
+//!
+//!     let result = suite.validate("./my-project").await?;
+//!     for (name, validator) in &result.validator_results {
+//!         println!("{name}: cache hit = {}", validator.cache_hit);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::storage::write_atomic;
+use crate::validation::{Validator, ValidatorResult};
+use crate::{GdkError, GdkResult, GdkResultExt};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::process::Command;
+
+/// Configuration for the on-disk validation cache
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationCacheConfig {
+    /// Directory holding cache entries (defaults to `<git common dir>/gdk/validation-cache`)
+    pub cache_dir: Option<String>,
+    /// Maximum total size of all entries in bytes before eviction
+    pub max_size_bytes: u64,
+    /// Maximum number of entries before eviction
+    pub max_entries: usize,
+}
+
+impl Default for ValidationCacheConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: None,
+            max_size_bytes: 64 * 1024 * 1024,
+            max_entries: 4096,
+        }
+    }
+}
+
+/// Key identifying a cached validator result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Git tree OID of the validated working tree
+    pub tree_oid: String,
+    /// SHA-256 of the validator definition
+    pub validator_hash: String,
+    /// SHA-256 of the toolchain version output
+    pub toolchain_fingerprint: String,
+}
+
+impl CacheKey {
+    /// Stable digest used as the entry file name
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.tree_oid.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.validator_hash.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.toolchain_fingerprint.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Serialized form of a cache entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    key: CacheKey,
+    result: ValidatorResult,
+    created_at: u64,
+}
+
+/// Handle to an on-disk validation cache directory
+///
+/// The handle only holds paths and limits, so it is cheap to clone into
+/// parallel validator tasks.
+#[derive(Debug, Clone)]
+pub struct ValidationCache {
+    root: PathBuf,
+    max_size_bytes: u64,
+    max_entries: usize,
+}
+
+impl ValidationCache {
+    /// Open (and create if needed) the cache for the repository at `repo_path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GdkError::GitError`] if no cache directory is configured and
+    /// `repo_path` is not a git repository, or [`GdkError::FileSystemError`]
+    /// if the directory cannot be created.
+    pub fn open(config: &ValidationCacheConfig, repo_path: &str) -> GdkResult<Self> {
+        let root = match &config.cache_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                let repo = Repository::discover(repo_path)
+                    .with_git_context("locating repository for validation cache")?;
+                git_common_dir(&repo).join("gdk").join("validation-cache")
+            }
+        };
+
+        fs::create_dir_all(&root)
+            .with_file_context(&root.to_string_lossy(), "creating validation cache directory")?;
+
+        Ok(Self {
+            root,
+            max_size_bytes: config.max_size_bytes,
+            max_entries: config.max_entries,
+        })
+    }
+
+    /// Directory holding the cache entries
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Look up a cached result, marking it as a cache hit
+    ///
+    /// Unreadable or corrupt entries are treated as misses and removed.
+    pub fn get(&self, key: &CacheKey) -> Option<ValidatorResult> {
+        let path = self.entry_path(key);
+        let content = fs::read_to_string(&path).ok()?;
+
+        match serde_json::from_str::<CacheEntry>(&content) {
+            Ok(entry) if entry.key == *key => {
+                // Refresh the modification time so eviction is least-recently-used
+                if let Ok(file) = fs::File::options().write(true).open(&path) {
+                    let _ = file.set_modified(SystemTime::now());
+                }
+                let mut result = entry.result;
+                result.cache_hit = true;
+                Some(result)
+            }
+            _ => {
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Store a result and evict old entries if the cache exceeds its bounds
+    pub fn put(&self, key: &CacheKey, result: &ValidatorResult) -> GdkResult<()> {
+        let mut stored = result.clone();
+        stored.cache_hit = false;
+
+        let entry = CacheEntry {
+            key: key.clone(),
+            result: stored,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+
+        let json = serde_json::to_vec(&entry).map_err(|e| GdkError::SerializationError {
+            format: "JSON".to_string(),
+            context: "validation cache entry".to_string(),
+            source: e,
+        })?;
+
+        write_atomic(&self.entry_path(key), &json, "validation cache entry")?;
+        self.evict()
+    }
+
+    /// Remove least-recently-used entries until the cache is within bounds
+    pub fn evict(&self) -> GdkResult<()> {
+        let mut entries = Vec::new();
+        let mut total_size = 0u64;
+
+        let dir = fs::read_dir(&self.root)
+            .with_file_context(&self.root.to_string_lossy(), "listing validation cache")?;
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            if let Ok(metadata) = entry.metadata() {
+                let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+                total_size += metadata.len();
+                entries.push((modified, metadata.len(), path));
+            }
+        }
+
+        if total_size <= self.max_size_bytes && entries.len() <= self.max_entries {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(modified, _, _)| *modified);
+
+        let mut remaining = entries.len();
+        for (_, size, path) in entries {
+            if total_size <= self.max_size_bytes && remaining <= self.max_entries {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total_size = total_size.saturating_sub(size);
+                remaining -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of entries currently stored
+    pub fn len(&self) -> usize {
+        fs::read_dir(&self.root)
+            .map(|dir| {
+                dir.flatten()
+                    .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("json"))
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.root.join(format!("{}.json", key.digest()))
+    }
+}
+
+/// Resolve the git directory shared by all worktrees of a repository
+///
+/// Linked worktrees keep a `commondir` file pointing back at the main
+/// repository's git directory. git2 0.19 does not bind libgit2's
+/// `git_repository_commondir`, so the file is read here.
+pub fn git_common_dir(repo: &Repository) -> PathBuf {
+    let git_dir = repo.path();
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(content) => {
+            let common = PathBuf::from(content.trim());
+            if common.is_absolute() {
+                common
+            } else {
+                git_dir.join(common)
+            }
+        }
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Compute the tree OID of the working tree as it would be committed
+///
+/// Stages all non-ignored files into an in-memory copy of the index, so the
+/// on-disk index is left untouched.
+pub fn working_tree_oid(repo_path: &str) -> GdkResult<String> {
+    let repo = Repository::discover(repo_path).with_git_context("opening repository for tree hashing")?;
+    let mut index = repo.index().with_git_context("reading index")?;
+    index
+        .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+        .with_git_context("staging working tree")?;
+    let tree_oid = index
+        .write_tree()
+        .with_git_context("writing working tree object")?;
+    Ok(tree_oid.to_string())
+}
+
+/// Hash the parts of a validator definition that affect its result
+///
+/// Weight and requirement level only matter for aggregation, and the working
+/// directory is made relative to the repository so that agents validating
+/// different worktrees of the same repository share entries.
+pub fn validator_hash(validator: &Validator, repo_path: &str) -> String {
+    let mut definition = validator.clone();
+    definition.weight = 0.0;
+    definition.is_required = false;
+    definition.working_dir = validator.working_dir.as_ref().map(|dir| {
+        Path::new(dir)
+            .strip_prefix(repo_path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| dir.clone())
+    });
+
+    let json = serde_json::to_string(&definition).unwrap_or_default();
+    hex::encode(Sha256::digest(json.as_bytes()))
+}
+
+/// How long a version probe may run before the toolchain counts as unknown
+pub const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fingerprint a toolchain from the output of its version probe
+///
+/// `probe` is a command line such as `npx --no-install eslint --version`
+/// (see [`Validator::version_probe`]). Probes that fail, or run longer than
+/// [`VERSION_PROBE_TIMEOUT`] (a command that ignores `--version` may run
+/// its real work instead), fall back to a fingerprint of the probe itself,
+/// which still separates distinct tools.
+pub async fn toolchain_fingerprint(probe: &[String], working_dir: &str) -> String {
+    let version = match probe.split_first() {
+        Some((program, args)) => {
+            let output = Command::new(program)
+                .args(args)
+                .current_dir(working_dir)
+                .stdin(Stdio::null())
+                .kill_on_drop(true)
+                .output();
+            match tokio::time::timeout(VERSION_PROBE_TIMEOUT, output).await {
+                Ok(Ok(output)) if output.status.success() => {
+                    String::from_utf8_lossy(&output.stdout).trim().to_string()
+                }
+                _ => format!("unknown:{}", probe.join(" ")),
+            }
+        }
+        None => "unknown:".to_string(),
+    };
+
+    hex::encode(Sha256::digest(version.as_bytes()))
+}
+
+/// Build cache keys for every validator in a suite
+///
+/// Toolchain fingerprints are computed once per distinct version probe.
+pub async fn cache_keys(validators: &[Validator], repo_path: &str) -> GdkResult<HashMap<String, CacheKey>> {
+    let tree_oid = working_tree_oid(repo_path)?;
+
+    let mut fingerprints: HashMap<Vec<String>, String> = HashMap::new();
+    let mut keys = HashMap::new();
+
+    for validator in validators {
+        let working_dir = validator.working_dir.as_deref().unwrap_or(repo_path);
+        let probe = validator.version_probe();
+        if !fingerprints.contains_key(&probe) {
+            let fingerprint = toolchain_fingerprint(&probe, working_dir).await;
+            fingerprints.insert(probe.clone(), fingerprint);
+        }
+
+        keys.insert(
+            validator.name.clone(),
+            CacheKey {
+                tree_oid: tree_oid.clone(),
+                validator_hash: validator_hash(validator, repo_path),
+                toolchain_fingerprint: fingerprints[&probe].clone(),
+            },
+        );
+    }
+
+    Ok(keys)
+}
@@ -540,7 +540,6 @@ pub fn save_visualization<W: Write>(
 // Generate sample tree data for testing
 pub fn generate_sample_tree(num_commits: usize, num_branches: usize) -> Vec<CommitNode> {
     let mut commits = Vec::new();
-    let mut commit_counter = 1;
 
     // Create main trunk commits
     let trunk_commits = num_commits / 2;
@@ -551,14 +550,14 @@ pub fn generate_sample_tree(num_commits: usize, num_branches: usize) -> Vec<Comm
             vec![]
         };
 
+        let commit_number = i + 1;
         let commit = create_sample_commit(
-            format!("commit_{commit_counter}"),
-            format!("Main trunk commit {commit_counter}"),
+            format!("commit_{commit_number}"),
+            format!("Main trunk commit {commit_number}"),
             parent_hashes,
             0.7 + (i as f64 * 0.1) % 0.3,
         );
         commits.push(commit);
-        commit_counter += 1;
     }
 
     // Create branch commits
@@ -580,7 +579,6 @@ pub fn generate_sample_tree(num_commits: usize, num_branches: usize) -> Vec<Comm
                 0.5 + (i as f64 * 0.2) % 0.4,
             );
             commits.push(commit);
-            commit_counter += 1;
         }
     }
 
@@ -646,6 +644,7 @@ fn create_sample_commit(
             test_pass_rate: health_score,
             quality_trend: vec![health_score],
             is_converged: health_score > 0.8,
+            ..Default::default()
         },
     }
 }
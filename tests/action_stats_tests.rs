@@ -0,0 +1,166 @@
+//! Action timing and analytics tests for the GDK system
+//!
+//! These tests check what the controller records about each action:
+//! - Start and end timestamps with durations
+//! - Per-type metadata for checkpoints, reverts and iterations
+//! - Per-type counts, success rates and latency percentiles
+//! - Failed operations recorded with the error that stopped them
+
+mod common;
+
+use common::setup_controller;
+use gdk::advisor::NextAction;
+use gdk::agent::{ActionType, LatencyPercentiles};
+use gdk::budget::SessionBudget;
+use gdk::policy::CommitPolicy;
+use std::fs;
+
+#[tokio::test]
+async fn test_actions_are_timed_with_metadata() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .create_spiral_checkpoint("agent-1", "before refactor")
+        .await
+        .unwrap();
+    controller.revert_to_last_checkpoint("agent-1").await.unwrap();
+    controller.get_convergence_status("agent-1").await.unwrap();
+
+    let history = &controller.action_history;
+    assert_eq!(history.len(), 3);
+    for action in history {
+        let ended = action.ended_at_ms.unwrap();
+        assert!(ended >= action.started_at_ms);
+        assert_eq!(action.duration_ms, Some(ended - action.started_at_ms));
+        assert_eq!(action.timestamp, action.started_at_ms / 1000);
+    }
+
+    assert_eq!(history[0].metadata["checkpoint_reason"], "before refactor");
+    assert_eq!(history[1].metadata["revert_reason"], "before refactor");
+    assert_eq!(history[2].metadata["converged"], "false");
+    assert!(history[2].metadata.contains_key("test_pass_rate"));
+}
+
+#[tokio::test]
+async fn test_iterations_record_commit_metadata() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    let _ = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await;
+
+    let iterations: Vec<_> = controller
+        .action_history
+        .iter()
+        .filter(|a| a.action_type == ActionType::InfiniteMonkeyIteration && a.success)
+        .collect();
+    assert!(!iterations.is_empty());
+    assert_eq!(iterations[0].metadata["attempt"], "1");
+    for key in ["health_score", "red_threads", "validator_ms", "validator_cpu_secs", "stalled"] {
+        assert!(iterations[0].metadata.contains_key(key), "missing {key}");
+    }
+}
+
+#[tokio::test]
+async fn test_statistics_by_action_type() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller.create_spiral_checkpoint("agent-1", "one").await.unwrap();
+    controller.create_spiral_checkpoint("agent-1", "two").await.unwrap();
+    controller.get_convergence_status("agent-1").await.unwrap();
+
+    let stats = controller.get_agent_statistics("agent-1").unwrap();
+    assert_eq!(stats.total_actions, 3);
+    let types: Vec<_> = stats
+        .action_types
+        .iter()
+        .map(|t| (t.action_type.clone(), t.count, t.successful))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            (ActionType::RevertToPoint, 2, 2),
+            (ActionType::ConvergenceCheck, 1, 1),
+        ]
+    );
+    assert_eq!(stats.action_types[0].success_rate, 1.0);
+    assert!(stats.action_types.iter().all(|t| t.latency.is_some()));
+}
+
+#[test]
+fn test_latency_percentiles() {
+    assert_eq!(LatencyPercentiles::from_durations(Vec::new()), None);
+
+    let single = LatencyPercentiles::from_durations(vec![7]).unwrap();
+    assert_eq!((single.p50_ms, single.p99_ms, single.max_ms), (7, 7, 7));
+
+    let durations: Vec<u64> = (1..=100).rev().collect();
+    assert_eq!(
+        LatencyPercentiles::from_durations(durations),
+        Some(LatencyPercentiles {
+            p50_ms: 50,
+            p90_ms: 90,
+            p99_ms: 99,
+            max_ms: 100,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_failed_actions_reach_the_history() {
+    let (temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    let checkpoint = controller
+        .create_spiral_checkpoint("agent-1", "known good")
+        .await
+        .unwrap();
+
+    // Rejected commits are recorded as failures with the error that stopped them
+    controller.workflow.commit_policy = CommitPolicy::default().with_protected_path("blocked.txt");
+    fs::write(temp_dir.path().join("blocked.txt"), "nope\n").unwrap();
+    for _ in 0..3 {
+        let err = controller.validate_and_commit("agent-1", "Blocked").await.unwrap_err();
+        assert_eq!(err.category(), "validation");
+    }
+    let failed = controller.action_history.last().unwrap();
+    assert_eq!(failed.action_type, ActionType::QualityValidation);
+    assert!(!failed.success);
+    assert!(failed.ended_at_ms.is_some());
+    assert!(failed.metadata["error"].contains("protected paths changed (blocked.txt)"));
+
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(recommendation.rule, "failure_streak");
+    assert_eq!(
+        recommendation.action,
+        NextAction::RevertToCheckpoint {
+            commit_hash: checkpoint.commit_hash
+        }
+    );
+
+    // So are budget refusals
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_reverts: Some(0),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(controller.revert_to_last_checkpoint("agent-1").await.is_err());
+    assert_eq!(controller.active_sessions["agent-1"].revert_stack.len(), 1);
+
+    let stats = controller.get_agent_statistics("agent-1").unwrap();
+    let types: Vec<_> = stats
+        .action_types
+        .iter()
+        .map(|t| (t.action_type.clone(), t.count, t.successful))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            (ActionType::RevertToPoint, 2, 1),
+            (ActionType::QualityValidation, 3, 0),
+        ]
+    );
+}
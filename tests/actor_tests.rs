@@ -0,0 +1,107 @@
+//! Workflow actor tests for the GDK system
+//!
+//! These tests use the thread-confined workflow from a multi-threaded runtime:
+//! - Handles are Send + Sync and usable from spawned tasks
+//! - Commands from concurrent tasks are serialized on the actor thread
+//! - Closures can read manager state on the actor thread
+//! - The handle drives an agent controller like any other workflow
+
+mod common;
+
+use common::setup_repo;
+use gdk::actor::WorkflowHandle;
+use gdk::agent::AgentWorkflowController;
+use gdk::SendGitWorkflow;
+use git2::Repository;
+use std::fs;
+use std::sync::Arc;
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_spawned_tasks_share_one_workflow() {
+    assert_send_sync::<WorkflowHandle>();
+    assert_send_sync::<Arc<dyn SendGitWorkflow>>();
+
+    let repo = setup_repo();
+    let workflow: Arc<dyn SendGitWorkflow> =
+        Arc::new(WorkflowHandle::open(repo.path().to_str().unwrap()).unwrap());
+
+    let tasks: Vec<_> = (0..4)
+        .map(|i| {
+            let workflow = workflow.clone();
+            tokio::spawn(async move {
+                workflow
+                    .create_commit_node(&format!("Commit from task {i}"))
+                    .await
+                    .unwrap()
+            })
+        })
+        .collect();
+
+    let mut hashes = Vec::new();
+    for task in tasks {
+        hashes.push(task.await.unwrap().hash);
+    }
+    hashes.sort();
+    hashes.dedup();
+    assert_eq!(hashes.len(), 4);
+
+    // Commands ran one after another, so the commits form a single chain
+    let git = Repository::open(repo.path()).unwrap();
+    let mut revwalk = git.revwalk().unwrap();
+    revwalk.push_head().unwrap();
+    assert_eq!(revwalk.count(), 5);
+
+    let metrics = workflow.analyze_convergence().await.unwrap();
+    assert_eq!(metrics.attempts, 4);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_revert_and_manager_calls_on_actor_thread() {
+    let repo = setup_repo();
+    let workflow = WorkflowHandle::open(repo.path().to_str().unwrap()).unwrap();
+    assert_eq!(workflow.repo_path(), repo.path().to_str().unwrap());
+
+    let point = workflow.create_revert_point("before edit").await.unwrap();
+    fs::write(repo.path().join("edit.txt"), "change\n").unwrap();
+    let commit = workflow.create_commit_node("Edit").await.unwrap();
+    assert_ne!(commit.hash, point.commit_hash);
+
+    let (history, thread_name) = workflow
+        .call(|manager| {
+            (
+                manager.commit_history.len(),
+                std::thread::current().name().map(str::to_string),
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(history, 1);
+    assert_eq!(thread_name.as_deref(), Some("gdk-workflow"));
+
+    tokio::spawn({
+        let workflow = workflow.clone();
+        async move { workflow.revert_to_point(&point).await.unwrap() }
+    })
+    .await
+    .unwrap();
+    assert!(!repo.path().join("edit.txt").exists());
+}
+
+#[tokio::test]
+async fn test_handle_drives_agent_controller() {
+    let repo = setup_repo();
+    let workflow = WorkflowHandle::open(repo.path().to_str().unwrap()).unwrap();
+    let mut controller = AgentWorkflowController::new(workflow.clone());
+
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .create_spiral_checkpoint("agent-1", "safe point")
+        .await
+        .unwrap();
+    controller.revert_to_last_checkpoint("agent-1").await.unwrap();
+
+    let stats = controller.get_agent_statistics("agent-1").unwrap();
+    assert_eq!(stats.total_actions, 2);
+}
@@ -0,0 +1,192 @@
+//! Next-action recommendation tests for the GDK system
+//!
+//! These tests check the typed recommendations behind `suggest_next_action`:
+//! - The fallback when no rule applies
+//! - Reverting after a streak of failed actions
+//! - Budget, stall, blocking-file and sibling rules
+//! - Custom rules and configuration
+
+mod common;
+
+use common::setup_controller;
+use gdk::advisor::{AdviceContext, AdvisorConfig, NextAction, NextActionAdvisor, NextActionRule, Recommendation};
+use gdk::budget::{BudgetResource, SessionBudget};
+use gdk::convergence::{BlockingThread, ThreadConvergenceSummary};
+use gdk::lineage::{BranchLineage, SiblingComparison};
+use gdk::threads::ThreadThreshold;
+use gdk::{ConvergenceMetrics, ThreadColor};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_fresh_session_continues() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(recommendation.action, NextAction::Continue);
+    assert_eq!(recommendation.rule, "default");
+    assert!(recommendation.to_string().starts_with("CONTINUE: "));
+    assert!(controller.suggest_next_action("missing").is_err());
+}
+
+#[tokio::test]
+async fn test_failure_streak_recommends_revert() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    let checkpoint = controller
+        .create_spiral_checkpoint("agent-1", "known good")
+        .await
+        .unwrap();
+
+    let mut failed = controller.action_history[0].clone();
+    failed.success = false;
+    controller.action_history.extend([failed.clone(), failed.clone(), failed]);
+
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(
+        recommendation.action,
+        NextAction::RevertToCheckpoint {
+            commit_hash: checkpoint.commit_hash.clone()
+        }
+    );
+    assert_eq!(recommendation.rule, "failure_streak");
+    assert!((recommendation.confidence - 0.8).abs() < 1e-9);
+    assert!(recommendation.rationale.contains("known good"));
+    assert_eq!(
+        serde_json::to_value(&recommendation.action).unwrap(),
+        json!({ "action": "revert_to_checkpoint", "commit_hash": checkpoint.commit_hash })
+    );
+}
+
+#[tokio::test]
+async fn test_budget_rule() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_commits: Some(10),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let session = controller.active_sessions.get_mut("agent-1").unwrap();
+    session.budget_usage.commits = 9;
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(recommendation.action, NextAction::Checkpoint);
+    assert_eq!(recommendation.rule, "budget");
+
+    let session = controller.active_sessions.get_mut("agent-1").unwrap();
+    session.budget_usage.commits = 10;
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(
+        recommendation.action,
+        NextAction::Stop {
+            resource: BudgetResource::Commits
+        }
+    );
+    assert_eq!(recommendation.confidence, 1.0);
+}
+
+#[tokio::test]
+async fn test_convergence_rules_are_ranked() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+
+    let session = controller.active_sessions.get_mut("agent-1").unwrap();
+    session.current_commit = Some("c3".to_string());
+    session.thread_colors.insert("src/a.rs".to_string(), ThreadColor::Red);
+    session.thread_colors.insert("src/c.rs".to_string(), ThreadColor::Green);
+    session.convergence_history.push(ConvergenceMetrics {
+        test_pass_rate: 0.7,
+        is_stalled: true,
+        thread_convergence: ThreadConvergenceSummary {
+            converged_share: 0.5,
+            converged_files: vec!["src/c.rs".to_string()],
+            blocking_files: vec![BlockingThread {
+                file_path: "src/b.rs".to_string(),
+                latest_score: 0.4,
+                threshold: ThreadThreshold::default(),
+            }],
+        },
+        siblings: vec![SiblingComparison {
+            base_hash: "c1".to_string(),
+            base_score: 0.5,
+            branches: vec![BranchLineage {
+                tip_hash: "c2".to_string(),
+                commits_since_base: 1,
+                latest_score: 0.75,
+                mean_score: 0.75,
+                delta_from_base: 0.25,
+            }],
+            best_tip: Some("c2".to_string()),
+        }],
+        ..Default::default()
+    });
+
+    let actions: Vec<_> = controller
+        .recommend_next_actions("agent-1")
+        .unwrap()
+        .into_iter()
+        .map(|r| r.action)
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            NextAction::SpiralBranch {
+                from_commit: Some("c3".to_string())
+            },
+            NextAction::FixFiles {
+                files: vec!["src/a.rs".to_string(), "src/b.rs".to_string()]
+            },
+            NextAction::MergeBranch {
+                tip_hash: "c2".to_string(),
+                base_hash: "c1".to_string()
+            },
+        ]
+    );
+}
+
+#[derive(Debug)]
+struct AlwaysCheckpoint;
+
+impl NextActionRule for AlwaysCheckpoint {
+    fn name(&self) -> &str {
+        "always_checkpoint"
+    }
+
+    fn evaluate(&self, _context: &AdviceContext<'_>) -> Option<Recommendation> {
+        Some(Recommendation {
+            action: NextAction::Checkpoint,
+            confidence: 0.99,
+            rationale: "House rule".to_string(),
+            rule: self.name().to_string(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_rules_and_config() {
+    let (_temp_dir, controller) = setup_controller();
+    let mut controller = controller.with_advisor(NextActionAdvisor::empty().with_rule(AlwaysCheckpoint));
+    controller.start_agent_session("agent-1").await.unwrap();
+    assert_eq!(controller.advisor.rule_names(), ["always_checkpoint"]);
+
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert_eq!(recommendation.to_string(), "CHECKPOINT: House rule");
+
+    // A lower streak threshold reverts sooner
+    let advisor = NextActionAdvisor::with_config(AdvisorConfig {
+        failure_streak: 1,
+        ..Default::default()
+    });
+    let mut controller = controller.with_advisor(advisor);
+    controller.create_spiral_checkpoint("agent-1", "base").await.unwrap();
+    let mut failed = controller.action_history[0].clone();
+    failed.success = false;
+    controller.action_history.push(failed);
+    let recommendation = controller.suggest_next_action("agent-1").unwrap();
+    assert!(matches!(recommendation.action, NextAction::RevertToCheckpoint { .. }));
+}
@@ -0,0 +1,232 @@
+//! Approval gate tests for the GDK system
+//!
+//! These tests check that converged spirals wait for a human decision:
+//! - Converged results are parked with diff stats and gate results
+//! - Approval by an authorized identity fast-forwards the branch
+//! - Rejections keep the branch and log the reason
+//! - Only configured approvers decide, and nobody does when none are
+//! - Moved branches block promotion
+
+mod common;
+
+use common::{head, setup_repo};
+use gdk::agent::{ActionType, AgentWorkflowController};
+use gdk::approval::{ApprovalGate, ApprovalRequest, ApprovalStatus};
+use gdk::convergence::{ConvergenceAnalyzer, ConvergencePolicy, ConvergenceResult};
+use gdk::core::GitWorkflowManager;
+use gdk::{CommitNode, ConvergenceMetrics, GdkError, GdkResult, GitWorkflow};
+use git2::{Repository, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Policy that accepts every attempt
+#[derive(Debug)]
+struct AlwaysConverged;
+
+impl ConvergencePolicy for AlwaysConverged {
+    fn evaluate(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult> {
+        let mut result = ConvergenceAnalyzer::new().evaluate(commit_history)?;
+        result.is_converged = true;
+        Ok(result)
+    }
+}
+
+fn setup_controller(
+    gate: impl FnOnce(ApprovalGate) -> ApprovalGate,
+) -> (TempDir, AgentWorkflowController<GitWorkflowManager>) {
+    let temp_dir = setup_repo();
+    let path = temp_dir.path().to_str().unwrap();
+    let manager = GitWorkflowManager::new(path)
+        .unwrap()
+        .with_convergence_policy(AlwaysConverged);
+    let gate = gate(ApprovalGate::for_repo(path).unwrap());
+    (temp_dir, AgentWorkflowController::new(manager).with_approval_gate(gate))
+}
+
+fn reviewer(gate: ApprovalGate) -> ApprovalGate {
+    gate.with_approvers(["reviewer@example.com"])
+}
+
+/// Run a spiral that converges on its first attempt, adding `feature.txt`
+async fn converge(temp_dir: &TempDir, controller: &mut AgentWorkflowController<GitWorkflowManager>) -> ApprovalRequest {
+    controller.start_agent_session("agent-1").await.unwrap();
+    fs::write(temp_dir.path().join("feature.txt"), "one\ntwo\n").unwrap();
+    let commit_node = controller
+        .execute_infinite_monkey_workflow("agent-1", 0.0)
+        .await
+        .unwrap();
+
+    let pending = controller.pending_approvals().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].converged_commit, commit_node.hash);
+    pending[0].clone()
+}
+
+#[tokio::test]
+async fn test_converged_result_awaits_approval() {
+    let (temp_dir, mut controller) = setup_controller(reviewer);
+    let base = head(temp_dir.path());
+    let request = converge(&temp_dir, &mut controller).await;
+
+    // The branch is back where the spiral started
+    assert_eq!(head(temp_dir.path()), base);
+    assert!(!temp_dir.path().join("feature.txt").exists());
+    assert_eq!(request.base_commit, base);
+    assert_eq!(request.status, ApprovalStatus::Pending);
+
+    let repo = Repository::open(temp_dir.path()).unwrap();
+    let pending_ref = repo.find_reference(&request.pending_ref()).unwrap();
+    assert_eq!(pending_ref.target().unwrap().to_string(), request.converged_commit);
+
+    let summary = &request.summary;
+    assert_eq!(summary.diff.files, ["feature.txt"]);
+    assert_eq!((summary.diff.insertions, summary.diff.deletions), (2, 0));
+    let gates: Vec<_> = summary.gates.iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(gates, ["convergence", "red_threads", "blocking_threads"]);
+    assert!(summary.gates[0].passed);
+
+    let submitted = controller.action_history.last().unwrap();
+    assert_eq!(submitted.action_type, ActionType::ApprovalRequest);
+    assert_eq!(submitted.metadata["approval_id"], request.id.to_string());
+    assert_eq!(
+        controller.active_sessions["agent-1"].current_commit.as_deref(),
+        Some(base.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_approval_promotes_result() {
+    let (temp_dir, mut controller) =
+        setup_controller(|gate| gate.with_approvers(["reviewer@example.com", "agent-1"]));
+    let request = converge(&temp_dir, &mut controller).await;
+
+    // The agent cannot approve its own work, even as an approver
+    let err = controller.approve_promotion(request.id, "agent-1").unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { .. }));
+
+    let approved = controller
+        .approve_promotion(request.id, "reviewer@example.com")
+        .unwrap();
+    assert_eq!(approved.status, ApprovalStatus::Approved);
+    assert_eq!(approved.decided_by.as_deref(), Some("reviewer@example.com"));
+    assert_eq!(head(temp_dir.path()), request.converged_commit);
+    assert_eq!(fs::read_to_string(temp_dir.path().join("feature.txt")).unwrap(), "one\ntwo\n");
+    assert!(controller.pending_approvals().unwrap().is_empty());
+
+    let decision = controller.action_history.last().unwrap();
+    assert_eq!(decision.action_type, ActionType::ApprovalDecision);
+    assert_eq!(decision.metadata["decision"], "approved");
+    assert_eq!(decision.commit_after.as_deref(), Some(request.converged_commit.as_str()));
+
+    // Decisions are final
+    assert!(controller
+        .reject_promotion(request.id, "reviewer@example.com", "too late")
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_rejection_is_logged_with_reason() {
+    let (temp_dir, mut controller) = setup_controller(reviewer);
+    let base = head(temp_dir.path());
+    let request = converge(&temp_dir, &mut controller).await;
+
+    assert!(controller
+        .reject_promotion(request.id, "reviewer@example.com", "  ")
+        .is_err());
+    let rejected = controller
+        .reject_promotion(request.id, "reviewer@example.com", "Touches the public API")
+        .unwrap();
+    assert_eq!(rejected.status, ApprovalStatus::Rejected);
+    assert_eq!(head(temp_dir.path()), base);
+
+    let repo = Repository::open(temp_dir.path()).unwrap();
+    assert!(repo.find_reference(&request.pending_ref()).is_err());
+
+    let session_id = controller.active_sessions["agent-1"].session_id;
+    let decision = controller.action_history.last().unwrap();
+    assert_eq!(decision.session_id, Some(session_id));
+    assert_eq!(decision.metadata["decision"], "rejected");
+    assert_eq!(decision.metadata["reason"], "Touches the public API");
+    assert_eq!(decision.metadata["approver"], "reviewer@example.com");
+
+    let gate = controller.approval_gate.as_ref().unwrap();
+    assert_eq!(gate.list().unwrap(), [rejected]);
+}
+
+#[tokio::test]
+async fn test_concurrent_changes_keep_every_request() {
+    let (temp_dir, mut controller) = setup_controller(reviewer);
+    let request = converge(&temp_dir, &mut controller).await;
+    let base = controller
+        .create_spiral_checkpoint("agent-1", "base")
+        .await
+        .unwrap();
+    fs::write(temp_dir.path().join("parallel.txt"), "parallel\n").unwrap();
+    let converged = controller.workflow.create_commit_node("Parallel work").await.unwrap();
+    let gate = controller.approval_gate.clone().unwrap();
+
+    // Other agents submit while a reviewer decides on the first request
+    let submitters: Vec<_> = (0..8)
+        .map(|i| {
+            let (gate, base, converged) = (gate.clone(), base.clone(), converged.clone());
+            std::thread::spawn(move || {
+                let agent_id = format!("agent-{}", i + 2);
+                gate.submit(&agent_id, None, &base, &converged, &ConvergenceMetrics::default(), 0.0)
+                    .unwrap()
+            })
+        })
+        .collect();
+    gate.reject(request.id, "reviewer@example.com", "racing").unwrap();
+    let submitted: Vec<_> = submitters.into_iter().map(|s| s.join().unwrap()).collect();
+
+    assert_eq!(gate.list().unwrap().len(), 9);
+    assert_eq!(gate.get(request.id).unwrap().status, ApprovalStatus::Rejected);
+    let pending = gate.pending().unwrap();
+    assert_eq!(pending.len(), 8);
+    assert!(submitted.iter().all(|s| pending.iter().any(|p| p.id == s.id)));
+    assert!(!gate.path().with_extension("json.lock").exists());
+}
+
+#[tokio::test]
+async fn test_approvers_and_fast_forward_are_enforced() {
+    let (temp_dir, mut controller) = setup_controller(|gate| gate.with_approvers(["alice@example.com"]));
+    let request = converge(&temp_dir, &mut controller).await;
+
+    assert!(controller
+        .approve_promotion(request.id, "mallory@example.com")
+        .is_err());
+
+    // Without configured approvers nobody may decide
+    let unconfigured = ApprovalGate::for_repo(temp_dir.path()).unwrap();
+    assert!(unconfigured.approvers().is_empty());
+    assert!(!unconfigured.is_authorized("alice@example.com", &request));
+    assert!(!unconfigured.is_authorized("x", &request));
+
+    // Someone else moved the branch while the request was pending
+    fs::write(temp_dir.path().join("other.txt"), "other\n").unwrap();
+    let repo = Repository::open(temp_dir.path()).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("other.txt")).unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let sig = Signature::now("Test User", "test@example.com").unwrap();
+    let moved = repo
+        .commit(Some("HEAD"), &sig, &sig, "Concurrent change", &tree, &[&parent])
+        .unwrap();
+
+    let err = controller
+        .approve_promotion(request.id, "alice@example.com")
+        .unwrap_err();
+    assert!(err.to_string().contains("approval_fast_forward"), "{err}");
+    assert_eq!(head(temp_dir.path()), moved.to_string());
+    assert_eq!(controller.pending_approvals().unwrap().len(), 1);
+
+    // Approvers can also come from git config
+    repo.config()
+        .unwrap()
+        .set_multivar("gdk.approver", "^$", "bob@example.com")
+        .unwrap();
+    let gate = ApprovalGate::for_repo(temp_dir.path()).unwrap();
+    assert_eq!(gate.approvers(), ["bob@example.com"]);
+}
@@ -0,0 +1,179 @@
+//! Session budget tests for the GDK system
+//!
+//! These tests drive sessions against a scratch repository until a budget
+//! runs out:
+//! - Commit budgets stopping the infinite monkey loop
+//! - Revert budgets stopping checkpoint reverts and spirals
+//! - Wall-time and validator CPU budgets
+//! - Remaining budget reported in agent statistics
+
+mod common;
+
+use common::{head, setup_controller};
+use gdk::budget::{BudgetRemaining, BudgetResource, BudgetUsage, SessionBudget};
+use gdk::GdkError;
+
+#[tokio::test]
+async fn test_commit_budget_stops_spiral() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_commits: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        GdkError::BudgetExhausted { resource, limit, used, .. }
+            if resource == "commits" && *limit == 2.0 && *used == 2.0
+    ));
+    assert_eq!(err.category(), "budget");
+    assert!(!err.is_recoverable());
+    assert_eq!(controller.workflow.commit_history.len(), 2);
+
+    let stats = controller.get_agent_statistics("agent-1").unwrap();
+    assert_eq!(stats.spiral_attempts, 2);
+    assert_eq!(stats.budget_usage.commits, 2);
+    assert_eq!(stats.budget_remaining.commits, Some(0));
+    assert_eq!(stats.budget_remaining.reverts, None);
+}
+
+#[tokio::test]
+async fn test_revert_budget_stops_reverts() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_reverts: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    controller.create_spiral_checkpoint("agent-1", "first").await.unwrap();
+    controller.create_spiral_checkpoint("agent-1", "second").await.unwrap();
+    controller.revert_to_last_checkpoint("agent-1").await.unwrap();
+
+    let err = controller.revert_to_last_checkpoint("agent-1").await.unwrap_err();
+    assert!(matches!(err, GdkError::BudgetExhausted { ref resource, .. } if resource == "reverts"));
+
+    // The refused revert leaves its checkpoint in place
+    let session = &controller.active_sessions["agent-1"];
+    assert_eq!(session.revert_stack.len(), 1);
+    assert_eq!(session.budget_usage.reverts, 1);
+}
+
+#[tokio::test]
+async fn test_revert_budget_stops_spiral_at_its_start() {
+    let (temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_reverts: Some(0),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let start = head(temp_dir.path());
+
+    let err = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::BudgetExhausted { ref resource, .. } if resource == "reverts"));
+
+    // The failed attempt is undone without charging the exhausted budget
+    assert_eq!(controller.workflow.commit_history.len(), 1);
+    assert_eq!(head(temp_dir.path()), start);
+    assert_eq!(controller.active_sessions["agent-1"].budget_usage.reverts, 0);
+}
+
+#[tokio::test]
+async fn test_wall_time_and_validator_cpu_budgets() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .set_session_budget(
+            "agent-1",
+            SessionBudget {
+                max_wall_time_secs: Some(60),
+                max_validator_cpu_secs: Some(10.0),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let stats = controller.get_agent_statistics("agent-1").unwrap();
+    assert!(stats.budget_remaining.wall_time_secs.unwrap() > 0);
+    assert_eq!(stats.budget_remaining.validator_cpu_secs, Some(10.0));
+
+    controller
+        .active_sessions
+        .get_mut("agent-1")
+        .unwrap()
+        .budget_usage
+        .validator_cpu_secs = 12.5;
+    let err = controller
+        .validate_and_commit("agent-1", "Over budget")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        GdkError::BudgetExhausted { ref resource, used, .. } if resource == "validator_cpu_secs" && used == 12.5
+    ));
+    assert!(controller.workflow.commit_history.is_empty());
+
+    let session = controller.active_sessions.get_mut("agent-1").unwrap();
+    session.budget_usage.validator_cpu_secs = 0.0;
+    session.start_time -= 120;
+    session.last_activity -= 120;
+    let err = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::BudgetExhausted { ref resource, .. } if resource == "wall_time_secs"));
+    assert_eq!(controller.active_sessions["agent-1"].spiral_attempts, 0);
+}
+
+#[test]
+fn test_budget_arithmetic() {
+    let budget = SessionBudget {
+        max_wall_time_secs: Some(100),
+        max_validator_cpu_secs: Some(5.0),
+        max_commits: Some(3),
+        max_reverts: None,
+    };
+    let usage = BudgetUsage {
+        validator_cpu_secs: 7.5,
+        commits: 1,
+        reverts: 40,
+    };
+
+    assert_eq!(
+        budget.remaining(&usage, 30),
+        BudgetRemaining {
+            wall_time_secs: Some(70),
+            validator_cpu_secs: Some(0.0),
+            commits: Some(2),
+            reverts: None,
+        }
+    );
+
+    let overrun = budget.overrun(&usage, 30, Some(BudgetResource::Commits)).unwrap();
+    assert_eq!(overrun.resource, BudgetResource::ValidatorCpu);
+    assert!(budget.overrun(&BudgetUsage::default(), 30, Some(BudgetResource::Reverts)).is_none());
+    assert!(SessionBudget::default().is_unlimited());
+}
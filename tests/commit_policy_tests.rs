@@ -0,0 +1,163 @@
+//! Commit policy tests for the GDK system
+//!
+//! These tests check the limits applied before a commit is written:
+//! - Path and branch pattern matching
+//! - Protected paths rejecting the commit and naming the files
+//! - Protected branches and diff size limits
+//! - Policies loaded from the repository's policy file
+
+mod common;
+
+use common::head;
+use gdk::core::GitWorkflowManager;
+use gdk::policy::{AccessPolicy, CommitPolicy, StagedFile};
+use gdk::{GdkError, GitWorkflow};
+use git2::{Repository, Status};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn setup_manager(policy: CommitPolicy) -> (TempDir, GitWorkflowManager) {
+    let (temp_dir, manager) = common::setup_manager();
+    (temp_dir, manager.with_commit_policy(policy))
+}
+
+fn staged(path: &str, insertions: usize) -> StagedFile {
+    StagedFile {
+        path: path.to_string(),
+        insertions,
+        deletions: 0,
+    }
+}
+
+#[test]
+fn test_pattern_matching() {
+    let policy = CommitPolicy::default()
+        .with_protected_path(".github/")
+        .with_protected_path("Cargo.lock")
+        .with_protected_path("LICENSE*")
+        .with_protected_path("docs/**/*.md")
+        .with_protected_branch("main")
+        .with_protected_branch("release/*");
+
+    assert!(policy.is_protected_path(".github/workflows/ci.yml"));
+    assert!(policy.is_protected_path("Cargo.lock"));
+    assert!(policy.is_protected_path("crates/core/Cargo.lock"));
+    assert!(policy.is_protected_path("LICENSE-MIT"));
+    assert!(policy.is_protected_path("docs/guide.md"));
+    assert!(policy.is_protected_path("docs/api/v1/index.md"));
+    assert!(!policy.is_protected_path("src/github.rs"));
+    assert!(!policy.is_protected_path("Cargo.toml"));
+    assert!(!policy.is_protected_path("src/docs/guide.md"));
+
+    assert!(policy.is_protected_branch("main"));
+    assert!(policy.is_protected_branch("release/1.0"));
+    assert!(!policy.is_protected_branch("release/1.0/hotfix"));
+    assert!(!policy.is_protected_branch("spiral-1a2b3c4d"));
+
+    assert!(CommitPolicy::default().is_empty());
+    assert!(CommitPolicy::default().check("main", &[staged("Cargo.lock", 10_000)]).is_ok());
+}
+
+#[tokio::test]
+async fn test_protected_paths_reject_commit() {
+    let policy = CommitPolicy::default()
+        .with_protected_path(".github/")
+        .with_protected_path("Cargo.lock");
+    let (temp_dir, mut manager) = setup_manager(policy);
+    let base = head(temp_dir.path());
+
+    fs::create_dir_all(temp_dir.path().join(".github/workflows")).unwrap();
+    fs::write(temp_dir.path().join(".github/workflows/ci.yml"), "on: push\n").unwrap();
+    fs::write(temp_dir.path().join("Cargo.lock"), "version = 3\n").unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "fine\n").unwrap();
+
+    let err = manager.create_commit_node("Sneaky").await.unwrap_err();
+    assert!(matches!(err, GdkError::ValidationError { ref rule, .. } if rule == "commit_policy"));
+    assert_eq!(
+        err.to_string(),
+        "Validation failed for commit_policy: protected paths changed (.github/workflows/ci.yml, Cargo.lock)"
+    );
+
+    // Nothing was committed or left staged; the edits stay in the working tree
+    assert_eq!(head(temp_dir.path()), base);
+    assert!(manager.commit_history.is_empty());
+    let repo = Repository::open(temp_dir.path()).unwrap();
+    assert_eq!(repo.status_file(Path::new("Cargo.lock")).unwrap(), Status::WT_NEW);
+    assert!(temp_dir.path().join(".github/workflows/ci.yml").exists());
+
+    // Dropping the protected changes lets the rest through
+    fs::remove_dir_all(temp_dir.path().join(".github")).unwrap();
+    fs::remove_file(temp_dir.path().join("Cargo.lock")).unwrap();
+    let node = manager.create_commit_node("Notes").await.unwrap();
+    assert_eq!(node.parent_hashes, [base]);
+}
+
+#[tokio::test]
+async fn test_protected_branch_and_diff_size() {
+    let (temp_dir, mut manager) = setup_manager(CommitPolicy::default());
+    let branch = Repository::open(temp_dir.path())
+        .unwrap()
+        .head()
+        .unwrap()
+        .shorthand()
+        .unwrap()
+        .to_string();
+
+    manager.commit_policy = CommitPolicy::default().with_protected_branch(&branch);
+    fs::write(temp_dir.path().join("feature.txt"), "feature\n").unwrap();
+    let err = manager.create_commit_node("Direct push").await.unwrap_err();
+    assert!(err.to_string().contains(&format!("branch '{branch}' is protected (feature.txt)")), "{err}");
+
+    // Spiral branches are fair game
+    let base = head(temp_dir.path());
+    manager.create_spiral_branch(&base).await.unwrap();
+    fs::write(temp_dir.path().join("feature.txt"), "feature\n").unwrap();
+    manager.create_commit_node("On a spiral").await.unwrap();
+
+    manager.commit_policy = CommitPolicy::default().with_max_diff_lines(5);
+    fs::write(temp_dir.path().join("big.txt"), "line\n".repeat(6)).unwrap();
+    fs::write(temp_dir.path().join("small.txt"), "line\n").unwrap();
+    let err = manager.create_commit_node("Too big").await.unwrap_err();
+    assert!(
+        err.to_string().ends_with("diff of 7 lines exceeds 5 (big.txt: 6, small.txt: 1)"),
+        "{err}"
+    );
+
+    fs::write(temp_dir.path().join("big.txt"), "line\n".repeat(4)).unwrap();
+    manager.create_commit_node("Small enough").await.unwrap();
+}
+
+#[test]
+fn test_policy_loaded_from_repository() {
+    let (temp_dir, _manager) = setup_manager(CommitPolicy::default());
+    assert!(CommitPolicy::for_repo(temp_dir.path()).unwrap().is_empty());
+
+    let path = AccessPolicy::repo_path(temp_dir.path()).unwrap();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(
+        &path,
+        r#"{
+            "default_role": "contributor",
+            "commits": {
+                "protected_paths": [".github/", "Cargo.lock"],
+                "protected_branches": ["main"],
+                "max_diff_lines": 500
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let policy = CommitPolicy::for_repo(temp_dir.path()).unwrap();
+    assert_eq!(policy.protected_paths, [".github/", "Cargo.lock"]);
+    assert_eq!(policy.protected_branches, ["main"]);
+    assert_eq!(policy.max_diff_lines, Some(500));
+
+    // The access policy in the same file is unaffected
+    let access = AccessPolicy::for_repo(temp_dir.path()).unwrap();
+    assert_eq!(access.default_role, Some(gdk::policy::Role::Contributor));
+
+    fs::write(&path, r#"{ "commits": { "max_diff_lines": "lots" } }"#).unwrap();
+    let err = CommitPolicy::for_repo(temp_dir.path()).unwrap_err();
+    assert!(matches!(err, GdkError::SerializationError { .. }));
+}
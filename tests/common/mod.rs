@@ -0,0 +1,54 @@
+//! Fixtures shared by the GDK integration test crates
+//!
+//! Each test crate compiles this module separately and uses only some of
+//! it, hence the `dead_code` allowance.
+
+#![allow(dead_code)]
+
+use gdk::agent::AgentWorkflowController;
+use gdk::core::GitWorkflowManager;
+use git2::{Repository, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Scratch repository with a committed README
+pub fn setup_repo() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = Repository::init(temp_dir.path()).unwrap();
+    fs::write(temp_dir.path().join("README.md"), "scratch\n").unwrap();
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("README.md")).unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("Test User", "test@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+        .unwrap();
+
+    temp_dir
+}
+
+/// Workflow manager on a fresh scratch repository
+pub fn setup_manager() -> (TempDir, GitWorkflowManager) {
+    let temp_dir = setup_repo();
+    let manager = GitWorkflowManager::new(temp_dir.path().to_str().unwrap()).unwrap();
+    (temp_dir, manager)
+}
+
+/// Agent controller on a fresh scratch repository
+pub fn setup_controller() -> (TempDir, AgentWorkflowController<GitWorkflowManager>) {
+    let (temp_dir, manager) = setup_manager();
+    (temp_dir, AgentWorkflowController::new(manager))
+}
+
+/// Commit the repository's HEAD points at
+pub fn head(path: &Path) -> String {
+    Repository::open(path)
+        .unwrap()
+        .head()
+        .unwrap()
+        .peel_to_commit()
+        .unwrap()
+        .id()
+        .to_string()
+}
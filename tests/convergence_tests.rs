@@ -0,0 +1,392 @@
+//! Convergence analysis tests for the GDK system
+//!
+//! These tests drive the analyzer with synthetic commit histories:
+//! - Change-point detection on shifted quality levels
+//! - Plateau (stall) detection with a patience window
+//! - Trend forecasts with confidence bounds and unavailability reasons
+//! - Pareto front tracking and per-dimension floors
+//! - Per-file thread convergence with per-path thresholds
+//! - Branch lineages and sibling comparisons over the commit DAG
+
+use gdk::convergence::{ConvergenceAnalyzer, ParetoConfig, ShiftDirection};
+use gdk::forecast::{ForecastConfig, ForecastUnavailable, Forecaster};
+use gdk::lineage::CommitGraph;
+use gdk::quality_metrics::{QualityDimensions, QualityMetricsAnalyzer};
+use gdk::threads::{ThreadConvergenceRules, ThreadThreshold};
+use gdk::{CommitNode, ConvergenceMetrics, FileThread, ThreadColor};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Build a chronological history with the given health scores
+fn history(scores: &[f64]) -> Vec<CommitNode> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(i, &score)| CommitNode {
+            id: format!("node-{i}"),
+            hash: format!("{i:040x}"),
+            parent_hashes: if i == 0 { vec![] } else { vec![format!("{:040x}", i - 1)] },
+            message: format!("Commit {i}"),
+            timestamp: 1_700_000_000 + i as u64,
+            file_threads: HashMap::new(),
+            health_score: score,
+            convergence_metrics: ConvergenceMetrics {
+                attempts: 1,
+                successful_builds: 1,
+                test_pass_rate: score,
+                quality_trend: vec![score],
+                ..Default::default()
+            },
+        })
+        .collect()
+}
+
+#[test]
+fn test_change_point_detects_recent_regression() {
+    // Long upward trend followed by a sharp drop
+    let commits = history(&[0.5, 0.55, 0.6, 0.65, 0.7, 0.75, 0.8, 0.85, 0.5, 0.48, 0.5]);
+    let analyzer = ConvergenceAnalyzer::new();
+
+    let change = analyzer.detect_change_point(&commits).expect("change point");
+    assert_eq!(change.direction, ShiftDirection::Regression);
+    assert_eq!(change.index, 8);
+    assert_eq!(change.commit_hash, commits[8].hash);
+    assert!(change.mean_before > change.mean_after);
+
+    // The older upward trend no longer counts as improvement
+    let result = analyzer.analyze_convergence(&commits).unwrap();
+    assert_eq!(result.change_point, Some(change));
+    assert_eq!(result.convergence_factors.trend_improvement, 0.0);
+    assert!(result.recommendations[0].contains("regressed"));
+}
+
+#[test]
+fn test_change_point_absent_for_stable_history() {
+    let commits = history(&[0.82, 0.83, 0.81, 0.82, 0.83, 0.82]);
+    assert_eq!(ConvergenceAnalyzer::new().detect_change_point(&commits), None);
+}
+
+#[test]
+fn test_plateau_detection_uses_patience() {
+    let analyzer = ConvergenceAnalyzer {
+        plateau_patience: 3,
+        ..ConvergenceAnalyzer::new()
+    };
+
+    let improving = history(&[0.3, 0.4, 0.5, 0.6]);
+    let plateau = analyzer.detect_plateau(&improving);
+    assert!(!plateau.is_stalled);
+    assert_eq!(plateau.commits_since_improvement, 0);
+
+    // Gains below the minimum improvement do not reset the patience counter
+    let stalled = history(&[0.3, 0.5, 0.505, 0.49, 0.5]);
+    let plateau = analyzer.detect_plateau(&stalled);
+    assert!(plateau.is_stalled);
+    assert_eq!(plateau.commits_since_improvement, 3);
+    assert_eq!(plateau.best_score, 0.505);
+
+    let result = analyzer.analyze_convergence(&stalled).unwrap();
+    assert!(result.plateau.is_stalled);
+    assert!(result.recommendations[0].contains("plateaued"));
+}
+
+#[test]
+fn test_plateau_is_measured_from_spiral_start() {
+    let analyzer = ConvergenceAnalyzer {
+        plateau_patience: 3,
+        ..ConvergenceAnalyzer::new()
+    };
+
+    // An earlier spiral reached 0.9; the new one starts lower at commit 2
+    let commits = history(&[0.6, 0.9, 0.5, 0.5, 0.5]);
+    let tip = &commits[4].hash;
+    assert!(analyzer.analyze_branch(&commits, tip).unwrap().plateau.is_stalled);
+
+    let plateau = analyzer.analyze_spiral(&commits, tip, 2).unwrap().plateau;
+    assert!(!plateau.is_stalled);
+    assert_eq!(plateau.best_score, 0.5);
+    assert_eq!(plateau.commits_since_improvement, 2);
+}
+
+#[test]
+fn test_forecast_intervals_use_exact_small_sample_quantiles() {
+    // Student-t quantiles at 90% for 1, 2 and 8 degrees of freedom
+    for (scores, expected_t) in [
+        (vec![0.5, 0.6, 0.8], 6.3138),
+        (vec![0.5, 0.6, 0.8, 0.75], 2.9200),
+        (vec![0.5, 0.6, 0.8, 0.75, 0.7, 0.9, 0.85, 0.8, 0.95, 0.9], 1.8595),
+    ] {
+        let forecaster = Forecaster::with_config(ForecastConfig {
+            min_points: 3,
+            ..ForecastConfig::default()
+        });
+        let model = forecaster.fit(&scores).unwrap();
+        let interval = model.slope_interval();
+        let sxx: f64 = {
+            let mean = (scores.len() - 1) as f64 / 2.0;
+            (0..scores.len()).map(|i| (i as f64 - mean).powi(2)).sum()
+        };
+        let t = (interval.upper - interval.lower) / 2.0 * sxx.sqrt() / model.residual_std;
+        assert!((t / expected_t - 1.0).abs() < 0.01, "t = {t}, expected {expected_t}");
+    }
+}
+
+#[test]
+fn test_forecast_exact_trend() {
+    let model = Forecaster::new().fit(&[0.1, 0.2, 0.3, 0.4, 0.5]).unwrap();
+    assert!((model.slope - 0.1).abs() < 1e-9);
+    assert!(model.residual_std < 1e-9);
+
+    let next = model.predict(2);
+    assert!((next.expected - 0.7).abs() < 1e-9);
+    assert!((next.interval.upper - next.interval.lower).abs() < 1e-9);
+
+    let forecast = model.iterations_to(0.8, 100).unwrap();
+    assert_eq!(forecast.expected_iterations, 3);
+    assert_eq!(forecast.iterations_lower, 3);
+    assert_eq!(forecast.iterations_upper, Some(3));
+}
+
+#[test]
+fn test_convergence_forecast_bounds_noisy_history() {
+    let commits = history(&[0.40, 0.47, 0.45, 0.53, 0.52, 0.58, 0.61, 0.60, 0.66]);
+    let forecast = ConvergenceAnalyzer::new()
+        .predict_convergence_time(&commits)
+        .unwrap();
+
+    assert!(forecast.expected_iterations > 0);
+    assert!(forecast.iterations_lower <= forecast.expected_iterations);
+    assert!(forecast.iterations_upper.is_none_or(|upper| upper >= forecast.expected_iterations));
+    let quality = forecast.expected_quality;
+    assert!(quality.interval.lower < quality.expected && quality.expected < quality.interval.upper);
+}
+
+#[test]
+fn test_forecast_reports_unavailability() {
+    let analyzer = ConvergenceAnalyzer::new();
+
+    assert_eq!(
+        analyzer.predict_convergence_time(&history(&[0.4, 0.5])),
+        Err(ForecastUnavailable::InsufficientHistory { required: 4, available: 2 })
+    );
+    assert!(matches!(
+        analyzer.predict_convergence_time(&history(&[0.6, 0.58, 0.57, 0.55, 0.54])),
+        Err(ForecastUnavailable::NoImprovement { slope }) if slope < 0.0
+    ));
+    assert!(matches!(
+        analyzer.predict_convergence_time(&history(&[0.1, 0.101, 0.102, 0.103, 0.104])),
+        Err(ForecastUnavailable::BeyondHorizon { max_horizon: 100, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_quality_prediction_fitted_on_history() {
+    let mut analyzer = QualityMetricsAnalyzer::with_default_config();
+    let commits = history(&[0.5, 0.6, 0.7, 0.8]);
+
+    let first = analyzer.analyze_commit_quality(&commits[0]).await.unwrap();
+    assert!(first.predictions.is_none());
+    assert!(matches!(
+        first.prediction_unavailable,
+        Some(ForecastUnavailable::InsufficientHistory { available: 1, .. })
+    ));
+
+    let mut last = first;
+    for commit in &commits[1..] {
+        last = analyzer.analyze_commit_quality(commit).await.unwrap();
+    }
+    let prediction = last.predictions.expect("prediction after enough history");
+    assert_eq!(last.prediction_unavailable, None);
+    assert_eq!(
+        prediction.forecasts.iter().map(|f| f.steps_ahead).collect::<Vec<_>>(),
+        vec![1, 5, 20]
+    );
+    assert_eq!(prediction.confidence, 0.9);
+}
+
+/// File thread with (lint, type_check, coverage, functionality) scores
+fn file_thread(path: &str, (lint, type_check, coverage, functionality): (f64, f64, f64, f64)) -> FileThread {
+    FileThread {
+        file_path: path.to_string(),
+        thread_id: Uuid::new_v4(),
+        color_status: ThreadColor::from_scores(lint, type_check, coverage, functionality),
+        lint_score: lint,
+        type_check_score: type_check,
+        test_coverage: coverage,
+        functionality_score: functionality,
+        history: Vec::new(),
+    }
+}
+
+/// Build a history where each commit has one `src/lib.rs` thread with the given scores
+fn threaded_history(scores: &[(f64, f64, f64, f64)]) -> Vec<CommitNode> {
+    let mut commits = history(&vec![0.8; scores.len()]);
+    for (commit, &thread_scores) in commits.iter_mut().zip(scores) {
+        commit
+            .file_threads
+            .insert("src/lib.rs".to_string(), file_thread("src/lib.rs", thread_scores));
+    }
+    commits
+}
+
+#[test]
+fn test_pareto_front_and_floors() {
+    let commits = threaded_history(&[
+        (0.8, 0.8, 0.8, 0.8),
+        (0.9, 0.9, 0.5, 0.9), // trades coverage for everything else
+        (0.7, 0.7, 0.7, 0.7),
+        (0.8, 0.8, 0.8, 0.8), // repeats attempt 0
+        (0.85, 0.85, 0.85, 0.85),
+        (0.85, 0.85, 0.85, 0.85),
+        (0.85, 0.85, 0.85, 0.85),
+    ]);
+    let analyzer = ConvergenceAnalyzer {
+        pareto: Some(ParetoConfig {
+            floors: QualityDimensions::uniform(0.8),
+            stability_window: 2,
+        }),
+        ..ConvergenceAnalyzer::new()
+    };
+
+    let result = analyzer.analyze_convergence(&commits).unwrap();
+    let pareto = result.pareto.expect("pareto analysis");
+    let front: Vec<usize> = pareto.front.iter().map(|p| p.index).collect();
+    assert_eq!(front, vec![1, 4]);
+    let dominated: Vec<usize> = pareto.dominated.iter().map(|d| d.index).collect();
+    assert_eq!(dominated, vec![0, 2, 3, 5, 6]);
+    assert_eq!(pareto.dominated[0].dominated_by, commits[4].hash);
+    assert_eq!(pareto.attempts_since_front_changed, 2);
+    assert!(pareto.front_stable);
+    assert!(pareto.floor_violations.is_empty());
+    assert!(result.is_converged);
+
+    // Not converged while the front is still moving
+    let moving = analyzer.analyze_convergence(&commits[..5]).unwrap();
+    assert!(!moving.pareto.unwrap().front_stable);
+    assert!(!moving.is_converged);
+
+    // Not converged while any dimension is below its floor
+    let strict = ConvergenceAnalyzer {
+        pareto: Some(ParetoConfig {
+            floors: QualityDimensions {
+                security: 0.9,
+                ..QualityDimensions::uniform(0.8)
+            },
+            stability_window: 2,
+        }),
+        ..ConvergenceAnalyzer::new()
+    };
+    let result = strict.analyze_convergence(&commits).unwrap();
+    let violations = result.pareto.unwrap().floor_violations;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].dimension, "security");
+    assert!(!result.is_converged);
+}
+
+#[test]
+fn test_thread_convergence_uses_path_thresholds() {
+    let mut commits = history(&[0.8, 0.8, 0.8]);
+    for commit in &mut commits {
+        for (path, score) in [("src/core.rs", 0.85), ("examples/demo.rs", 0.6), ("README.md", 0.9)] {
+            commit
+                .file_threads
+                .insert(path.to_string(), file_thread(path, (score, score, score, score)));
+        }
+    }
+
+    let rules = ThreadConvergenceRules::default()
+        .with_path_threshold("src/", ThreadThreshold { min_quality: 0.9, ..Default::default() })
+        .with_path_threshold("examples/", ThreadThreshold { min_quality: 0.5, ..Default::default() });
+    let analyzer = ConvergenceAnalyzer {
+        thread_rules: rules,
+        ..ConvergenceAnalyzer::new()
+    };
+
+    let summary = analyzer.analyze_thread_convergence(&commits);
+    assert_eq!(summary.converged_files, vec!["README.md", "examples/demo.rs"]);
+    assert_eq!(summary.blocking_files.len(), 1);
+    assert_eq!(summary.blocking_files[0].file_path, "src/core.rs");
+    assert_eq!(summary.blocking_files[0].threshold.min_quality, 0.9);
+    assert!((summary.converged_share - 2.0 / 3.0).abs() < 1e-9);
+
+    let result = analyzer.analyze_convergence(&commits).unwrap();
+    assert_eq!(result.thread_convergence, summary);
+    assert!(!result.is_converged);
+    assert!(result.recommendations.iter().any(|r| r.contains("src/core.rs")));
+}
+
+#[test]
+fn test_thread_convergence_needs_a_full_window() {
+    let mut commits = history(&[0.9, 0.9, 0.9]);
+    for (i, commit) in commits.iter_mut().enumerate() {
+        commit
+            .file_threads
+            .insert("src/lib.rs".to_string(), file_thread("src/lib.rs", (0.95, 0.95, 0.95, 0.95)));
+        if i == 2 {
+            commit
+                .file_threads
+                .insert("src/new.rs".to_string(), file_thread("src/new.rs", (1.0, 1.0, 1.0, 1.0)));
+        }
+    }
+
+    // One perfect score is not a converged thread
+    let summary = ConvergenceAnalyzer::new().analyze_thread_convergence(&commits);
+    assert_eq!(summary.converged_files, ["src/lib.rs"]);
+    assert_eq!(summary.blocking_files[0].file_path, "src/new.rs");
+    assert!(!ThreadThreshold::default().is_converged(&[1.0, 1.0]));
+    assert!(ThreadThreshold::default().is_converged(&[0.5, 1.0, 1.0, 1.0]));
+}
+
+/// Base chain 0-1-2 with two interleaved spiral branches off commit 2:
+/// A = 3, 5 (improving) and B = 4, 6 (regressing)
+fn spiral_history() -> Vec<CommitNode> {
+    let mut commits = history(&[0.5, 0.55, 0.6, 0.62, 0.5, 0.64, 0.45]);
+    for (child, parent) in [(3, 2), (4, 2), (5, 3), (6, 4)] {
+        commits[child].parent_hashes = vec![commits[parent].hash.clone()];
+    }
+    commits
+}
+
+#[test]
+fn test_commit_graph_lineages_and_siblings() {
+    let commits = spiral_history();
+    let graph = CommitGraph::new(&commits);
+    let hashes = |nodes: Vec<&CommitNode>| nodes.into_iter().map(|c| c.hash.clone()).collect::<Vec<_>>();
+
+    assert_eq!(
+        hashes(graph.latest_lineage()),
+        hashes([0, 1, 2, 4, 6].iter().map(|&i| &commits[i]).collect())
+    );
+    assert_eq!(hashes(graph.tips()), vec![commits[5].hash.clone(), commits[6].hash.clone()]);
+
+    let siblings = graph.compare_siblings();
+    assert_eq!(siblings.len(), 1);
+    let fork = &siblings[0];
+    assert_eq!(fork.base_hash, commits[2].hash);
+    assert_eq!(fork.branches.len(), 2);
+    assert_eq!(fork.branches[0].commits_since_base, 2);
+    assert!((fork.branches[0].delta_from_base - 0.04).abs() < 1e-9);
+    assert!(fork.branches[1].delta_from_base < 0.0);
+    assert_eq!(fork.best_tip.as_deref(), Some(commits[5].hash.as_str()));
+}
+
+#[test]
+fn test_branch_analysis_separates_interleaved_spirals() {
+    let commits = spiral_history();
+    let analyzer = ConvergenceAnalyzer::new();
+
+    let branches = analyzer.analyze_branches(&commits).unwrap();
+    assert_eq!(branches.len(), 2);
+    assert_eq!(branches[0].tip_hash, commits[5].hash);
+    assert_eq!(branches[0].lineage_length, 5);
+    let improving = &branches[0].result.convergence_factors;
+    let regressing = &branches[1].result.convergence_factors;
+    assert!(improving.trend_improvement > regressing.trend_improvement);
+
+    // The default analysis follows the latest commit's lineage (branch B)
+    let latest = analyzer.analyze_convergence(&commits).unwrap();
+    assert_eq!(latest.convergence_factors, *regressing);
+    assert_eq!(latest.siblings.len(), 1);
+
+    assert!(analyzer.analyze_branch(&commits, "unknown").is_err());
+}
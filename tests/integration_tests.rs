@@ -7,7 +7,8 @@
 //! - Visualization generation
 //! - Error handling and recovery
 
-use gdk::core::GitWorkflowManager;
+use gdk::convergence::{ConvergenceAnalyzer, ConvergencePolicy, ConvergenceResult};
+use gdk::core::{GitWorkflowManager, SPIRAL_START_REASON};
 use gdk::{CommitNode, ThreadColor, GdkResult, GitWorkflow};
 use std::fs;
 use tempfile::TempDir;
@@ -156,6 +157,25 @@ async fn test_revert_point_creation() -> GdkResult<()> {
     Ok(())
 }
 
+#[test]
+async fn test_spiral_start_point_scopes_plateau_detection() -> GdkResult<()> {
+    let (_temp_dir, mut manager) = setup_test_repo().await?;
+
+    manager.create_commit_node("Earlier spiral").await?;
+    manager.create_revert_point("Test checkpoint").await?;
+    assert_eq!(manager.spiral_start, 0);
+
+    // The reason is only a label; starting a spiral is explicit
+    manager.create_revert_point(SPIRAL_START_REASON).await?;
+    assert_eq!(manager.spiral_start, 0);
+
+    let start = manager.begin_spiral().await?;
+    assert_eq!(start.metadata.reason, SPIRAL_START_REASON);
+    assert_eq!(manager.spiral_start, 1);
+
+    Ok(())
+}
+
 #[test]
 async fn test_thread_color_scoring() {
     // Test thread color calculation from scores
@@ -191,6 +211,61 @@ async fn test_convergence_analysis() -> GdkResult<()> {
     Ok(())
 }
 
+/// Policy that converges once the history reaches a fixed length
+#[derive(Debug)]
+struct MinimumCommits(usize);
+
+impl ConvergencePolicy for MinimumCommits {
+    fn evaluate(&self, commit_history: &[CommitNode]) -> GdkResult<ConvergenceResult> {
+        let mut result = ConvergenceAnalyzer::new().evaluate(commit_history)?;
+        result.is_converged = commit_history.len() >= self.0;
+        result.recommendations = vec![format!("needs {} commits", self.0)];
+        Ok(result)
+    }
+}
+
+#[test]
+async fn test_convergence_policy_reports_analyzer_details() -> GdkResult<()> {
+    let (_temp_dir, mut manager) = setup_test_repo().await?;
+    manager.create_commit_node("Commit 1").await?;
+
+    // Default policy is the weighted analyzer
+    let convergence = manager.analyze_convergence().await?;
+    let expected = ConvergenceAnalyzer::new().analyze_convergence(&manager.commit_history)?;
+    assert_eq!(convergence.is_converged, expected.is_converged);
+    assert_eq!(convergence.confidence_score, expected.confidence_score);
+    assert_eq!(convergence.convergence_factors, Some(expected.convergence_factors));
+    assert_eq!(convergence.recommendations, expected.recommendations);
+
+    // Custom policies replace the decision
+    let mut manager = manager.with_convergence_policy(MinimumCommits(2));
+    assert!(!manager.analyze_convergence().await?.is_converged);
+    manager.create_commit_node("Commit 2").await?;
+    let convergence = manager.analyze_convergence().await?;
+    assert!(convergence.is_converged);
+    assert_eq!(convergence.recommendations, vec!["needs 2 commits".to_string()]);
+
+    Ok(())
+}
+
+#[test]
+async fn test_convergence_follows_head_lineage() -> GdkResult<()> {
+    let (_temp_dir, mut manager) = setup_test_repo().await?;
+
+    manager.create_commit_node("Base").await?;
+    let base = manager.create_revert_point("fork").await?;
+    manager.create_commit_node("Attempt A").await?;
+    manager.revert_to_point(&base).await?;
+    manager.create_commit_node("Attempt B").await?;
+
+    // Attempt A is a sibling branch and must not appear in HEAD's trend
+    let convergence = manager.analyze_convergence().await?;
+    assert_eq!(convergence.attempts, 3);
+    assert_eq!(convergence.quality_trend.len(), 2);
+
+    Ok(())
+}
+
 #[test]
 async fn test_spiral_branching() -> GdkResult<()> {
     let (_temp_dir, mut manager) = setup_test_repo().await?;
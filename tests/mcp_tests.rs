@@ -0,0 +1,158 @@
+//! Model Context Protocol server tests for the GDK system
+//!
+//! These tests speak MCP to the server the way an agent client would:
+//! - Initialize handshake and tool listing with input schemas
+//! - Tool calls for checkpoints, thread colors and tree rendering
+//! - Tool failures reported as error results rather than protocol errors
+//! - Thread and convergence resources
+
+mod common;
+
+use common::setup_controller;
+use gdk::mcp::{McpServer, DEFAULT_AGENT_ID, PROTOCOL_VERSION};
+use gdk::rpc::INVALID_PARAMS;
+use gdk::{CommitNode, ConvergenceMetrics, FileThread, ThreadColor};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tempfile::TempDir;
+use tokio::sync::mpsc::unbounded_channel;
+use uuid::Uuid;
+
+fn setup_server() -> (TempDir, McpServer) {
+    let (temp_dir, controller) = setup_controller();
+    (temp_dir, McpServer::new(controller))
+}
+
+fn thread(path: &str, color: ThreadColor, score: f64) -> FileThread {
+    FileThread {
+        file_path: path.to_string(),
+        thread_id: Uuid::new_v4(),
+        color_status: color,
+        lint_score: score,
+        type_check_score: score,
+        test_coverage: score,
+        functionality_score: score,
+        history: Vec::new(),
+    }
+}
+
+fn record_commit(server: &mut McpServer, index: usize, threads: Vec<FileThread>) {
+    server.controller.workflow.commit_history.push(CommitNode {
+        id: format!("node-{index}"),
+        hash: format!("{index:040x}"),
+        parent_hashes: Vec::new(),
+        message: format!("Commit {index}"),
+        timestamp: 1_700_000_000 + index as u64,
+        file_threads: threads
+            .into_iter()
+            .map(|t| (t.file_path.clone(), t))
+            .collect::<HashMap<_, _>>(),
+        health_score: 0.6,
+        convergence_metrics: ConvergenceMetrics::default(),
+    });
+}
+
+async fn call(server: &mut McpServer, id: u64, method: &str, params: Value) -> Value {
+    let (tx, _rx) = unbounded_channel();
+    let request = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+    let reply = server.handle_line(&request.to_string(), &tx).await.unwrap();
+    serde_json::from_str(&reply).unwrap()
+}
+
+fn tool_text(reply: &Value) -> &str {
+    reply["result"]["content"][0]["text"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_initialize_and_list_tools() {
+    let (_temp_dir, mut server) = setup_server();
+
+    let reply = call(&mut server, 1, "initialize", json!({"protocolVersion": PROTOCOL_VERSION, "capabilities": {}, "clientInfo": {"name": "test", "version": "0"}})).await;
+    assert_eq!(reply["result"]["protocolVersion"], PROTOCOL_VERSION);
+    assert!(reply["result"]["capabilities"]["tools"].is_object());
+    assert!(reply["result"]["capabilities"]["resources"].is_object());
+
+    let reply = call(&mut server, 2, "tools/list", json!({})).await;
+    let tools = reply["result"]["tools"].as_array().unwrap();
+    let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert_eq!(
+        names,
+        ["gdk_commit", "gdk_checkpoint", "gdk_revert", "gdk_validate", "gdk_thread_colors", "gdk_render_tree"]
+    );
+    assert!(tools.iter().all(|t| t["inputSchema"]["type"] == "object"));
+    assert_eq!(tools[0]["inputSchema"]["required"], json!(["message"]));
+}
+
+#[tokio::test]
+async fn test_checkpoint_and_revert_tools_start_a_session() {
+    let (_temp_dir, mut server) = setup_server();
+
+    let reply = call(&mut server, 1, "tools/call", json!({"name": "gdk_checkpoint", "arguments": {"reason": "safe point"}})).await;
+    assert_eq!(reply["result"]["isError"], false);
+    let point: Value = serde_json::from_str(tool_text(&reply)).unwrap();
+    assert_eq!(point["metadata"]["reason"], "safe point");
+    assert!(server.controller.active_sessions.contains_key(DEFAULT_AGENT_ID));
+
+    let reply = call(&mut server, 2, "tools/call", json!({"name": "gdk_revert", "arguments": {}})).await;
+    assert_eq!(reply["result"]["isError"], false);
+
+    // The checkpoint was consumed, so a second revert fails as a tool error
+    let reply = call(&mut server, 3, "tools/call", json!({"name": "gdk_revert", "arguments": {}})).await;
+    assert_eq!(reply["result"]["isError"], true);
+}
+
+#[tokio::test]
+async fn test_thread_colors_and_tree_rendering() {
+    let (_temp_dir, mut server) = setup_server();
+    record_commit(&mut server, 1, vec![thread("src/lib.rs", ThreadColor::Red, 0.2), thread("src/main.rs", ThreadColor::Yellow, 0.6)]);
+    record_commit(&mut server, 2, vec![thread("src/lib.rs", ThreadColor::Green, 0.95)]);
+
+    let reply = call(&mut server, 1, "tools/call", json!({"name": "gdk_thread_colors", "arguments": {"files": ["src/lib.rs"]}})).await;
+    let colors: Value = serde_json::from_str(tool_text(&reply)).unwrap();
+    assert_eq!(colors, json!({"src/lib.rs": {
+        "color": "Green",
+        "lint_score": 0.95,
+        "type_check_score": 0.95,
+        "test_coverage": 0.95,
+        "functionality_score": 0.95,
+    }}));
+
+    let reply = call(&mut server, 2, "tools/call", json!({"name": "gdk_render_tree", "arguments": {"format": "html"}})).await;
+    assert_eq!(reply["result"]["isError"], false);
+    assert!(tool_text(&reply).contains("Total commits: 2"));
+
+    let reply = call(&mut server, 3, "tools/call", json!({"name": "gdk_render_tree", "arguments": {"format": "pdf"}})).await;
+    assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+
+    let reply = call(&mut server, 4, "tools/call", json!({"name": "gdk_teleport", "arguments": {}})).await;
+    assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_thread_and_convergence_resources() {
+    let (_temp_dir, mut server) = setup_server();
+    record_commit(&mut server, 1, vec![thread("src/lib.rs", ThreadColor::Orange, 0.4)]);
+
+    let reply = call(&mut server, 1, "resources/list", json!({})).await;
+    let uris: Vec<&str> = reply["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["uri"].as_str().unwrap())
+        .collect();
+    assert_eq!(uris, ["gdk://convergence", "gdk://threads", "gdk://threads/src/lib.rs"]);
+
+    let reply = call(&mut server, 2, "resources/read", json!({"uri": "gdk://threads/src/lib.rs"})).await;
+    let contents = &reply["result"]["contents"][0];
+    assert_eq!(contents["uri"], "gdk://threads/src/lib.rs");
+    let thread: FileThread = serde_json::from_str(contents["text"].as_str().unwrap()).unwrap();
+    assert_eq!(thread.color_status, ThreadColor::Orange);
+
+    let reply = call(&mut server, 3, "resources/read", json!({"uri": "gdk://convergence"})).await;
+    let metrics: ConvergenceMetrics =
+        serde_json::from_str(reply["result"]["contents"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(metrics.attempts, 1);
+
+    let reply = call(&mut server, 4, "resources/read", json!({"uri": "gdk://threads/missing.rs"})).await;
+    assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+}
@@ -0,0 +1,205 @@
+//! Concurrent multi-agent tests for the GDK system
+//!
+//! These tests run several agents against one temp repository:
+//! - Each agent commits and reverts in its own worktree
+//! - One agent's revert leaves other agents and the main tree alone
+//! - Worktree removal keeps the agent branch for later sessions
+//! - Sessions run from spawned tasks on a multi-threaded runtime
+//! - The ref lock excludes concurrent holders
+
+mod common;
+
+use common::{head, setup_repo};
+use gdk::multi_agent::{MultiAgentController, RefLock};
+use gdk::policy::AccessPolicy;
+use gdk::{GdkError, GitWorkflow};
+use git2::{BranchType, Repository};
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Checkpoint, write a file in the agent's worktree and commit it
+async fn checkpoint_and_commit(controller: &MultiAgentController, agent_id: &str, file: &str) -> String {
+    controller
+        .create_spiral_checkpoint(agent_id, "before edit")
+        .await
+        .unwrap();
+    let worktree = controller.worktree_path(agent_id).unwrap();
+    fs::write(worktree.join(file), format!("written by {agent_id}\n")).unwrap();
+
+    let message = format!("{agent_id} edit");
+    controller
+        .agent(agent_id)
+        .unwrap()
+        .call(move |agent| Box::pin(async move { agent.workflow.create_commit_node(&message).await }))
+        .await
+        .unwrap()
+        .unwrap()
+        .hash
+}
+
+#[tokio::test]
+async fn test_agents_work_in_isolated_worktrees() {
+    let repo = setup_repo();
+    let main_head = head(repo.path());
+    let controller = MultiAgentController::new(repo.path()).unwrap();
+
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller.start_agent_session("agent-2").await.unwrap();
+    assert_eq!(controller.agent_ids(), ["agent-1", "agent-2"]);
+
+    let first = controller.worktree_path("agent-1").unwrap();
+    let second = controller.worktree_path("agent-2").unwrap();
+    assert_ne!(first, second);
+    assert!(first.join("README.md").exists());
+
+    let (first_commit, second_commit) = tokio::join!(
+        checkpoint_and_commit(&controller, "agent-1", "one.txt"),
+        checkpoint_and_commit(&controller, "agent-2", "two.txt"),
+    );
+
+    // Each commit landed on its own agent branch
+    let git = Repository::open(repo.path()).unwrap();
+    for (agent_id, commit) in [("agent-1", &first_commit), ("agent-2", &second_commit)] {
+        let branch = git
+            .find_branch(&MultiAgentController::agent_branch(agent_id), BranchType::Local)
+            .unwrap();
+        assert_eq!(&branch.get().target().unwrap().to_string(), commit);
+    }
+    assert!(!first.join("two.txt").exists());
+    assert!(!second.join("one.txt").exists());
+
+    // Reverting agent-1 leaves agent-2 and the main tree untouched
+    controller.revert_to_last_checkpoint("agent-1").await.unwrap();
+    assert!(!first.join("one.txt").exists());
+    assert_eq!(head(&first), main_head);
+    assert!(second.join("two.txt").exists());
+    assert_eq!(head(&second), second_commit);
+    assert_eq!(head(repo.path()), main_head);
+    assert!(!repo.path().join("one.txt").exists());
+
+    let history = controller.action_history().await;
+    assert!(history.iter().any(|a| a.agent_id == "agent-1"));
+    assert!(history.iter().any(|a| a.agent_id == "agent-2"));
+    assert!(!controller.ref_lock().path().exists());
+}
+
+#[tokio::test]
+async fn test_ending_a_session_keeps_the_branch() {
+    let repo = setup_repo();
+    let controller = MultiAgentController::new(repo.path()).unwrap();
+
+    controller.start_agent_session("agent-1").await.unwrap();
+    let duplicate = controller.start_agent_session("agent-1").await.unwrap_err();
+    assert_eq!(duplicate.category(), "agent");
+
+    let worktree = controller.worktree_path("agent-1").unwrap();
+    let commit = checkpoint_and_commit(&controller, "agent-1", "work.txt").await;
+
+    let branch = controller.end_agent_session("agent-1").await.unwrap();
+    assert_eq!(branch, "gdk/agents/agent-1");
+    assert!(!worktree.exists());
+    assert!(controller.agent("agent-1").is_err());
+
+    // A new session resumes from the agent's branch
+    controller.start_agent_session("agent-1").await.unwrap();
+    let worktree = controller.worktree_path("agent-1").unwrap();
+    assert_eq!(head(&worktree), commit);
+    assert!(worktree.join("work.txt").exists());
+
+    let invalid = controller.start_agent_session("agent..1").await.unwrap_err();
+    assert_eq!(invalid.category(), "configuration");
+    // Ids are worktree names as-is, so nothing is escaped into a collision
+    controller.start_agent_session("team_a").await.unwrap();
+    let invalid = controller.start_agent_session("team/a").await.unwrap_err();
+    assert_eq!(invalid.category(), "configuration");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sessions_run_on_spawned_tasks() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let repo = setup_repo();
+    let controller = Arc::new(MultiAgentController::new(repo.path()).unwrap());
+    assert_send_sync(&*controller);
+
+    let tasks: Vec<_> = ["agent-1", "agent-2", "agent-3"]
+        .into_iter()
+        .map(|agent_id| {
+            let controller = controller.clone();
+            tokio::spawn(async move {
+                controller.start_agent_session(agent_id).await.unwrap();
+                checkpoint_and_commit(&controller, agent_id, "work.txt").await
+            })
+        })
+        .collect();
+    let commits = futures::future::join_all(tasks).await;
+
+    let git = Repository::open(repo.path()).unwrap();
+    for (agent_id, commit) in ["agent-1", "agent-2", "agent-3"].into_iter().zip(commits) {
+        let branch = git
+            .find_branch(&MultiAgentController::agent_branch(agent_id), BranchType::Local)
+            .unwrap();
+        assert_eq!(branch.get().target().unwrap().to_string(), commit.unwrap());
+        let stats = controller.get_agent_statistics(agent_id).await.unwrap();
+        assert_eq!(stats.agent_id, agent_id);
+    }
+    assert_eq!(controller.agent_ids(), ["agent-1", "agent-2", "agent-3"]);
+    assert!(!controller.ref_lock().path().exists());
+}
+
+#[tokio::test]
+async fn test_ref_lock_excludes_concurrent_holders() {
+    let dir = TempDir::new().unwrap();
+    let lock = RefLock::new(dir.path().join("gdk").join("refs.lock"));
+    let other = lock.clone();
+
+    let guard = lock.acquire().await.unwrap();
+    assert!(lock.path().exists());
+
+    let waiter = async {
+        let _second = other.acquire().await.unwrap();
+        std::time::Instant::now()
+    };
+    let holder = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let released = std::time::Instant::now();
+        drop(guard);
+        released
+    };
+    let (acquired, released) = tokio::join!(waiter, holder);
+    assert!(acquired >= released);
+    assert!(!lock.path().exists());
+}
+
+#[tokio::test]
+async fn test_agents_share_the_repository_policies() {
+    let repo = setup_repo();
+    let path = AccessPolicy::repo_path(repo.path()).unwrap();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, r#"{ "roles": { "lead": "integrator" } }"#).unwrap();
+
+    // Unknown identities get no role, whichever agent they name
+    let controller = MultiAgentController::new(repo.path()).unwrap();
+    controller.start_agent_session("agent-1").await.unwrap();
+    let err = controller
+        .create_spiral_checkpoint("agent-1", "denied")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { ref identity, .. } if identity == "agent-1"));
+    let gated = controller
+        .agent("agent-1")
+        .unwrap()
+        .call(|agent| Box::pin(async move { agent.approval_gate.is_some() }))
+        .await
+        .unwrap();
+    assert!(gated);
+
+    let controller = MultiAgentController::new(repo.path()).unwrap().with_caller("lead");
+    controller.start_agent_session("agent-2").await.unwrap();
+    controller
+        .create_spiral_checkpoint("agent-2", "allowed")
+        .await
+        .unwrap();
+}
@@ -0,0 +1,219 @@
+//! Access policy tests for the GDK system
+//!
+//! These tests check role-based authorization of agent actions:
+//! - Role ordering and the default minimum role per action type
+//! - Denied operations failing with `PermissionDenied` and being logged
+//! - The caller's role, not the named agent's, authorizing each action
+//! - Spiral branches and approval decisions requiring integrators
+//! - Policies loaded from the repository's policy file
+
+mod common;
+
+use common::{head, setup_controller};
+use gdk::agent::ActionType;
+use gdk::approval::ApprovalGate;
+use gdk::policy::{AccessPolicy, Role};
+use gdk::{GdkError, GitWorkflow};
+use std::fs;
+
+#[test]
+fn test_roles_and_default_permissions() {
+    assert!(Role::Observer < Role::Contributor && Role::Integrator < Role::Admin);
+    assert_eq!(Role::default_minimum(&ActionType::ConvergenceCheck), Role::Observer);
+    assert_eq!(Role::default_minimum(&ActionType::CommitCreate), Role::Contributor);
+    assert_eq!(Role::default_minimum(&ActionType::RevertToPoint), Role::Integrator);
+
+    let open = AccessPolicy::default();
+    assert!(open.allows("anyone", &ActionType::SpiralBranch));
+
+    let policy = AccessPolicy::deny_by_default()
+        .with_role("dev", Role::Contributor)
+        .with_permission(ActionType::CommitCreate, Role::Integrator);
+    assert_eq!(policy.role_of("stranger"), None);
+    assert!(!policy.allows("stranger", &ActionType::ConvergenceCheck));
+    assert!(policy.allows("dev", &ActionType::QualityValidation));
+    assert!(!policy.allows("dev", &ActionType::CommitCreate));
+
+    let err = policy
+        .authorize("dev", &ActionType::RevertToPoint)
+        .unwrap_err();
+    assert_eq!(err.category(), "permission");
+    assert!(!err.is_recoverable());
+    assert_eq!(
+        err.to_string(),
+        "Permission denied for 'dev': RevertToPoint (requires integrator, has contributor)"
+    );
+}
+
+#[tokio::test]
+async fn test_denied_actions_are_logged() {
+    let (_temp_dir, controller) = setup_controller();
+    let policy = AccessPolicy::deny_by_default()
+        .with_role("agent-1", Role::Contributor)
+        .with_role("watcher", Role::Observer);
+    let mut controller = controller.with_policy(policy);
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller.start_agent_session("watcher").await.unwrap();
+
+    // Contributors may not checkpoint or hard-reset
+    let err = controller
+        .create_spiral_checkpoint("agent-1", "risky")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { ref identity, .. } if identity == "agent-1"));
+    assert!(controller.active_sessions["agent-1"].revert_stack.is_empty());
+    assert!(controller.revert_to_last_checkpoint("agent-1").await.is_err());
+
+    let denied = &controller.action_history[0];
+    assert_eq!(denied.action_type, ActionType::RevertToPoint);
+    assert!(!denied.success);
+    assert_eq!(denied.metadata["role"], "contributor");
+    assert_eq!(denied.metadata["required_role"], "integrator");
+    assert!(denied.metadata["permission_denied"].contains("requires integrator"));
+    assert_eq!(controller.action_history.len(), 2);
+
+    // Observers may only look
+    controller.get_convergence_status("watcher").await.unwrap();
+    let err = controller
+        .execute_infinite_monkey_workflow("watcher", 1.1)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { .. }));
+    assert!(controller.workflow.commit_history.is_empty());
+    assert_eq!(controller.active_sessions["watcher"].spiral_attempts, 0);
+
+    let last = controller.action_history.last().unwrap();
+    assert_eq!(last.action_type, ActionType::InfiniteMonkeyIteration);
+    assert_eq!(last.metadata["role"], "observer");
+}
+
+#[tokio::test]
+async fn test_actions_are_authorized_as_the_caller() {
+    let (temp_dir, controller) = setup_controller();
+    let policy = AccessPolicy::deny_by_default()
+        .with_role("agent-1", Role::Admin)
+        .with_role("dev", Role::Contributor)
+        .with_role("lead", Role::Integrator);
+    let mut controller = controller.with_policy(policy).with_caller("dev");
+    controller.start_agent_session("agent-1").await.unwrap();
+    let base = head(temp_dir.path());
+
+    // The named agent's role lends the caller nothing
+    let err = controller
+        .create_spiral_checkpoint("agent-1", "risky")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { ref identity, .. } if identity == "dev"));
+
+    // Spirals hard-reset between attempts, so they need the revert role too
+    fs::write(temp_dir.path().join("feature.txt"), "feature\n").unwrap();
+    let err = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { .. }));
+    assert!(controller.workflow.commit_history.is_empty());
+    assert_eq!(head(temp_dir.path()), base);
+    assert!(temp_dir.path().join("feature.txt").exists());
+    let denied = controller.action_history.last().unwrap();
+    assert_eq!(denied.action_type, ActionType::RevertToPoint);
+    assert_eq!(denied.metadata["caller"], "dev");
+    assert_eq!(denied.metadata["role"], "contributor");
+
+    controller.caller = Some("lead".to_string());
+    controller
+        .create_spiral_checkpoint("agent-1", "safe")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_spiral_branch_requires_integrator() {
+    let (temp_dir, controller) = setup_controller();
+    let policy = AccessPolicy::deny_by_default()
+        .with_role("dev", Role::Contributor)
+        .with_role("lead", Role::Integrator);
+    let mut controller = controller.with_policy(policy);
+    controller.start_agent_session("dev").await.unwrap();
+    controller.start_agent_session("lead").await.unwrap();
+
+    let head = head(temp_dir.path());
+    assert!(controller.create_spiral_branch("dev", &head).await.is_err());
+
+    let branch = controller.create_spiral_branch("lead", &head).await.unwrap();
+    assert!(branch.starts_with("spiral-"));
+    assert_eq!(controller.workflow.current_branch, branch);
+
+    let action = controller.action_history.last().unwrap();
+    assert_eq!(action.action_type, ActionType::SpiralBranch);
+    assert!(action.success);
+    assert_eq!(action.metadata["branch"], branch);
+}
+
+#[tokio::test]
+async fn test_approval_decisions_need_a_role() {
+    let (temp_dir, controller) = setup_controller();
+    let policy = AccessPolicy::deny_by_default()
+        .with_role("agent-1", Role::Contributor)
+        .with_role("intern@example.com", Role::Contributor)
+        .with_role("lead@example.com", Role::Integrator);
+    let gate = ApprovalGate::for_repo(temp_dir.path())
+        .unwrap()
+        .with_approvers(["intern@example.com", "lead@example.com"]);
+    let mut controller = controller.with_policy(policy).with_approval_gate(gate.clone());
+    let session_id = controller.start_agent_session("agent-1").await.unwrap();
+
+    let base = controller.workflow.create_revert_point("base").await.unwrap();
+    fs::write(temp_dir.path().join("feature.txt"), "feature\n").unwrap();
+    let node = controller.workflow.create_commit_node("Feature").await.unwrap();
+    let convergence = controller.workflow.analyze_convergence().await.unwrap();
+    let request = gate
+        .submit("agent-1", Some(session_id), &base, &node, &convergence, 0.0)
+        .unwrap();
+    controller.workflow.revert_to_point(&base).await.unwrap();
+
+    let err = controller
+        .approve_promotion(request.id, "intern@example.com")
+        .unwrap_err();
+    assert!(matches!(err, GdkError::PermissionDenied { .. }));
+    let denied = controller.action_history.last().unwrap();
+    assert_eq!(denied.action_type, ActionType::ApprovalDecision);
+    assert_eq!(denied.session_id, Some(session_id));
+    assert_eq!(denied.metadata["decision"], "denied");
+    assert_eq!(denied.metadata["approver"], "intern@example.com");
+    assert_eq!(controller.pending_approvals().unwrap().len(), 1);
+
+    controller
+        .approve_promotion(request.id, "lead@example.com")
+        .unwrap();
+    assert!(controller.pending_approvals().unwrap().is_empty());
+}
+
+#[test]
+fn test_policy_loaded_from_repository() {
+    let (temp_dir, _controller) = setup_controller();
+    assert_eq!(AccessPolicy::for_repo(temp_dir.path()).unwrap(), AccessPolicy::default());
+
+    let path = AccessPolicy::repo_path(temp_dir.path()).unwrap();
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(
+        &path,
+        r#"{
+            "roles": { "lead@example.com": "admin", "ci-bot": "observer" },
+            "permissions": { "ApprovalDecision": "admin" }
+        }"#,
+    )
+    .unwrap();
+
+    let policy = AccessPolicy::for_repo(temp_dir.path()).unwrap();
+    assert_eq!(policy.default_role, None);
+    assert_eq!(policy.role_of("ci-bot"), Some(Role::Observer));
+    assert_eq!(policy.required_role(&ActionType::ApprovalDecision), Role::Admin);
+    assert!(policy.allows("lead@example.com", &ActionType::ApprovalDecision));
+    assert!(!policy.allows("ci-bot", &ActionType::CommitCreate));
+    assert!(!policy.allows("unknown", &ActionType::ConvergenceCheck));
+
+    fs::write(&path, r#"{ "roles": { "x": "superuser" } }"#).unwrap();
+    let err = AccessPolicy::for_repo(temp_dir.path()).unwrap_err();
+    assert!(matches!(err, GdkError::SerializationError { .. }));
+}
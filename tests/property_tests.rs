@@ -160,6 +160,7 @@ proptest! {
             test_pass_rate,
             quality_trend: quality_trend.clone(),
             is_converged,
+            ..Default::default()
         };
         
         // Basic constraints
@@ -256,6 +257,7 @@ proptest! {
                 test_pass_rate: health_score,
                 quality_trend: vec![health_score],
                 is_converged: health_score > 0.8,
+                ..Default::default()
             },
         };
         
@@ -0,0 +1,237 @@
+//! Change proposer tests for the GDK system
+//!
+//! These tests run each built-in proposer against a scratch repository:
+//! - Patches applied in order from a directory and from an mbox
+//! - External commands editing the tree, reporting exhaustion and timing out
+//! - Closures driving the infinite monkey loop with attempt context
+
+mod common;
+
+use common::setup_repo;
+use gdk::agent::AgentWorkflowController;
+use gdk::core::GitWorkflowManager;
+use gdk::proposer::{ChangeProposer, ClosureProposer, CommandProposer, PatchProposer, Proposal, ProposalContext};
+use gdk::{FileThread, GdkError, GdkResult, ThreadColor, ThreadMetrics, ThreadState};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Create a repository with one committed file
+fn new_file_patch(name: &str, line: &str) -> String {
+    format!(
+        "diff --git a/{name} b/{name}\n\
+         new file mode 100644\n\
+         --- /dev/null\n\
+         +++ b/{name}\n\
+         @@ -0,0 +1 @@\n\
+         +{line}\n"
+    )
+}
+
+fn read(repo: &TempDir, name: &str) -> String {
+    fs::read_to_string(repo.path().join(name)).unwrap()
+}
+
+#[tokio::test]
+async fn test_patch_dir_applies_patches_in_order() {
+    let repo = setup_repo();
+    let patches = TempDir::new().unwrap();
+    fs::write(patches.path().join("0002-second.patch"), new_file_patch("second.txt", "two")).unwrap();
+    fs::write(patches.path().join("0001-first.diff"), new_file_patch("first.txt", "one")).unwrap();
+    fs::write(patches.path().join("notes.md"), "not a patch").unwrap();
+
+    let mut proposer = PatchProposer::from_dir(repo.path(), patches.path()).unwrap();
+    let context = ProposalContext::default();
+    assert_eq!(proposer.remaining(), 2);
+
+    assert_eq!(
+        proposer.propose(&context).await.unwrap(),
+        Proposal::Applied { description: "0001-first.diff".to_string() }
+    );
+    assert_eq!(read(&repo, "first.txt"), "one\n");
+    assert!(!repo.path().join("second.txt").exists());
+
+    proposer.propose(&context).await.unwrap();
+    assert_eq!(read(&repo, "second.txt"), "two\n");
+    assert_eq!(proposer.propose(&context).await.unwrap(), Proposal::Exhausted);
+}
+
+#[tokio::test]
+async fn test_mbox_patches_are_split_per_message() {
+    let repo = setup_repo();
+    let mbox = TempDir::new().unwrap();
+    let mbox_path = mbox.path().join("series.mbox");
+    let message = |subject: &str, patch: String| {
+        format!(
+            "From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001\n\
+             From: Test User <test@example.com>\n\
+             Subject: {subject}\n\
+             \n\
+             ---\n\
+             {patch}\
+             -- \n\
+             2.43.0\n\
+             \n"
+        )
+    };
+    fs::write(
+        &mbox_path,
+        message("[PATCH 1/2] Add alpha", new_file_patch("alpha.txt", "a"))
+            + &message("[PATCH 2/2] Add beta", new_file_patch("beta.txt", "b")),
+    )
+    .unwrap();
+
+    let mut proposer = PatchProposer::from_mbox(repo.path(), &mbox_path).unwrap();
+    assert_eq!(proposer.remaining(), 2);
+    assert_eq!(
+        proposer.propose(&ProposalContext::default()).await.unwrap(),
+        Proposal::Applied { description: "[PATCH 1/2] Add alpha".to_string() }
+    );
+    assert_eq!(read(&repo, "alpha.txt"), "a\n");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_proposer_sees_context_and_reports_exhaustion() {
+    let repo = setup_repo();
+    let script = r#"
+        [ "$GDK_ATTEMPT" -gt 2 ] && exit 3
+        echo "$GDK_PREVIOUS_SCORE|$GDK_FAILING_FILES" > attempt-$GDK_ATTEMPT.txt
+        echo "edited attempt $GDK_ATTEMPT"
+    "#;
+    let mut proposer = CommandProposer::new("sh", repo.path()).arg("-c").arg(script);
+
+    let mut context = ProposalContext {
+        agent_id: "agent-1".to_string(),
+        attempt: 2,
+        previous_scores: vec![0.4, 0.55],
+        ..Default::default()
+    };
+    assert_eq!(
+        proposer.propose(&context).await.unwrap(),
+        Proposal::Applied { description: "edited attempt 2".to_string() }
+    );
+    assert_eq!(read(&repo, "attempt-2.txt"), "0.55|\n");
+
+    context.attempt = 3;
+    assert_eq!(proposer.propose(&context).await.unwrap(), Proposal::Exhausted);
+
+    let mut failing = CommandProposer::new("sh", repo.path()).args(["-c", "echo broken >&2; exit 1"]);
+    let err = failing.propose(&context).await.unwrap_err();
+    assert_eq!(err.category(), "agent");
+    assert!(err.to_string().contains("agent-1"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_command_proposer_streams_large_io_and_times_out() {
+    let repo = setup_repo();
+    let thread = FileThread {
+        file_path: "src/big.rs".to_string(),
+        thread_id: Uuid::new_v4(),
+        color_status: ThreadColor::Red,
+        lint_score: 0.0,
+        type_check_score: 0.0,
+        test_coverage: 0.0,
+        functionality_score: 0.0,
+        history: vec![ThreadState {
+            commit_hash: "0".repeat(40),
+            diff_content: "+x\n".repeat(200_000),
+            metrics: ThreadMetrics {
+                lines_added: 200_000,
+                lines_removed: 0,
+                complexity_delta: 0.0,
+                quality_score: 0.0,
+            },
+            timestamp: 0,
+        }],
+    };
+    let context = ProposalContext {
+        agent_id: "agent-1".to_string(),
+        attempt: 1,
+        failing_threads: vec![thread],
+        ..Default::default()
+    };
+
+    // Fill stdout well past a pipe buffer before reading the large context
+    let script = "head -c 200000 /dev/zero; echo; wc -c | tr -d ' '";
+    let mut proposer = CommandProposer::new("sh", repo.path()).args(["-c", script]);
+    let Proposal::Applied { description } = proposer.propose(&context).await.unwrap() else {
+        panic!("expected an applied proposal");
+    };
+    assert!(description.parse::<usize>().unwrap() > 600_000);
+
+    let started = Instant::now();
+    let mut stuck = CommandProposer::new("sh", repo.path())
+        .args(["-c", "sleep 30"])
+        .timeout(Duration::from_millis(200));
+    let err = stuck.propose(&context).await.unwrap_err();
+    assert!(err.to_string().contains("agent-1"));
+    assert!(format!("{err:?}").contains("timed out"));
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_closure_proposer_varies_spiral_attempts() -> GdkResult<()> {
+    let repo = setup_repo();
+    let workdir = repo.path().to_path_buf();
+    let seen: Arc<Mutex<Vec<ProposalContext>>> = Arc::default();
+
+    let mut controller =
+        AgentWorkflowController::new(GitWorkflowManager::new(repo.path().to_str().unwrap())?);
+    controller.start_agent_session("agent-1").await?;
+
+    let recorded = Arc::clone(&seen);
+    controller.set_change_proposer(
+        "agent-1",
+        ClosureProposer::new(move |context| {
+            recorded.lock().unwrap().push(context.clone());
+            if context.attempt > 2 {
+                return Ok(Proposal::Exhausted);
+            }
+            fs::write(workdir.join("candidate.txt"), format!("candidate {}\n", context.attempt))
+                .map_err(|e| GdkError::file_system_error("candidate.txt", "write", e))?;
+            Ok(Proposal::Applied {
+                description: format!("candidate {}", context.attempt),
+            })
+        }),
+    )?;
+
+    let result = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await;
+    assert!(matches!(result, Err(GdkError::ConvergenceError { iterations: 2, .. })));
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[0].attempt, 1);
+    assert!(seen[0].previous_scores.is_empty());
+    assert_eq!(seen[2].previous_scores.len(), 2);
+
+    let messages: Vec<String> = controller
+        .workflow
+        .commit_history
+        .iter()
+        .map(|commit| commit.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        ["Infinite monkey attempt 1: candidate 1", "Infinite monkey attempt 2: candidate 2"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_change_proposer_requires_session() {
+    let repo = setup_repo();
+    let mut controller =
+        AgentWorkflowController::new(GitWorkflowManager::new(repo.path().to_str().unwrap()).unwrap());
+    let result = controller.set_change_proposer(
+        "missing",
+        ClosureProposer::new(|_| Ok(Proposal::Exhausted)),
+    );
+    assert!(result.is_err());
+}
@@ -0,0 +1,178 @@
+//! Session recording and replay tests for the GDK system
+//!
+//! These tests record a spiral against a scratch repository and replay it:
+//! - Recording every workflow call with its inputs and outputs, including
+//!   through an existing controller
+//! - Saving and loading recordings
+//! - Faithful replays in a scratch clone, including reverted commits
+//! - Divergence reports when an output no longer matches
+
+mod common;
+
+use common::setup_repo;
+use gdk::agent::AgentWorkflowController;
+use gdk::core::GitWorkflowManager;
+use gdk::proposer::{ChangeProposer, ClosureProposer, Proposal};
+use gdk::replay::{self, RecordedCall, RecordedOutcome, Recording, RecordingWorkflow};
+use gdk::GdkError;
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Proposer writing a new `candidate.txt` for every attempt
+fn candidate_proposer(repo_dir: &Path) -> impl ChangeProposer {
+    let workdir = repo_dir.to_path_buf();
+    ClosureProposer::new(move |context| {
+        fs::write(workdir.join("candidate.txt"), format!("candidate {}\n", context.attempt))
+            .map_err(|e| GdkError::file_system_error("candidate.txt", "write", e))?;
+        Ok(Proposal::Applied {
+            description: format!("candidate {}", context.attempt),
+        })
+    })
+}
+
+/// Record a three-attempt spiral whose attempts each write a new candidate
+async fn record_spiral(repo_dir: &Path) -> Recording {
+    let repo_path = repo_dir.to_str().unwrap();
+    let workflow = RecordingWorkflow::new(GitWorkflowManager::new(repo_path).unwrap(), repo_path).unwrap();
+    let mut controller = AgentWorkflowController::new(workflow);
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .active_sessions
+        .get_mut("agent-1")
+        .unwrap()
+        .max_spiral_attempts = 3;
+
+    controller
+        .set_change_proposer("agent-1", candidate_proposer(repo_dir))
+        .unwrap();
+
+    let _ = controller
+        .execute_infinite_monkey_workflow("agent-1", 1.1)
+        .await;
+    controller.workflow.into_recording()
+}
+
+#[tokio::test]
+async fn test_recording_captures_calls() {
+    let repo_dir = setup_repo();
+    let head = Repository::open(repo_dir.path())
+        .unwrap()
+        .head()
+        .unwrap()
+        .target()
+        .unwrap();
+    let recording = record_spiral(repo_dir.path()).await;
+
+    assert_eq!(recording.base_commit, head.to_string());
+    assert!(recording.steps.iter().enumerate().all(|(i, step)| step.index == i));
+
+    let commits: Vec<_> = recording
+        .steps
+        .iter()
+        .filter_map(|step| match (&step.call, &step.outcome) {
+            (RecordedCall::CreateCommitNode { message, tree }, RecordedOutcome::Commit { tree: committed, .. }) => {
+                assert_eq!(tree.as_ref(), Some(committed));
+                Some(message.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(commits[0], "Infinite monkey attempt 1: candidate 1");
+    assert!(recording
+        .steps
+        .iter()
+        .any(|step| matches!(step.call, RecordedCall::RevertToPoint { .. })));
+
+    let file = repo_dir.path().join("spiral.json");
+    recording.save(&file).unwrap();
+    assert_eq!(Recording::load(&file).unwrap(), recording);
+}
+
+#[tokio::test]
+async fn test_replay_reproduces_recording() {
+    let repo_dir = setup_repo();
+    let recording = record_spiral(repo_dir.path()).await;
+
+    let scratch_dir = TempDir::new().unwrap();
+    let scratch_path = scratch_dir.path().join("replay");
+    let mut scratch = replay::scratch_clone(&recording, repo_dir.path(), &scratch_path).unwrap();
+    let report = replay::replay(&recording, &mut scratch).await.unwrap();
+
+    assert_eq!(report.steps_replayed, recording.steps.len());
+    assert!(report.is_faithful(), "{:#?}", report.divergences);
+    assert_eq!(report.first_divergent_step(), None);
+
+    // The scratch clone ends where the original did, without touching it
+    let original = fs::read_to_string(repo_dir.path().join("candidate.txt")).ok();
+    let replayed = fs::read_to_string(scratch_path.join("candidate.txt")).ok();
+    assert_eq!(replayed, original);
+}
+
+#[tokio::test]
+async fn test_replay_reports_divergence() {
+    let repo_dir = setup_repo();
+    let mut recording = record_spiral(repo_dir.path()).await;
+
+    let step = recording
+        .steps
+        .iter()
+        .position(|step| matches!(step.outcome, RecordedOutcome::Commit { .. }))
+        .unwrap();
+    if let RecordedOutcome::Commit { health_score, .. } = &mut recording.steps[step].outcome {
+        *health_score += 0.25;
+    }
+
+    let scratch_dir = TempDir::new().unwrap();
+    let mut scratch = replay::scratch_clone(&recording, repo_dir.path(), scratch_dir.path().join("replay")).unwrap();
+    let report = replay::replay(&recording, &mut scratch).await.unwrap();
+
+    assert!(!report.is_faithful());
+    assert_eq!(report.first_divergent_step(), Some(step));
+    assert_eq!(report.divergences.len(), 1);
+    let divergence = &report.divergences[0];
+    assert_eq!(divergence.call, "create_commit_node");
+    assert_eq!(divergence.field, "health_score");
+    assert!(divergence.to_string().starts_with(&format!("step {step} (create_commit_node): health_score")));
+}
+
+#[tokio::test]
+async fn test_recording_a_controller_keeps_its_state() {
+    let repo_dir = setup_repo();
+    let repo_path = repo_dir.path().to_str().unwrap();
+    let mut controller = AgentWorkflowController::new(GitWorkflowManager::new(repo_path).unwrap());
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .active_sessions
+        .get_mut("agent-1")
+        .unwrap()
+        .max_spiral_attempts = 2;
+    controller
+        .set_change_proposer("agent-1", candidate_proposer(repo_dir.path()))
+        .unwrap();
+
+    let workflow = RecordingWorkflow::new(GitWorkflowManager::new(repo_path).unwrap(), repo_path).unwrap();
+    let (workflow, outcome) = controller
+        .with_workflow(workflow, |recorder| {
+            Box::pin(async move {
+                recorder
+                    .execute_infinite_monkey_workflow("agent-1", 1.1)
+                    .await
+            })
+        })
+        .await;
+    assert!(outcome.is_err());
+
+    // The proposer wrote the recorded attempts and is still attached
+    let recording = workflow.into_recording();
+    let commits = recording
+        .steps
+        .iter()
+        .filter(|step| matches!(step.call, RecordedCall::CreateCommitNode { .. }))
+        .count();
+    assert_eq!(commits, 2);
+    assert!(controller.change_proposers.contains_key("agent-1"));
+    assert_eq!(controller.active_sessions["agent-1"].max_spiral_attempts, 2);
+    assert!(!controller.action_history.is_empty());
+}
@@ -0,0 +1,153 @@
+//! JSON-RPC agent protocol tests for the GDK system
+//!
+//! These tests drive the server over in-memory streams:
+//! - Session, checkpoint and statistics round trips
+//! - Progress notifications around each request
+//! - Standard JSON-RPC errors and workflow error mapping
+//! - Batches and client notifications
+
+mod common;
+
+use common::setup_controller;
+use gdk::rpc::{RpcServer, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR, SERVER_ERROR};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+fn setup_server() -> (TempDir, RpcServer) {
+    let (temp_dir, controller) = setup_controller();
+    (temp_dir, RpcServer::new(controller))
+}
+
+/// Send requests through `serve` and collect every line written back
+async fn exchange(server: &mut RpcServer, requests: &[Value]) -> Vec<Value> {
+    let input: String = requests.iter().map(|r| format!("{r}\n")).collect();
+    let (mut client, server_side) = tokio::io::duplex(1 << 20);
+    let (server_read, server_write) = tokio::io::split(server_side);
+
+    client.write_all(input.as_bytes()).await.unwrap();
+    client.shutdown().await.unwrap();
+    server
+        .serve(BufReader::new(server_read), server_write)
+        .await
+        .unwrap();
+
+    let mut lines = BufReader::new(client).lines();
+    let mut messages = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        messages.push(serde_json::from_str(&line).unwrap());
+    }
+    messages
+}
+
+fn responses(messages: &[Value]) -> Vec<&Value> {
+    messages.iter().filter(|m| m.get("id").is_some()).collect()
+}
+
+#[tokio::test]
+async fn test_session_round_trip_with_progress() {
+    let (_temp_dir, mut server) = setup_server();
+    let messages = exchange(
+        &mut server,
+        &[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "session.start", "params": {"agent_id": "agent-1"}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "workflow.checkpoint", "params": {"agent_id": "agent-1", "reason": "before refactor"}}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "agent.statistics", "params": {"agent_id": "agent-1"}}),
+        ],
+    )
+    .await;
+
+    let replies = responses(&messages);
+    assert_eq!(replies.len(), 3);
+    assert!(replies[0]["result"]["session_id"].is_string());
+    assert_eq!(replies[1]["result"]["metadata"]["reason"], "before refactor");
+    assert_eq!(replies[2]["result"]["agent_id"], "agent-1");
+    assert_eq!(replies[2]["result"]["revert_points_used"], 1);
+
+    // Each request is bracketed by progress notifications
+    let progress: Vec<(&Value, &str)> = messages
+        .iter()
+        .filter(|m| m["method"] == "gdk/progress")
+        .map(|m| (&m["params"]["request_id"], m["params"]["stage"].as_str().unwrap()))
+        .collect();
+    assert_eq!(progress.len(), 6);
+    assert_eq!(progress[0], (&json!(1), "started"));
+    assert_eq!(progress[1], (&json!(1), "completed"));
+}
+
+#[tokio::test]
+async fn test_protocol_errors() {
+    let (_temp_dir, mut server) = setup_server();
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let reply: Value =
+        serde_json::from_str(&server.handle_line("{not json", &tx).await.unwrap()).unwrap();
+    assert_eq!(reply["error"]["code"], PARSE_ERROR);
+    assert_eq!(reply["id"], Value::Null);
+
+    let reply: Value = serde_json::from_str(
+        &server
+            .handle_line(r#"{"jsonrpc":"2.0","id":"a","method":"nope"}"#, &tx)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+    assert_eq!(reply["id"], "a");
+
+    let reply: Value = serde_json::from_str(
+        &server
+            .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"agent.suggest","params":{}}"#, &tx)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+}
+
+#[tokio::test]
+async fn test_workflow_errors_carry_category() {
+    let (_temp_dir, mut server) = setup_server();
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let reply: Value = serde_json::from_str(
+        &server
+            .handle_line(
+                r#"{"jsonrpc":"2.0","id":7,"method":"agent.statistics","params":{"agent_id":"ghost"}}"#,
+                &tx,
+            )
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(reply["error"]["code"], SERVER_ERROR);
+    assert_eq!(reply["error"]["data"]["category"], "agent");
+}
+
+#[tokio::test]
+async fn test_batches_and_client_notifications() {
+    let (_temp_dir, mut server) = setup_server();
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+    // A notification is executed but never answered
+    let reply = server
+        .handle_line(
+            r#"{"jsonrpc":"2.0","method":"session.start","params":{"agent_id":"agent-1"}}"#,
+            &tx,
+        )
+        .await;
+    assert!(reply.is_none());
+    assert!(server.controller.active_sessions.contains_key("agent-1"));
+
+    let batch = json!([
+        {"jsonrpc": "2.0", "id": 1, "method": "agent.suggest", "params": {"agent_id": "agent-1"}},
+        {"jsonrpc": "2.0", "method": "agent.suggest", "params": {"agent_id": "agent-1"}},
+        {"jsonrpc": "2.0", "id": 2, "method": "convergence.status", "params": {"agent_id": "agent-1"}},
+    ]);
+    let reply: Value =
+        serde_json::from_str(&server.handle_line(&batch.to_string(), &tx).await.unwrap()).unwrap();
+    let replies = reply.as_array().unwrap();
+    assert_eq!(replies.len(), 2);
+    assert!(replies[0]["result"]["suggestion"].is_string());
+    assert_eq!(replies[1]["id"], 2);
+}
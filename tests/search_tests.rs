@@ -0,0 +1,121 @@
+//! Search strategy tests for the infinite monkey workflow
+//!
+//! These tests feed synthetic attempts to each strategy and check where the
+//! next attempt starts:
+//! - Random restart always returns to the initial point
+//! - Hill climbing keeps the best attempt
+//! - Beam search rotates between the best attempts
+//! - Simulated annealing is reproducible and cools over time
+
+use gdk::search::{BeamSearch, SearchAttempt, SearchStrategy, SearchStrategyKind, SimulatedAnnealing};
+use gdk::{ConvergenceMetrics, RevertMetadata, RevertPoint};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn point(hash: &str) -> RevertPoint {
+    RevertPoint {
+        commit_hash: hash.to_string(),
+        branch_name: "main".to_string(),
+        snapshot_id: Uuid::new_v4(),
+        file_states: HashMap::new(),
+        metadata: RevertMetadata {
+            reason: "test".to_string(),
+            agent_id: "agent".to_string(),
+            timestamp: 0,
+            convergence_state: ConvergenceMetrics::default(),
+        },
+    }
+}
+
+fn attempt(hash: &str, score: f64) -> SearchAttempt {
+    SearchAttempt {
+        point: point(hash),
+        score,
+    }
+}
+
+#[test]
+fn test_random_restart_returns_to_initial_point() {
+    let initial = point("initial");
+    let mut strategy = SearchStrategyKind::default().build();
+
+    assert_eq!(strategy.kind(), SearchStrategyKind::RandomRestart);
+    for (hash, score) in [("a", 0.4), ("b", 0.7), ("c", 0.5)] {
+        let next = strategy.next_start(&initial, attempt(hash, score));
+        assert_eq!(next.commit_hash, "initial");
+    }
+    assert_eq!(strategy.best().unwrap().point.commit_hash, "b");
+}
+
+#[test]
+fn test_hill_climbing_continues_from_best_attempt() {
+    let initial = point("initial");
+    let mut strategy = SearchStrategyKind::HillClimbing.build();
+
+    assert_eq!(strategy.next_start(&initial, attempt("a", 0.4)).commit_hash, "a");
+    assert_eq!(strategy.next_start(&initial, attempt("b", 0.6)).commit_hash, "b");
+    // A worse attempt is abandoned in favour of the best so far
+    assert_eq!(strategy.next_start(&initial, attempt("c", 0.5)).commit_hash, "b");
+    assert_eq!(strategy.best().unwrap().score, 0.6);
+}
+
+#[test]
+fn test_beam_search_rotates_between_best_attempts() {
+    let initial = point("initial");
+    let mut beam = BeamSearch::new(2);
+
+    // Until the beam is full the initial point stays a branch
+    assert_eq!(beam.next_start(&initial, attempt("a", 0.4)).commit_hash, "a");
+    assert_eq!(beam.next_start(&initial, attempt("b", 0.6)).commit_hash, "a");
+    assert_eq!(beam.next_start(&initial, attempt("c", 0.5)).commit_hash, "b");
+    assert_eq!(beam.next_start(&initial, attempt("d", 0.3)).commit_hash, "c");
+
+    let hashes: Vec<_> = beam.beam().iter().map(|a| a.point.commit_hash.as_str()).collect();
+    assert_eq!(hashes, ["b", "c"]);
+    assert_eq!(beam.best().unwrap().point.commit_hash, "b");
+    assert_eq!(beam.kind(), SearchStrategyKind::BeamSearch { width: 2 });
+}
+
+#[test]
+fn test_simulated_annealing_is_reproducible_and_cools() {
+    let initial = point("initial");
+    let run = |seed| {
+        let mut annealing = SimulatedAnnealing::new(0.5, 0.5, seed);
+        let starts: Vec<String> = [0.5, 0.4, 0.45, 0.3, 0.6, 0.2]
+            .iter()
+            .enumerate()
+            .map(|(i, &score)| annealing.next_start(&initial, attempt(&format!("{i}"), score)).commit_hash)
+            .collect();
+        (starts, annealing.temperature())
+    };
+
+    let (first, temperature) = run(7);
+    let (second, _) = run(7);
+    assert_eq!(first, second);
+    assert!((temperature - 0.5 * 0.5f64.powi(6)).abs() < 1e-12);
+    // Improvements are always accepted
+    assert_eq!(first[4], "4");
+}
+
+#[test]
+fn test_cold_annealing_behaves_like_hill_climbing() {
+    let initial = point("initial");
+    let mut annealing = SearchStrategyKind::SimulatedAnnealing {
+        initial_temperature: 0.0,
+        cooling_rate: 0.9,
+        seed: 1,
+    }
+    .build();
+
+    assert_eq!(annealing.next_start(&initial, attempt("a", 0.5)).commit_hash, "a");
+    assert_eq!(annealing.next_start(&initial, attempt("b", 0.4)).commit_hash, "a");
+    assert_eq!(annealing.next_start(&initial, attempt("c", 0.7)).commit_hash, "c");
+}
+
+#[test]
+fn test_strategy_kind_round_trips_through_json() {
+    let kind = SearchStrategyKind::BeamSearch { width: 4 };
+    let json = serde_json::to_string(&kind).unwrap();
+    assert_eq!(json, r#"{"strategy":"beam_search","width":4}"#);
+    assert_eq!(serde_json::from_str::<SearchStrategyKind>(&json).unwrap(), kind);
+}
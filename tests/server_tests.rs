@@ -0,0 +1,354 @@
+//! HTTP REST API tests for the GDK system
+//!
+//! These tests run the server on a localhost port against temp repositories:
+//! - Health check and OpenAPI document
+//! - Idle connections not holding up other requests
+//! - Session, checkpoint and revert resources
+//! - Thread state, convergence and visualization resources
+//! - Error statuses for unknown routes, sessions and malformed bodies
+//! - Bearer tokens identifying the caller whose role is checked
+//! - Approval decisions made as the bearer token's identity
+
+mod common;
+
+use common::setup_repo;
+use gdk::policy::AccessPolicy;
+use gdk::server::{ApiTokens, HttpRequest, HttpServer};
+use gdk::{CommitNode, ConvergenceMetrics, GitWorkflow};
+use git2::Repository;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Bearer token the test servers accept, for `tester`
+const TOKEN: &str = "test-token";
+
+fn tokens() -> ApiTokens {
+    ApiTokens::default().with_token("tester", TOKEN)
+}
+
+/// Request carrying `token`
+fn request_as(token: &str, method: &str, target: &str, body: impl Into<Vec<u8>>) -> HttpRequest {
+    let mut request = HttpRequest::new(method, target, body);
+    request
+        .headers
+        .insert("authorization".to_string(), format!("Bearer {token}"));
+    request
+}
+
+/// Send one raw HTTP request as `tester` and return (status, body)
+async fn send(addr: std::net::SocketAddr, method: &str, path: &str, body: Option<Value>) -> (u16, String) {
+    let body = body.map(|b| b.to_string()).unwrap_or_default();
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, body.to_string())
+}
+
+#[tokio::test]
+async fn test_rest_api_over_localhost() {
+    let api = setup_repo();
+    let web = setup_repo();
+    let mut server = HttpServer::new()
+        .with_tokens(tokens())
+        .with_repository("api", api.path().to_str().unwrap())
+        .unwrap()
+        .with_repository("web", web.path().to_str().unwrap())
+        .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+
+    let client = async move {
+        let (status, body) = send(addr, "GET", "/healthz", None).await;
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["status"], "ok");
+
+        let (status, body) = send(addr, "GET", "/openapi.json", None).await;
+        assert_eq!(status, 200);
+        let document: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(document["openapi"], "3.0.3");
+        assert!(document["paths"]["/repos/{repo}/sessions/{agent_id}/checkpoints"]["post"].is_object());
+
+        let (status, body) = send(addr, "GET", "/repos", None).await;
+        assert_eq!(status, 200);
+        let repos: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(repos[0]["name"], "api");
+        assert_eq!(repos[1]["name"], "web");
+
+        let (status, body) =
+            send(addr, "POST", "/repos/api/sessions", Some(json!({"agent_id": "agent-1"}))).await;
+        assert_eq!(status, 201);
+        assert!(serde_json::from_str::<Value>(&body).unwrap()["session_id"].is_string());
+
+        let (status, body) = send(
+            addr,
+            "POST",
+            "/repos/api/sessions/agent-1/checkpoints",
+            Some(json!({"reason": "before refactor"})),
+        )
+        .await;
+        assert_eq!(status, 201);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["metadata"]["reason"], "before refactor");
+
+        let (status, _) = send(addr, "POST", "/repos/api/sessions/agent-1/revert", None).await;
+        assert_eq!(status, 200);
+        // No checkpoint left to revert to
+        let (status, body) = send(addr, "POST", "/repos/api/sessions/agent-1/revert", None).await;
+        assert_eq!(status, 422);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["error"]["category"], "validation");
+
+        let (status, body) = send(addr, "GET", "/repos/api/sessions/agent-1", None).await;
+        assert_eq!(status, 200);
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["agent_id"], "agent-1");
+
+        // Sessions are per repository
+        let (status, _) = send(addr, "GET", "/repos/web/sessions/agent-1", None).await;
+        assert_eq!(status, 404);
+
+        let (status, body) = send(addr, "GET", "/repos/api/convergence", None).await;
+        assert_eq!(status, 200);
+        serde_json::from_str::<ConvergenceMetrics>(&body).unwrap();
+
+        let (status, _) = send(addr, "GET", "/repos/nope/threads", None).await;
+        assert_eq!(status, 404);
+        let (status, _) = send(addr, "POST", "/repos/api/sessions", Some(json!({"agent": 1}))).await;
+        assert_eq!(status, 400);
+        let (status, _) = send(addr, "GET", "/nowhere", None).await;
+        assert_eq!(status, 404);
+
+        stop.send(()).unwrap();
+    };
+
+    let serve = server.serve(listener, async {
+        stopped.await.ok();
+    });
+    let (result, ()) = tokio::join!(serve, client);
+    result.unwrap();
+    assert_eq!(server.repositories["api"].active_sessions.len(), 1);
+}
+
+#[tokio::test]
+async fn test_idle_connection_does_not_block_requests() {
+    let repo = setup_repo();
+    let mut server = HttpServer::new()
+        .with_tokens(tokens())
+        .with_repository("api", repo.path().to_str().unwrap())
+        .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+
+    let client = async move {
+        // A client that starts a request and never finishes it
+        let mut idle = TcpStream::connect(addr).await.unwrap();
+        idle.write_all(b"POST /repos/api/sessions HTTP/1.1\r\nContent-Length: 100\r\n\r\n{")
+            .await
+            .unwrap();
+
+        let requests = async {
+            let (status, body) = send(addr, "GET", "/healthz", None).await;
+            assert_eq!(status, 200);
+            assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["repositories"], 1);
+
+            let (status, _) =
+                send(addr, "POST", "/repos/api/sessions", Some(json!({"agent_id": "agent-1"}))).await;
+            assert_eq!(status, 201);
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), requests)
+            .await
+            .expect("requests were held up by the idle connection");
+
+        stop.send(()).unwrap();
+        // Shutdown closes the idle connection without a response
+        let mut response = Vec::new();
+        idle.read_to_end(&mut response).await.unwrap();
+        assert!(response.is_empty());
+    };
+
+    let serve = server.serve(listener, async {
+        stopped.await.ok();
+    });
+    let (result, ()) = tokio::join!(serve, client);
+    result.unwrap();
+    assert!(server.repositories["api"].active_sessions.contains_key("agent-1"));
+}
+
+#[tokio::test]
+async fn test_threads_and_visualization_resources() {
+    let repo = setup_repo();
+    let mut server = HttpServer::new()
+        .with_tokens(tokens())
+        .with_repository("default", repo.path().to_str().unwrap())
+        .unwrap();
+    server
+        .repositories
+        .get_mut("default")
+        .unwrap()
+        .workflow
+        .commit_history
+        .push(CommitNode {
+            id: "node-1".to_string(),
+            hash: format!("{:040x}", 1),
+            parent_hashes: Vec::new(),
+            message: "Recorded attempt".to_string(),
+            timestamp: 1_700_000_000,
+            file_threads: HashMap::new(),
+            health_score: 0.7,
+            convergence_metrics: ConvergenceMetrics::default(),
+        });
+
+    let response = server.handle(request_as(TOKEN, "GET", "/repos/default/threads", "")).await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.json_body().unwrap(), json!({}));
+
+    let response = server
+        .handle(request_as(TOKEN, "GET", "/repos/default/visualization?format=svg", ""))
+        .await;
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, "image/svg+xml");
+    assert!(String::from_utf8(response.body).unwrap().contains("Recorded attempt"));
+
+    let response = server
+        .handle(request_as(TOKEN, "GET", "/repos/default/visualization?format=pdf", ""))
+        .await;
+    assert_eq!(response.status, 400);
+
+    let response = server.handle(request_as(TOKEN, "GET", "/repos/default/commits", "")).await;
+    assert_eq!(response.json_body().unwrap()[0]["message"], "Recorded attempt");
+
+    let response = server
+        .handle(request_as(TOKEN, "POST", "/repos/default/validations", r#"{"preset":"cobol"}"#))
+        .await;
+    assert_eq!(response.status, 400);
+    assert_eq!(response.json_body().unwrap()["error"]["category"], "configuration");
+}
+
+#[tokio::test]
+async fn test_approval_decisions_use_the_bearer_identity() {
+    let repo = setup_repo();
+    Repository::open(repo.path())
+        .unwrap()
+        .config()
+        .unwrap()
+        .set_multivar("gdk.approver", "^$", "lead@example.com")
+        .unwrap();
+    let tokens = ApiTokens::default()
+        .with_token("lead@example.com", "lead-secret")
+        .with_token("agent-1", "agent-secret");
+    let mut server = HttpServer::new()
+        .with_tokens(tokens)
+        .with_repository("default", repo.path().to_str().unwrap())
+        .unwrap();
+
+    let controller = server.repositories.get_mut("default").unwrap();
+    let session_id = controller.start_agent_session("agent-1").await.unwrap();
+    let base = controller.workflow.create_revert_point("base").await.unwrap();
+    fs::write(repo.path().join("feature.txt"), "feature\n").unwrap();
+    let node = controller.workflow.create_commit_node("Feature").await.unwrap();
+    let convergence = controller.workflow.analyze_convergence().await.unwrap();
+    let request = controller
+        .approval_gate
+        .as_ref()
+        .unwrap()
+        .submit("agent-1", Some(session_id), &base, &node, &convergence, 0.0)
+        .unwrap();
+    controller.workflow.revert_to_point(&base).await.unwrap();
+
+    let approve = format!("/repos/default/approvals/{}/approve", request.id);
+    let reject = format!("/repos/default/approvals/{}/reject", request.id);
+
+    // Naming an approver in the body proves nothing
+    let body = json!({"approver": "lead@example.com"}).to_string();
+    let response = server.handle(HttpRequest::new("POST", &approve, body.clone())).await;
+    assert_eq!(response.status, 401);
+    let response = server.handle(request_as("guessed", "POST", &approve, body.clone())).await;
+    assert_eq!(response.status, 401);
+    let response = server.handle(request_as("agent-secret", "POST", &approve, body)).await;
+    assert_eq!(response.status, 403);
+
+    let response = server
+        .handle(request_as("lead-secret", "POST", &reject, r#"{"reason":"Not yet"}"#))
+        .await;
+    assert_eq!(response.status, 200);
+    let decided = response.json_body().unwrap();
+    assert_eq!(decided["status"], "rejected");
+    assert_eq!(decided["decided_by"], "lead@example.com");
+}
+
+#[test]
+fn test_api_tokens_are_stored_as_digests() {
+    let tokens = ApiTokens::default().with_token("ci-bot", "s3cret");
+    assert_eq!(tokens.identify("s3cret"), Some("ci-bot"));
+    assert_eq!(tokens.identify("S3CRET"), None);
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("tokens.json");
+    fs::write(&path, serde_json::to_string(&tokens).unwrap()).unwrap();
+    let stored = fs::read_to_string(&path).unwrap();
+    assert!(!stored.contains("s3cret"));
+    assert!(stored.contains(&ApiTokens::digest("s3cret")));
+    assert_eq!(ApiTokens::load(&path).unwrap(), tokens);
+}
+
+#[tokio::test]
+async fn test_requests_are_authorized_as_the_caller() {
+    let repo = setup_repo();
+    let policy = AccessPolicy::repo_path(repo.path()).unwrap();
+    fs::create_dir_all(policy.parent().unwrap()).unwrap();
+    fs::write(
+        &policy,
+        r#"{ "roles": { "dev": "contributor", "lead": "integrator", "agent-1": "admin" } }"#,
+    )
+    .unwrap();
+    let tokens = ApiTokens::default()
+        .with_token("dev", "dev-secret")
+        .with_token("lead", "lead-secret");
+    let mut server = HttpServer::new()
+        .with_tokens(tokens)
+        .with_repository("default", repo.path().to_str().unwrap())
+        .unwrap();
+
+    let response = server.handle(HttpRequest::new("GET", "/repos", "")).await;
+    assert_eq!(response.status, 401);
+    assert_eq!(response.json_body().unwrap()["error"]["category"], "authentication");
+    let response = server.handle(HttpRequest::new("GET", "/healthz", "")).await;
+    assert_eq!(response.status, 200);
+
+    let response = server
+        .handle(request_as("dev-secret", "POST", "/repos/default/sessions", r#"{"agent_id":"agent-1"}"#))
+        .await;
+    assert_eq!(response.status, 201);
+    let response = server
+        .handle(request_as("lead-secret", "POST", "/repos/default/sessions/agent-1/checkpoints", r#"{"reason":"safe"}"#))
+        .await;
+    assert_eq!(response.status, 201);
+
+    // Naming an admin agent does not lend its role to a contributor
+    let response = server
+        .handle(request_as("dev-secret", "POST", "/repos/default/sessions/agent-1/revert", ""))
+        .await;
+    assert_eq!(response.status, 403);
+    let message = response.json_body().unwrap()["error"]["message"].clone();
+    assert!(message.as_str().unwrap().starts_with("Permission denied for 'dev'"), "{message}");
+    let denied = server.repositories["default"].action_history.last().unwrap();
+    assert_eq!(denied.metadata["caller"], "dev");
+    assert_eq!(denied.metadata["role"], "contributor");
+
+    let response = server
+        .handle(request_as("lead-secret", "POST", "/repos/default/sessions/agent-1/revert", ""))
+        .await;
+    assert_eq!(response.status, 200);
+}
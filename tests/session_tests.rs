@@ -0,0 +1,192 @@
+//! Agent session lifecycle tests for the GDK system
+//!
+//! These tests cover sessions from start to end:
+//! - Starting twice, completing and restarting
+//! - Pausing, resuming and abandoning
+//! - Idle expiry, both lazy and explicit
+//! - Persisting sessions in the repository's session store
+
+mod common;
+
+use common::setup_controller;
+use gdk::agent::AgentWorkflowController;
+use gdk::core::GitWorkflowManager;
+use gdk::session::{SessionRecords, SessionState, SessionStore};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_complete_and_restart_session() {
+    let (_temp_dir, mut controller) = setup_controller();
+    let first = controller.start_agent_session("agent-1").await.unwrap();
+
+    // A live session is never silently replaced
+    let err = controller.start_agent_session("agent-1").await.unwrap_err();
+    assert_eq!(err.category(), "agent");
+
+    let point = controller
+        .create_spiral_checkpoint("agent-1", "safe point")
+        .await
+        .unwrap();
+    controller.revert_to_last_checkpoint("agent-1").await.unwrap();
+
+    let summary = controller.complete_agent_session("agent-1").unwrap();
+    assert_eq!(summary.session_id, first);
+    assert_eq!(summary.state, SessionState::Completed);
+    assert!(summary.end_time.is_some());
+    assert_eq!(summary.final_commit, Some(point.commit_hash));
+    assert!(!summary.converged);
+    assert_eq!(summary.total_actions, 2);
+    assert_eq!(summary.successful_actions, 2);
+
+    // Ended sessions reject operations and cannot be ended twice
+    assert!(controller.create_spiral_checkpoint("agent-1", "late").await.is_err());
+    assert!(controller.abandon_agent_session("agent-1").is_err());
+
+    let second = controller.start_agent_session("agent-1").await.unwrap();
+    assert_ne!(second, first);
+    let summary = controller.session_summary("agent-1").unwrap();
+    assert_eq!(summary.state, SessionState::Active);
+    assert_eq!(summary.total_actions, 0);
+    assert_eq!(controller.action_history.len(), 2);
+}
+
+#[tokio::test]
+async fn test_pause_resume_and_abandon() {
+    let (_temp_dir, mut controller) = setup_controller();
+    controller.start_agent_session("agent-1").await.unwrap();
+
+    controller.pause_agent_session("agent-1").unwrap();
+    assert!(controller.has_live_session("agent-1"));
+    let err = controller
+        .create_spiral_checkpoint("agent-1", "while paused")
+        .await
+        .unwrap_err();
+    assert_eq!(err.category(), "agent");
+    assert!(controller.pause_agent_session("agent-1").is_err());
+
+    controller.resume_agent_session("agent-1").unwrap();
+    controller
+        .create_spiral_checkpoint("agent-1", "after resume")
+        .await
+        .unwrap();
+    assert!(controller.resume_agent_session("agent-1").is_err());
+
+    let summary = controller.abandon_agent_session("agent-1").unwrap();
+    assert_eq!(summary.state, SessionState::Abandoned);
+    assert!(!controller.has_live_session("agent-1"));
+}
+
+#[tokio::test]
+async fn test_idle_sessions_expire() {
+    let (_temp_dir, controller) = setup_controller();
+    let mut controller = controller.with_idle_timeout(Some(Duration::from_secs(60)));
+    controller.start_agent_session("agent-1").await.unwrap();
+    controller.start_agent_session("agent-2").await.unwrap();
+    controller.start_agent_session("agent-3").await.unwrap();
+    controller.pause_agent_session("agent-3").unwrap();
+
+    for agent_id in ["agent-1", "agent-3"] {
+        let session = controller.active_sessions.get_mut(agent_id).unwrap();
+        session.start_time -= 600;
+        session.last_activity -= 600;
+    }
+
+    // Touching an idle session expires it
+    let err = controller.get_convergence_status("agent-1").await.unwrap_err();
+    assert_eq!(err.category(), "agent");
+    assert_eq!(controller.active_sessions["agent-1"].state, SessionState::Expired);
+
+    // Paused sessions expire too, and cannot be resumed afterwards
+    let expired = controller.expire_idle_sessions().unwrap();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].agent_id, "agent-3");
+    assert!(controller.resume_agent_session("agent-3").is_err());
+    assert!(controller.has_live_session("agent-2"));
+
+    let states: Vec<_> = controller
+        .list_sessions()
+        .into_iter()
+        .map(|s| (s.agent_id, s.state))
+        .collect();
+    assert_eq!(
+        states,
+        [
+            ("agent-1".to_string(), SessionState::Expired),
+            ("agent-2".to_string(), SessionState::Active),
+            ("agent-3".to_string(), SessionState::Expired),
+        ]
+    );
+
+    // An expired session may be replaced by a fresh one
+    controller.start_agent_session("agent-1").await.unwrap();
+    assert!(controller.has_live_session("agent-1"));
+}
+
+#[tokio::test]
+async fn test_sessions_persist_in_store() {
+    let (temp_dir, mut controller) = setup_controller();
+    let store = SessionStore::for_repo(temp_dir.path()).unwrap();
+    assert_eq!(store.path(), temp_dir.path().join(".git").join("gdk").join("sessions.json"));
+    assert_eq!(store.load().unwrap(), SessionRecords::default());
+
+    let session_id = controller.start_agent_session("agent-1").await.unwrap();
+    controller
+        .create_spiral_checkpoint("agent-1", "persisted")
+        .await
+        .unwrap();
+    store.save(&controller.session_records()).unwrap();
+
+    let manager = GitWorkflowManager::new(temp_dir.path().to_str().unwrap()).unwrap();
+    let mut restored = AgentWorkflowController::new(manager);
+    restored.restore_sessions(store.load().unwrap());
+
+    let summary = restored.session_summary("agent-1").unwrap();
+    assert_eq!(summary.session_id, session_id);
+    assert_eq!(summary.total_actions, 1);
+    assert_eq!(restored.active_sessions["agent-1"].revert_stack.len(), 1);
+
+    // A restored session carries on where it left off
+    restored.revert_to_last_checkpoint("agent-1").await.unwrap();
+    let summary = restored.complete_agent_session("agent-1").unwrap();
+    assert_eq!(summary.total_actions, 2);
+}
+
+#[tokio::test]
+async fn test_concurrent_invocations_merge_sessions() {
+    let (temp_dir, mut setup) = setup_controller();
+    let store = SessionStore::for_repo(temp_dir.path()).unwrap();
+    setup.start_agent_session("agent-1").await.unwrap();
+    store.save(&setup.session_records()).unwrap();
+
+    // Two invocations load the same sessions and each works on its own agent
+    let loaded = store.load().unwrap();
+    let open = || {
+        let manager = GitWorkflowManager::new(temp_dir.path().to_str().unwrap()).unwrap();
+        let mut controller = AgentWorkflowController::new(manager);
+        controller.restore_sessions(loaded.clone());
+        controller
+    };
+    let mut first = open();
+    let mut second = open();
+
+    first
+        .create_spiral_checkpoint("agent-1", "first invocation")
+        .await
+        .unwrap();
+    second.start_agent_session("agent-2").await.unwrap();
+    second
+        .create_spiral_checkpoint("agent-2", "second invocation")
+        .await
+        .unwrap();
+
+    store.save(&first.session_records().changes_since(&loaded)).unwrap();
+    store.save(&second.session_records().changes_since(&loaded)).unwrap();
+
+    // The second save keeps the first invocation's agent-1 changes
+    let merged = store.load().unwrap();
+    assert_eq!(merged.sessions.len(), 2);
+    assert_eq!(merged.sessions["agent-1"].revert_stack.len(), 1);
+    assert_eq!(merged.sessions["agent-2"].revert_stack.len(), 1);
+    assert_eq!(merged.action_history.len(), 2);
+    assert!(!store.path().with_extension("json.lock").exists());
+}
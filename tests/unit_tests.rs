@@ -90,6 +90,7 @@ fn test_convergence_metrics() {
         test_pass_rate: 0.85,
         quality_trend: vec![0.6, 0.7, 0.75, 0.8, 0.85],
         is_converged: true,
+        ..Default::default()
     };
     
     // Test that convergence detection is reasonable
@@ -170,6 +171,7 @@ fn test_commit_node() {
             test_pass_rate: 1.0,
             quality_trend: vec![0.85],
             is_converged: true,
+            ..Default::default()
         },
     };
     
@@ -271,6 +273,7 @@ fn test_serialization_roundtrip() -> GdkResult<()> {
         test_pass_rate: 0.92,
         quality_trend: vec![0.6, 0.7, 0.8, 0.9],
         is_converged: true,
+        ..Default::default()
     };
     let json = serde_json::to_string(&convergence).unwrap();
     let deserialized: ConvergenceMetrics = serde_json::from_str(&json).unwrap();
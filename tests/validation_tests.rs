@@ -0,0 +1,529 @@
+//! Validation suite tests for the GDK system
+//!
+//! These tests exercise validators against throwaway repositories:
+//! - Content-addressed result caching across runs and tool upgrades
+//! - Cache eviction bounds
+//! - Language presets, output parsers and project root detection
+//! - Progress event streaming
+//! - Failure classification, retries for transient failures and bounded
+//!   timeouts
+
+use gdk::validation::{
+    detect_project_roots, FailureClass, OutputParser, OutputStream, ProjectKind, RetryPolicy,
+    TransientReason, ValidationEvent, ValidationRules, ValidationSuite, Validator,
+};
+use gdk::validation_cache::{ValidationCache, ValidationCacheConfig};
+use gdk::GdkResult;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::test;
+
+/// Helper function to create a git repository containing a single file
+fn setup_repo() -> (TempDir, String) {
+    let temp_dir = TempDir::new().unwrap();
+    git2::Repository::init(temp_dir.path()).unwrap();
+    fs::write(temp_dir.path().join("lib.rs"), "pub fn answer() -> u32 { 42 }\n").unwrap();
+    let repo_path = temp_dir.path().to_str().unwrap().to_string();
+    (temp_dir, repo_path)
+}
+
+/// Validator that appends a line to `log` every time it actually runs
+fn counting_validator(name: &str, log: &Path) -> Validator {
+    Validator {
+        name: name.to_string(),
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), format!("echo run >> '{}'", log.display())],
+        working_dir: None,
+        timeout_seconds: 10,
+        weight: 1.0,
+        is_required: true,
+        parser: OutputParser::ExitCode,
+        retry: RetryPolicy::none(),
+        version_command: None,
+    }
+}
+
+/// Validator that prints `output` and exits with `exit_code`
+fn scripted_validator(name: &str, output: &str, exit_code: i32, parser: OutputParser) -> Validator {
+    Validator {
+        name: name.to_string(),
+        command: "sh".to_string(),
+        args: vec!["-c".to_string(), format!("printf '%s\\n' '{output}'; exit {exit_code}")],
+        working_dir: None,
+        timeout_seconds: 10,
+        weight: 1.0,
+        is_required: false,
+        parser,
+        retry: RetryPolicy::none(),
+        version_command: None,
+    }
+}
+
+fn run_count(log: &Path) -> usize {
+    fs::read_to_string(log).map(|s| s.lines().count()).unwrap_or(0)
+}
+
+#[test]
+async fn test_validation_cache_reuses_identical_tree() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+    let scratch = TempDir::new().unwrap();
+    let log = scratch.path().join("runs.log");
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(counting_validator("counter", &log));
+    suite.set_cache(ValidationCacheConfig {
+        cache_dir: Some(scratch.path().join("cache").to_string_lossy().to_string()),
+        ..Default::default()
+    });
+
+    let first = suite.validate(&repo_path).await?;
+    assert!(first.passed);
+    assert!(!first.validator_results["counter"].cache_hit);
+    assert_eq!(run_count(&log), 1);
+
+    // Same tree, same validator, same toolchain: served from cache
+    let second = suite.validate(&repo_path).await?;
+    assert!(second.validator_results["counter"].cache_hit);
+    assert_eq!(second.validator_results["counter"].score, 1.0);
+    assert_eq!(run_count(&log), 1);
+
+    // Changing the tree invalidates the key
+    fs::write(Path::new(&repo_path).join("lib.rs"), "pub fn answer() -> u32 { 43 }\n").unwrap();
+    let third = suite.validate(&repo_path).await?;
+    assert!(!third.validator_results["counter"].cache_hit);
+    assert_eq!(run_count(&log), 2);
+
+    Ok(())
+}
+
+#[test]
+async fn test_validation_cache_keys_on_the_tool_version() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+    let scratch = TempDir::new().unwrap();
+    let log = scratch.path().join("runs.log");
+    let version = scratch.path().join("tool-version");
+    fs::write(&version, "tool 1.0\n").unwrap();
+
+    // The probe reports the tool the runner launches, not the runner
+    let mut validator = counting_validator("counter", &log);
+    validator.version_command = Some(vec!["cat".to_string(), version.to_string_lossy().to_string()]);
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(validator);
+    suite.set_cache(ValidationCacheConfig {
+        cache_dir: Some(scratch.path().join("cache").to_string_lossy().to_string()),
+        ..Default::default()
+    });
+
+    suite.validate(&repo_path).await?;
+    assert!(suite.validate(&repo_path).await?.validator_results["counter"].cache_hit);
+
+    fs::write(&version, "tool 2.0\n").unwrap();
+    let upgraded = suite.validate(&repo_path).await?;
+    assert!(!upgraded.validator_results["counter"].cache_hit);
+    assert_eq!(run_count(&log), 2);
+
+    Ok(())
+}
+
+#[test]
+async fn test_validation_cache_eviction_bound() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+    let scratch = TempDir::new().unwrap();
+    let log = scratch.path().join("runs.log");
+
+    let config = ValidationCacheConfig {
+        cache_dir: Some(scratch.path().join("cache").to_string_lossy().to_string()),
+        max_entries: 2,
+        ..Default::default()
+    };
+
+    let mut suite = ValidationSuite::new();
+    for name in ["a", "b", "c", "d"] {
+        suite.add_validator(counting_validator(name, &log));
+    }
+    suite.set_cache(config.clone());
+    suite.validate(&repo_path).await?;
+
+    let cache = ValidationCache::open(&config, &repo_path)?;
+    assert!(cache.len() <= 2);
+
+    Ok(())
+}
+
+#[test]
+async fn test_output_parsers_score_tool_summaries() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(scripted_validator(
+        "pytest",
+        "===== 2 failed, 8 passed in 0.12s =====",
+        1,
+        OutputParser::Pytest,
+    ));
+    suite.add_validator(scripted_validator(
+        "jest",
+        "Tests:       1 failed, 3 passed, 4 total",
+        1,
+        OutputParser::NodeTest,
+    ));
+    suite.add_validator(scripted_validator(
+        "ruff",
+        "Found 4 errors.",
+        1,
+        OutputParser::Ruff,
+    ));
+    // Auto resolves from the scoped name
+    suite.add_validator(scripted_validator(
+        "services/api:mypy",
+        "Found 3 errors in 1 file (checked 4 source files)",
+        1,
+        OutputParser::Auto,
+    ));
+
+    let result = suite.validate(&repo_path).await?;
+    let score = |name: &str| result.validator_results[name].score;
+
+    assert!((score("pytest") - 0.8).abs() < 1e-9);
+    assert!((score("jest") - 0.75).abs() < 1e-9);
+    assert!((score("ruff") - 0.8).abs() < 1e-9);
+    assert!((score("services/api:mypy") - 0.7).abs() < 1e-9);
+
+    Ok(())
+}
+
+#[test]
+async fn test_polyglot_preset_scopes_validators_to_roots() -> GdkResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+    fs::create_dir_all(root.join("crates/core")).unwrap();
+    fs::write(root.join("crates/core/Cargo.toml"), "[package]\n").unwrap();
+    fs::create_dir_all(root.join("services/api")).unwrap();
+    fs::write(root.join("services/api/pyproject.toml"), "[project]\n").unwrap();
+    fs::create_dir_all(root.join("web/node_modules/dep")).unwrap();
+    fs::write(root.join("web/package.json"), "{}").unwrap();
+    fs::write(root.join("web/node_modules/dep/package.json"), "{}").unwrap();
+    fs::create_dir_all(root.join("tools/gen")).unwrap();
+    fs::write(root.join("tools/gen/go.mod"), "module gen\n").unwrap();
+
+    let repo_path = root.to_str().unwrap();
+    let roots = detect_project_roots(repo_path)?;
+    let found: Vec<(&str, ProjectKind)> = roots
+        .iter()
+        .map(|r| (r.relative_path.as_str(), r.kind))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (".", ProjectKind::Rust),
+            ("services/api", ProjectKind::Python),
+            ("tools/gen", ProjectKind::Go),
+            ("web", ProjectKind::Node),
+        ]
+    );
+
+    let suite = ValidationSuite::polyglot_default(repo_path)?;
+    let pytest = suite
+        .validators
+        .iter()
+        .find(|v| v.name == "services/api:pytest")
+        .expect("pytest scoped to services/api");
+    assert!(pytest.working_dir.as_deref().unwrap().ends_with("services/api"));
+    assert!(suite.validators.iter().any(|v| v.name == "web:tsc"));
+    assert!(suite.validators.iter().any(|v| v.name == "tools/gen:go_test"));
+    assert!(suite.validators.iter().any(|v| v.name == ".:cargo_test"));
+    assert!(!suite.validators.iter().any(|v| v.name.starts_with("crates/core:")));
+
+    Ok(())
+}
+
+#[test]
+async fn test_validation_progress_events() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut required = scripted_validator("broken", "boom", 1, OutputParser::ExitCode);
+    required.is_required = true;
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(scripted_validator("first", "hello", 0, OutputParser::ExitCode));
+    suite.add_validator(required);
+    suite.add_validator(scripted_validator("never", "unused", 0, OutputParser::ExitCode));
+    suite.set_rules(ValidationRules {
+        fail_fast: true,
+        parallel_execution: false,
+        ..Default::default()
+    });
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let result = suite.validate_with_progress(&repo_path, tx).await?;
+    assert!(!result.passed);
+
+    let mut events = Vec::new();
+    while let Some(event) = rx.recv().await {
+        events.push(event);
+    }
+
+    assert_eq!(events[0], ValidationEvent::Started { validator: "first".to_string() });
+    assert!(events.contains(&ValidationEvent::OutputLine {
+        validator: "first".to_string(),
+        stream: OutputStream::Stdout,
+        line: "hello".to_string(),
+    }));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        ValidationEvent::Finished { validator, passed: false, .. } if validator == "broken"
+    )));
+    assert!(matches!(
+        events.last(),
+        Some(ValidationEvent::Skipped { validator, .. }) if validator == "never"
+    ));
+
+    Ok(())
+}
+
+#[test]
+async fn test_invalid_utf8_output_is_decoded_lossily() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut validator = scripted_validator("binary", "", 0, OutputParser::ExitCode);
+    validator.args = vec!["-c".to_string(), "printf 'bad \\377 byte\\r\\nafter\\n'".to_string()];
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(validator);
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let result = suite.validate_with_progress(&repo_path, tx).await?;
+    assert!(result.validator_results["binary"].passed);
+
+    let mut lines = Vec::new();
+    while let Some(event) = rx.recv().await {
+        if let ValidationEvent::OutputLine { line, .. } = event {
+            lines.push(line);
+        }
+    }
+    assert_eq!(lines, ["bad \u{FFFD} byte", "after"]);
+
+    Ok(())
+}
+
+#[test]
+async fn test_validation_timeout_event() {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(Validator {
+        name: "sleepy".to_string(),
+        command: "sleep".to_string(),
+        args: vec!["5".to_string()],
+        working_dir: None,
+        timeout_seconds: 1,
+        weight: 1.0,
+        is_required: true,
+        parser: OutputParser::ExitCode,
+        retry: RetryPolicy::none(),
+        version_command: None,
+    });
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _ = suite.validate_with_progress(&repo_path, tx).await;
+
+    let mut timed_out = false;
+    while let Some(event) = rx.recv().await {
+        if let ValidationEvent::TimedOut { validator, timeout_seconds } = event {
+            assert_eq!(validator, "sleepy");
+            assert_eq!(timeout_seconds, 1);
+            timed_out = true;
+        }
+    }
+    assert!(timed_out);
+}
+
+/// Retry policy with short backoffs so tests stay fast
+fn quick_retry(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff_ms: 10,
+        backoff_multiplier: 2.0,
+        max_backoff_ms: 50,
+        retry_timeouts: true,
+    }
+}
+
+#[test]
+async fn test_transient_failure_is_retried() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+    let scratch = TempDir::new().unwrap();
+    let marker = scratch.path().join("locked-once");
+
+    // Fails with cargo's lock message the first time, then succeeds
+    let mut validator = scripted_validator("locked", "", 0, OutputParser::ExitCode);
+    validator.args = vec![
+        "-c".to_string(),
+        format!(
+            "if [ -e '{m}' ]; then exit 0; fi; touch '{m}'; echo 'Blocking waiting for file lock on package cache' >&2; exit 101",
+            m = marker.display()
+        ),
+    ];
+    validator.retry = quick_retry(3);
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(validator);
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let result = suite.validate_with_progress(&repo_path, tx).await?;
+    let locked = &result.validator_results["locked"];
+    assert!(locked.passed);
+    assert_eq!(locked.attempts.len(), 2);
+    assert_eq!(
+        locked.attempts[0].failure,
+        Some(FailureClass::Transient(TransientReason::LockContention))
+    );
+    assert_eq!(locked.attempts[0].backoff_ms, 10);
+    assert_eq!(locked.attempts[1].failure, None);
+
+    let mut retried = false;
+    while let Some(event) = rx.recv().await {
+        if let ValidationEvent::Retrying { validator, attempt, .. } = event {
+            assert_eq!(validator, "locked");
+            assert_eq!(attempt, 2);
+            retried = true;
+        }
+    }
+    assert!(retried);
+
+    Ok(())
+}
+
+#[test]
+async fn test_permanent_failure_is_not_retried() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut validator = scripted_validator("lint", "error: unused variable", 1, OutputParser::ExitCode);
+    validator.retry = quick_retry(3);
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(validator);
+
+    let result = suite.validate(&repo_path).await?;
+    let lint = &result.validator_results["lint"];
+    assert!(!lint.passed);
+    assert_eq!(lint.attempts.len(), 1);
+    assert_eq!(lint.attempts[0].failure, Some(FailureClass::Permanent));
+
+    Ok(())
+}
+
+#[test]
+async fn test_timeout_is_not_defeated_by_grandchildren() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    // The shell is killed on timeout, but its sleeping child keeps the pipes open
+    let mut validator = scripted_validator("stuck", "", 0, OutputParser::ExitCode);
+    validator.args = vec!["-c".to_string(), "echo started; sleep 30; echo done".to_string()];
+    validator.timeout_seconds = 1;
+    validator.retry = RetryPolicy::default();
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(validator);
+
+    let started = std::time::Instant::now();
+    let result = suite.validate(&repo_path).await?;
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+    // Timeouts are not retried unless the policy asks for it
+    let stuck = &result.validator_results["stuck"];
+    assert!(!stuck.passed);
+    assert_eq!(stuck.output, "started\n");
+    assert_eq!(stuck.attempts.len(), 1);
+    assert!(stuck.attempts[0].timed_out);
+
+    Ok(())
+}
+
+#[test]
+async fn test_exhausted_timeout_retries_report_failure() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+
+    let mut suite = ValidationSuite::new();
+    suite.add_validator(Validator {
+        name: "sleepy".to_string(),
+        command: "sleep".to_string(),
+        args: vec!["5".to_string()],
+        working_dir: None,
+        timeout_seconds: 1,
+        weight: 1.0,
+        is_required: false,
+        parser: OutputParser::ExitCode,
+        retry: quick_retry(2),
+        version_command: None,
+    });
+
+    let result = suite.validate(&repo_path).await?;
+    let sleepy = &result.validator_results["sleepy"];
+    assert!(!sleepy.passed);
+    assert_eq!(sleepy.score, 0.0);
+    assert_eq!(sleepy.attempts.len(), 2);
+    assert!(sleepy.attempts.iter().all(|a| a.timed_out
+        && a.failure == Some(FailureClass::Transient(TransientReason::Timeout))));
+
+    Ok(())
+}
+
+#[test]
+async fn test_failures_are_classified_conservatively() -> GdkResult<()> {
+    let (_repo_dir, repo_path) = setup_repo();
+    let lock = "echo 'Blocking waiting for file lock on package cache' >&2";
+    let cases = [
+        // Killed from outside GDK, e.g. by the OOM killer
+        ("killed", "kill -KILL $$".to_string(), FailureClass::Transient(TransientReason::Signal(9))),
+        ("terminated", "kill -TERM $$".to_string(), FailureClass::Transient(TransientReason::Signal(15))),
+        // A crashing or aborting test binary is a real failure
+        ("crashed", "kill -SEGV $$".to_string(), FailureClass::Permanent),
+        ("aborted", "kill -ABRT $$".to_string(), FailureClass::Permanent),
+        // An exit code that merely looks like SIGKILL through a shell
+        ("exit_137", "exit 137".to_string(), FailureClass::Permanent),
+        (
+            "allocation",
+            "echo 'memory allocation of 1048576 bytes failed' >&2; exit 134".to_string(),
+            FailureClass::Transient(TransientReason::OutOfMemory),
+        ),
+        (
+            "oom_test_name",
+            "echo 'test handles_out_of_memory ... FAILED'; echo 'out of memory' >&2; exit 101".to_string(),
+            FailureClass::Permanent,
+        ),
+        // Cargo waited for the lock, then ran and failed as usual
+        (
+            "waited_then_failed",
+            format!("{lock}; echo 'test result: FAILED. 1 passed; 1 failed'; echo 'error: test failed' >&2; exit 101"),
+            FailureClass::Permanent,
+        ),
+        ("still_locked", format!("{lock}; exit 101"), FailureClass::Transient(TransientReason::LockContention)),
+    ];
+
+    let mut suite = ValidationSuite::new();
+    for (name, script, _) in &cases {
+        let mut validator = scripted_validator(name, "", 0, OutputParser::ExitCode);
+        validator.args = vec!["-c".to_string(), script.clone()];
+        validator.retry = quick_retry(2);
+        suite.add_validator(validator);
+    }
+
+    let result = suite.validate(&repo_path).await?;
+    for (name, _, expected) in &cases {
+        let validator = &result.validator_results[*name];
+        assert!(!validator.passed, "{name}");
+        assert_eq!(validator.attempts[0].failure.as_ref(), Some(expected), "{name}");
+        let attempts = if expected.is_transient() { 2 } else { 1 };
+        assert_eq!(validator.attempts.len(), attempts, "{name}");
+    }
+
+    // Backoff between attempts is not execution time
+    let retried = &result.validator_results["killed"];
+    let ran: u64 = retried.attempts.iter().map(|a| a.execution_time_ms).sum();
+    assert_eq!(retried.execution_time_ms, ran);
+
+    Ok(())
+}